@@ -0,0 +1,144 @@
+//! Runs every search over the bundled maps of `benches/maps` and the presets
+//! of `scenarios`, one benchmark group per map: `cargo bench`, or
+//! `cargo bench -- maze` for a single map.
+//!
+//! Each benchmark runs all the queries of its map. The number of expanded
+//! nodes is printed along, as a change in speed often comes from exploring
+//! another part of the map rather than from exploring faster.
+
+use std::path::Path;
+use std::time::Duration;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use pathfinding::anytime::anytime_astar;
+use pathfinding::diff::{load_corpus, Scenario};
+use pathfinding::graph::GridGraph;
+use pathfinding::heuristic::Zero;
+use pathfinding::movement::MovementModel;
+use pathfinding::scenarios::Preset;
+use pathfinding::search::Searcher;
+use pathfinding::{astar, astar_graph_with_stats, diagonal_distance, manhattan_distance};
+
+/// Prints the total number of nodes expanded by a configuration on a map.
+fn report(scenario: &Scenario, name: &str, nodes_expanded: usize) {
+    eprintln!(
+        "{}/{}: {} nodes expanded",
+        scenario.name, name, nodes_expanded
+    );
+}
+
+/// Panics unless every query of the map has a path: a query that fails at
+/// once would time nothing but the error.
+fn check_queries(scenario: &Scenario) {
+    let is_solid = |row: usize, col: usize, grid: &Vec<Vec<i32>>| grid[row][col] != 0;
+    for &(start, end) in &scenario.queries {
+        if let Err(e) = astar(start, end, &scenario.grid, manhattan_distance, is_solid) {
+            panic!(
+                "{}: no path from {:?} to {:?}: {}",
+                scenario.name, start, end, e
+            );
+        }
+    }
+}
+
+fn bench_scenario(c: &mut Criterion, scenario: &Scenario) {
+    check_queries(scenario);
+    let grid = &scenario.grid;
+    let is_solid = |row: usize, col: usize, grid: &Vec<Vec<i32>>| grid[row][col] != 0;
+    let four_way = GridGraph::new(grid, is_solid);
+    let eight_way = GridGraph::new(grid, is_solid).with_movement(MovementModel::eight_way());
+    let mut group = c.benchmark_group(&scenario.name);
+
+    group.bench_function("astar", |b| {
+        b.iter(|| {
+            for &(start, end) in &scenario.queries {
+                let _ = astar(start, end, grid, manhattan_distance, is_solid);
+            }
+        })
+    });
+
+    let configurations = [
+        (
+            "astar_graph",
+            &four_way,
+            manhattan_distance as fn(_, _) -> u32,
+        ),
+        ("astar_graph_eight_way", &eight_way, diagonal_distance),
+    ];
+    for (name, graph, heuristic) in configurations {
+        let expanded = scenario
+            .queries
+            .iter()
+            .map(|&(start, end)| {
+                astar_graph_with_stats(graph, start, end, heuristic).nodes_expanded
+            })
+            .sum();
+        report(scenario, name, expanded);
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for &(start, end) in &scenario.queries {
+                    astar_graph_with_stats(graph, start, end, heuristic);
+                }
+            })
+        });
+    }
+
+    let expanded = scenario
+        .queries
+        .iter()
+        .map(|&(start, end)| astar_graph_with_stats(&four_way, start, end, Zero).nodes_expanded)
+        .sum();
+    report(scenario, "dijkstra", expanded);
+    group.bench_function("dijkstra", |b| {
+        b.iter(|| {
+            for &(start, end) in &scenario.queries {
+                astar_graph_with_stats(&four_way, start, end, Zero);
+            }
+        })
+    });
+
+    // The buffers of the searcher are reused between the queries.
+    let mut searcher = Searcher::new(manhattan_distance);
+    group.bench_function("searcher", |b| {
+        b.iter(|| {
+            for &(start, end) in &scenario.queries {
+                searcher.find(start, end, &four_way);
+            }
+        })
+    });
+
+    group.bench_function("anytime_astar", |b| {
+        b.iter(|| {
+            for &(start, end) in &scenario.queries {
+                let _ = anytime_astar(
+                    &four_way,
+                    start,
+                    end,
+                    manhattan_distance,
+                    Duration::from_millis(10),
+                );
+            }
+        })
+    });
+
+    group.finish();
+}
+
+fn benches(c: &mut Criterion) {
+    let maps = Path::new(env!("CARGO_MANIFEST_DIR")).join("benches/maps");
+    for scenario in load_corpus(&maps).expect("the bundled maps can be loaded") {
+        bench_scenario(c, &scenario);
+    }
+    for preset in Preset::ALL {
+        let scenario = Scenario {
+            name: format!("preset-{}", preset.name()),
+            grid: preset.grid().to_vec(),
+            queries: vec![preset.endpoints()],
+        };
+        bench_scenario(c, &scenario);
+    }
+}
+
+criterion_group!(search, benches);
+criterion_main!(search);
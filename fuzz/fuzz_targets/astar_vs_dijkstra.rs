@@ -0,0 +1,93 @@
+//! Differential fuzzing of A* against Dijkstra's algorithm (A* with the `Zero`
+//! heuristic) on random grids: both must agree on whether a path exists and
+//! on its cost, and the A* path must be a valid path of that cost.
+//!
+//! Both run through `AstarBuilder`, like the grid searches of the crate.
+//!
+//! Run with `cargo fuzz run astar_vs_dijkstra` from the repository root.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use pathfinding::builder::AstarBuilder;
+use pathfinding::heuristic::Zero;
+use pathfinding::movement::MovementModel;
+use pathfinding::path::validate_path;
+use pathfinding::{diagonal_distance, manhattan_distance};
+
+/// A search problem decoded from the fuzzer input.
+struct Input {
+    grid: Vec<Vec<i32>>,
+    diagonal: bool,
+    start: (i32, i32),
+    end: (i32, i32),
+}
+
+/// Decodes the fuzzer input: the width and height (1 to 16 cells), whether
+/// diagonal steps are allowed, the start and end cells, then one bit per cell,
+/// set for walls. Cells past the end of the input are free.
+fn decode(data: &[u8]) -> Option<Input> {
+    let (&[width, height, diagonal, r0, c0, r1, c1], walls) = data.split_first_chunk::<7>()?;
+    let (width, height) = (1 + width as usize % 16, 1 + height as usize % 16);
+    let cell = |row: u8, col: u8| {
+        (
+            (row as usize % height) as i32,
+            (col as usize % width) as i32,
+        )
+    };
+    let grid = (0..height)
+        .map(|row| {
+            (0..width)
+                .map(|col| {
+                    let bit = row * width + col;
+                    walls
+                        .get(bit / 8)
+                        .map_or(0, |byte| (byte >> (bit % 8)) as i32 & 1)
+                })
+                .collect()
+        })
+        .collect();
+    Some(Input {
+        grid,
+        diagonal: diagonal % 2 == 1,
+        start: cell(r0, c0),
+        end: cell(r1, c1),
+    })
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Some(Input {
+        grid,
+        diagonal,
+        start,
+        end,
+    }) = decode(data)
+    else {
+        return;
+    };
+    let (movement, heuristic): (_, fn(_, _) -> u32) = if diagonal {
+        (MovementModel::eight_way(), diagonal_distance)
+    } else {
+        (MovementModel::four_way(), manhattan_distance)
+    };
+    let search = AstarBuilder::new().movement(movement.clone());
+    let astar = search.clone().heuristic(heuristic).run(start, end, &grid);
+    let dijkstra = search.heuristic(Zero).run(start, end, &grid);
+
+    assert_eq!(
+        astar.cost, dijkstra.cost,
+        "A* and Dijkstra found different costs"
+    );
+    match (astar.path, dijkstra.path) {
+        (Ok(found), Ok(_)) => {
+            assert_eq!(
+                validate_path(&found, &grid, start, end, &movement),
+                Ok(found.cost()),
+                "A* returned an invalid path"
+            );
+        }
+        (Err(found), Err(best)) => assert_eq!(found, best, "the searches failed differently"),
+        (found, best) => panic!("A* found {:?}, Dijkstra {:?}", found, best),
+    }
+});
@@ -0,0 +1,53 @@
+/*
+ * C interface to the pathfinding crate, see `src/ffi.rs`.
+ *
+ * Build the shared library with
+ * `cargo rustc --lib --release --no-default-features --features ffi --crate-type cdylib`.
+ */
+
+#ifndef PATHFINDING_H
+#define PATHFINDING_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* A grid cell. */
+typedef struct PfCell {
+    int32_t row;
+    int32_t col;
+} PfCell;
+
+/* The results of pf_astar. */
+#define PF_OK 0
+#define PF_NO_PATH 1
+#define PF_START_OUT_OF_BOUNDS 2
+#define PF_GOAL_OUT_OF_BOUNDS 3
+#define PF_START_BLOCKED 4
+#define PF_GOAL_BLOCKED 5
+#define PF_BUFFER_TOO_SMALL 6
+#define PF_INVALID_ARGUMENT (-1)
+
+/*
+ * Searches the shortest path from `start` to `end` on a grid of `width` x
+ * `height` cells stored row by row, 0 for the free cells and anything else
+ * for walls, with diagonal steps if `diagonal`.
+ *
+ * The cells of the path, `start` and `end` included, are written to `path`
+ * (which may be NULL if `capacity` is 0), their number to `length` and the
+ * cost of the path to `cost` (unless NULL). If the path is longer than
+ * `capacity`, `length` and `cost` are still set and PF_BUFFER_TOO_SMALL is
+ * returned.
+ */
+int32_t pf_astar(const int32_t *cells, size_t width, size_t height, PfCell start, PfCell end,
+                 bool diagonal, PfCell *path, size_t capacity, size_t *length, uint32_t *cost);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* PATHFINDING_H */
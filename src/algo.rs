@@ -1,11 +1,25 @@
-use std::collections::BinaryHeap;
-use std::collections::HashMap;
-use std::collections::HashSet;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::hash::Hash;
+
+use crate::collections::HashMap;
+
+use crate::cell::Cell;
+use crate::coord::Coord;
+use crate::costgrid::CostGrid;
+use crate::graph::{self, CellGraph, Graph, Grid3Graph, GridGraph, IMPASSABLE};
+use crate::grid::Grid;
+use crate::grid3::{Connectivity, Grid3, Voxel};
+use crate::heuristic::Heuristic;
+use crate::movement::MovementModel;
+use crate::path::Path;
+use crate::search::{Observer, SearchError, SearchLimits, SearchResult, SearchState};
+use crate::world::{WorldGraph, WorldGrid};
 
 /// Reconstructs the path from start to end using the `came_from` map.
 /// It works by starting from the end and following the path backwards.
-/// Here we are taking in terms of the grid (vector of vectors) and not the
-/// actual coordinates or nodes of the graph.
+/// The nodes can be anything hashable: grid positions, 3D coordinates, hex
+/// coordinates or opaque node ids.
 ///
 /// ### Arguments
 ///
@@ -16,6 +30,10 @@ use std::collections::HashSet;
 ///
 /// A vector of nodes from start to end.
 ///
+/// ### Panics
+///
+/// If `came_from` contains a cycle, see `try_reconstruct_path`.
+///
 /// ### Example
 ///
 /// ```
@@ -30,22 +48,81 @@ use std::collections::HashSet;
 /// let path = reconstruct_path(&came_from, (1, 1));
 /// assert_eq!(path, vec![(0, 1), (0, 0), (1, 0), (1, 1)    ]);
 /// ```
-pub fn reconstruct_path(
-    came_from: &HashMap<(i32, i32), (i32, i32)>,
-    current: (i32, i32),
-) -> Vec<(i32, i32)> {
+///
+/// Any other node type works the same, for example opaque ids:
+///
+/// ```
+/// use pathfinding::reconstruct_path;
+/// use std::collections::HashMap;
+///
+/// let came_from = HashMap::from([("harbor", "market"), ("market", "castle")]);
+///
+/// assert_eq!(reconstruct_path(&came_from, "harbor"), vec!["castle", "market", "harbor"]);
+/// ```
+pub fn reconstruct_path<N>(came_from: &HashMap<N, N>, current: N) -> Vec<N>
+where
+    N: Copy + Eq + Hash,
+{
+    // Every node appears at most once on a path, a longer one went around in circles.
+    try_reconstruct_path(came_from, current, came_from.len() + 1)
+        .expect("came_from contains a cycle")
+}
+
+/// Like `reconstruct_path`, but gives up once the path has more than
+/// `max_len` nodes, so a `came_from` map with a cycle (or an unexpectedly long
+/// path) can't hang the caller.
+///
+/// ### Arguments
+///
+/// * `came_from` - A map of positions to their previous positions.
+/// * `current` - The current position.
+/// * `max_len` - The maximum number of nodes of the path.
+///
+/// ### Returns
+///
+/// A vector of nodes from start to end, or `None` if it would be longer than `max_len`.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::try_reconstruct_path;
+/// use std::collections::HashMap;
+///
+/// let came_from = HashMap::from([(2, 1), (1, 0)]);
+/// assert_eq!(try_reconstruct_path(&came_from, 2, 3), Some(vec![0, 1, 2]));
+/// assert_eq!(try_reconstruct_path(&came_from, 2, 2), None);
+///
+/// let cycle = HashMap::from([(2, 1), (1, 2)]);
+/// assert_eq!(try_reconstruct_path(&cycle, 2, 100), None);
+/// ```
+pub fn try_reconstruct_path<N>(
+    came_from: &HashMap<N, N>,
+    current: N,
+    max_len: usize,
+) -> Option<Vec<N>>
+where
+    N: Copy + Eq + Hash,
+{
     let mut total_path = vec![current];
     let mut current = current;
-    while came_from.contains_key(&current) {
-        current = came_from[&current];
+    while let Some(&previous) = came_from.get(&current) {
+        if total_path.len() >= max_len {
+            return None;
+        }
+        current = previous;
         total_path.push(current);
     }
+    if total_path.len() > max_len {
+        return None;
+    }
     total_path.reverse();
-    total_path
+    Some(total_path)
 }
 
 /// Get the neighbors of an element in the 2d grid (up, down, left, right) via additional predicate.
-/// The predicate is used to check if the neighbor is solid or not.
+/// This is `MovementModel::four_way`, see `movement` for other neighborhoods.
+/// The predicate is used to check if the neighbor is solid or not. It can be any
+/// closure, so it is free to capture its environment (an agent size, a cost table...).
 ///
 /// ### Arguments
 ///
@@ -80,42 +157,37 @@ pub fn reconstruct_path(
 /// assert_eq!(neighbors, vec![(1, 2), (2, 1), (3, 2), (2, 3)]);
 /// assert_eq!(neighbors.len(), 4);
 /// ```
-pub fn get_neighbors(
-    row: i32,
-    col: i32,
-    grid: &Vec<Vec<i32>>,
-    is_solid: fn(usize, usize, &Vec<Vec<i32>>) -> bool,
-) -> Vec<(i32, i32)> {
-    let mut neighbors = vec![];
-    if row > 0 && !is_solid(row as usize - 1, col as usize, grid) {
-        neighbors.push((row - 1, col));
-    }
-    if col > 0 && !is_solid(row as usize, col as usize - 1, grid) {
-        neighbors.push((row, col - 1));
-    }
-    if row < grid.len() as i32 - 1 && !is_solid(row as usize + 1, col as usize, grid) {
-        neighbors.push((row + 1, col));
-    }
-    if col < grid[0].len() as i32 - 1 && !is_solid(row as usize, col as usize + 1, grid) {
-        neighbors.push((row, col + 1));
-    }
-    neighbors
+pub fn get_neighbors<F>(row: i32, col: i32, grid: &Vec<Vec<i32>>, is_solid: F) -> Vec<(i32, i32)>
+where
+    F: Fn(usize, usize, &Vec<Vec<i32>>) -> bool,
+{
+    MovementModel::four_way()
+        .neighbors(row, col, grid, is_solid)
+        .into_iter()
+        .map(|(cell, _)| cell)
+        .collect()
 }
 
 /// A* - algorithm for finding the shortest path in an 2D grid array.
 /// It uses a heuristic function to estimate the distance to the end.
+/// This is `astar_graph` run on a `GridGraph` built from the grid and the predicate.
 ///
 /// ### Arguments
 ///
 /// * `start` - The start position.
 /// * `end` - The end position.
 /// * `grid` - The grid (consisting of vector of vectors).
-/// * `heuristic` - The heuristic function.
-/// * `is_cell_solid` - The predicate function to check if a node is solid or not.
+/// * `heuristic` - The heuristic (any `Heuristic`, closures included).
+/// * `is_cell_solid` - The predicate closure to check if a node is solid or not.
 ///
 /// ### Returns
 ///
-/// A vector of nodes from start to end. Same as the `reconstruct_path` function.
+/// The `Path` of nodes from start to end, with the cost of every node on it.
+/// If there is none, the `SearchError` tells why: an endpoint outside of the grid,
+/// an endpoint on a solid cell, or no path between them. The endpoints are
+/// checked in that order, the start first, before anything is searched, so
+/// bad coordinates never panic. When `start == end` on a free cell the path
+/// is that single cell, with a cost of `0`.
 ///
 /// ### Example
 ///
@@ -130,7 +202,7 @@ pub fn get_neighbors(
 ///     vec![1, 0, 0, 0, 1],
 ///     vec![1, 1, 1, 1, 1],
 /// ];
-/// 
+///
 /// let path = astar(
 ///     (1, 1),
 ///     (1, 3),
@@ -138,120 +210,746 @@ pub fn get_neighbors(
 ///     manhattan_distance,
 ///     |row, col, grid| grid[row][col] == 1,
 /// );
-/// 
+///
 /// println!("{:?}", path);
-/// 
-/// let expected_path = Some(vec![(1, 1), (2, 1), (3, 1), (3, 2), (3, 3), (2, 3), (1, 3)]);
-/// assert_eq!(path, expected_path);
+///
+/// let expected_path = vec![(1, 1), (2, 1), (3, 1), (3, 2), (3, 3), (2, 3), (1, 3)];
+/// assert_eq!(path.unwrap(), expected_path);
+/// ```
+///
+/// A failed search explains itself:
+///
+/// ```
+/// use pathfinding::astar;
+/// use pathfinding::manhattan_distance;
+/// use pathfinding::search::SearchError;
+///
+/// let grid = vec![
+///     vec![0, 1, 0],
+///     vec![0, 1, 0],
+/// ];
+/// let is_solid = |row: usize, col: usize, grid: &Vec<Vec<i32>>| grid[row][col] == 1;
+///
+/// assert_eq!(astar((0, 0), (0, 2), &grid, manhattan_distance, is_solid), Err(SearchError::NoPath));
+/// assert_eq!(astar((0, 0), (0, 1), &grid, manhattan_distance, is_solid), Err(SearchError::GoalBlocked));
+/// assert_eq!(astar((5, 0), (0, 2), &grid, manhattan_distance, is_solid), Err(SearchError::StartOutOfBounds));
+/// ```
+///
+/// Degenerate inputs have well-defined results too:
+///
 /// ```
-pub fn astar(
+/// use pathfinding::astar;
+/// use pathfinding::manhattan_distance;
+/// use pathfinding::search::SearchError;
+///
+/// let is_solid = |row: usize, col: usize, grid: &Vec<Vec<i32>>| grid[row][col] == 1;
+/// let ragged = vec![
+///     vec![0, 0, 0],
+///     vec![0],
+/// ];
+///
+/// let path = astar((1, 0), (1, 0), &ragged, manhattan_distance, is_solid).unwrap();
+/// assert_eq!((path.nodes(), path.cost()), (&[(1, 0)][..], 0));
+/// assert_eq!(astar((0, 1), (0, 1), &vec![vec![0, 1]], manhattan_distance, is_solid), Err(SearchError::StartBlocked));
+/// assert_eq!(astar((0, 0), (1, 2), &ragged, manhattan_distance, is_solid), Err(SearchError::GoalOutOfBounds));
+/// assert_eq!(astar((-1, 0), (0, 0), &ragged, manhattan_distance, is_solid), Err(SearchError::StartOutOfBounds));
+/// assert_eq!(astar((0, 0), (0, 0), &vec![], manhattan_distance, is_solid), Err(SearchError::StartOutOfBounds));
+/// assert_eq!(astar((0, 0), (0, 0), &vec![vec![]], manhattan_distance, is_solid), Err(SearchError::StartOutOfBounds));
+/// ```
+///
+/// The predicate may capture its environment, e.g. a set of cells that should be
+/// avoided in addition to the walls.
+///
+/// ```
+/// use pathfinding::astar;
+/// use pathfinding::manhattan_distance;
+///
+/// let grid = vec![vec![0; 3]; 3];
+/// let avoid = vec![(0, 1), (1, 1)];
+///
+/// let path = astar(
+///     (0, 0),
+///     (0, 2),
+///     &grid,
+///     manhattan_distance,
+///     |row, col, grid| grid[row][col] == 1 || avoid.contains(&(row as i32, col as i32)),
+/// );
+///
+/// assert_eq!(path.map(|path| path.len()), Ok(7));
+/// ```
+pub fn astar<H, F>(
     start: (i32, i32),
     end: (i32, i32),
     grid: &Vec<Vec<i32>>,
-    heuristic: fn((i32, i32), (i32, i32)) -> i32,
-    is_cell_solid: fn(usize, usize, &Vec<Vec<i32>>) -> bool,
-) -> Option<Vec<(i32, i32)>> {
-    let mut closed_set = HashSet::new();
-    let mut open_set = HashSet::new();
-    open_set.insert(start);
+    heuristic: H,
+    is_cell_solid: F,
+) -> Result<Path<(i32, i32)>, SearchError>
+where
+    H: Heuristic<(i32, i32)>,
+    F: Fn(usize, usize, &Vec<Vec<i32>>) -> bool,
+{
+    astar_with_stats(start, end, grid, heuristic, is_cell_solid).path
+}
 
-    let mut came_from = HashMap::<(i32, i32), (i32, i32)>::new();
+/// The error of a search between endpoints that are free (`Some(true)`),
+/// solid (`Some(false)`) or outside of the grid (`None`), the bounds being
+/// checked first. Every grid search validates its endpoints through it.
+pub(crate) fn endpoint_error(start: Option<bool>, end: Option<bool>) -> Result<(), SearchError> {
+    match (start, end) {
+        (None, _) => Err(SearchError::StartOutOfBounds),
+        (_, None) => Err(SearchError::GoalOutOfBounds),
+        (Some(false), _) => Err(SearchError::StartBlocked),
+        (_, Some(false)) => Err(SearchError::GoalBlocked),
+        _ => Ok(()),
+    }
+}
 
-    let mut g_score = HashMap::new();
-    g_score.insert(start, 0);
+/// Checks that both endpoints of a grid search are inside the grid and not solid.
+pub(crate) fn check_endpoints<F>(
+    start: (i32, i32),
+    end: (i32, i32),
+    grid: &Vec<Vec<i32>>,
+    is_cell_solid: &F,
+) -> Result<(), SearchError>
+where
+    F: Fn(usize, usize, &Vec<Vec<i32>>) -> bool,
+{
+    let free = |pos: (i32, i32)| {
+        let (row, col) = Coord::from(pos).to_usize()?;
+        grid.get(row)?.get(col)?;
+        Some(!is_cell_solid(row, col, grid))
+    };
+    endpoint_error(free(start), free(end))
+}
 
-    let mut f_score = HashMap::new();
-    f_score.insert(start, heuristic(start, end));
+/// Same as `astar`, but returns a `SearchResult` carrying the path cost and
+/// statistics about the search next to the path.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::astar_with_stats;
+/// use pathfinding::manhattan_distance;
+///
+/// let grid = vec![
+///     vec![0, 0, 0],
+///     vec![1, 1, 0],
+///     vec![0, 0, 0],
+/// ];
+///
+/// let result = astar_with_stats((0, 0), (2, 0), &grid, manhattan_distance, |row, col, grid| {
+///     grid[row][col] == 1
+/// });
+///
+/// assert_eq!(result.cost, Some(6));
+/// println!("expanded {} nodes in {:?}", result.nodes_expanded, result.duration);
+/// ```
+pub fn astar_with_stats<H, F>(
+    start: (i32, i32),
+    end: (i32, i32),
+    grid: &Vec<Vec<i32>>,
+    heuristic: H,
+    is_cell_solid: F,
+) -> SearchResult<(i32, i32)>
+where
+    H: Heuristic<(i32, i32)>,
+    F: Fn(usize, usize, &Vec<Vec<i32>>) -> bool,
+{
+    astar_with_limits(
+        start,
+        end,
+        grid,
+        heuristic,
+        is_cell_solid,
+        SearchLimits::default(),
+    )
+}
 
-    let mut open_set_heap = BinaryHeap::new();
-    open_set_heap.push((heuristic(start, end), start));
+/// Same as `astar_with_stats`, but stops with `SearchError::Aborted` when one of
+/// the `limits` is hit, so long searches can be bounded or interrupted.
+pub fn astar_with_limits<H, F>(
+    start: (i32, i32),
+    end: (i32, i32),
+    grid: &Vec<Vec<i32>>,
+    heuristic: H,
+    is_cell_solid: F,
+    limits: SearchLimits,
+) -> SearchResult<(i32, i32)>
+where
+    H: Heuristic<(i32, i32)>,
+    F: Fn(usize, usize, &Vec<Vec<i32>>) -> bool,
+{
+    if let Err(e) = check_endpoints(start, end, grid, &is_cell_solid) {
+        return SearchResult::failed(e);
+    }
+    astar_graph_with_limits(
+        &GridGraph::new(grid, is_cell_solid),
+        start,
+        end,
+        heuristic,
+        limits,
+    )
+}
 
-    while !open_set_heap.is_empty() {
-        let current: (i32, i32) = open_set_heap.pop().unwrap().1;
-        if current == end {
-            return Some(reconstruct_path(&came_from, current));
+/// Same as `astar`, but every step costs `move_cost(from, to)` instead of `1`,
+/// so costs can depend on the whole transition. The heuristic must not
+/// overestimate these costs, or the path may not be the cheapest one.
+///
+/// ### Arguments
+///
+/// * `start` - The start position.
+/// * `end` - The end position.
+/// * `grid` - The grid (consisting of vector of vectors).
+/// * `heuristic` - The heuristic (any `Heuristic`, closures included).
+/// * `is_cell_solid` - The predicate closure to check if a node is solid or not.
+/// * `move_cost` - The cost of a step between two neighboring cells.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::astar_with_cost;
+/// use pathfinding::manhattan_distance;
+///
+/// let grid = vec![vec![0; 3]; 2];
+/// let heights = vec![
+///     vec![0, 5, 0],
+///     vec![0, 1, 0],
+/// ];
+/// // Climbing costs the height gained on top of the step, descending is free.
+/// let climb = |from: (i32, i32), to: (i32, i32)| {
+///     let height = |(row, col): (i32, i32)| heights[row as usize][col as usize];
+///     1 + (height(to) - height(from)).max(0) as u32
+/// };
+///
+/// let path = astar_with_cost((0, 0), (0, 2), &grid, manhattan_distance, |row, col, grid| {
+///     grid[row][col] == 1
+/// }, climb);
+///
+/// // Around the hill (1 + 2 + 1 + 1) rather than over it (6 + 1).
+/// assert_eq!(path.unwrap(), vec![(0, 0), (1, 0), (1, 1), (1, 2), (0, 2)]);
+/// ```
+pub fn astar_with_cost<H, F, C>(
+    start: (i32, i32),
+    end: (i32, i32),
+    grid: &Vec<Vec<i32>>,
+    heuristic: H,
+    is_cell_solid: F,
+    move_cost: C,
+) -> Result<Path<(i32, i32)>, SearchError>
+where
+    H: Heuristic<(i32, i32)>,
+    F: Fn(usize, usize, &Vec<Vec<i32>>) -> bool,
+    C: Fn((i32, i32), (i32, i32)) -> u32,
+{
+    check_endpoints(start, end, grid, &is_cell_solid)?;
+    let graph = graph::with_step_cost(GridGraph::new(grid, is_cell_solid), move_cost);
+    astar_graph(&graph, start, end, heuristic)
+}
+
+/// Best-effort A*: like `astar_with_stats`, but if the end can't be reached the
+/// path leads to the reachable cell with the lowest heuristic to the end
+/// instead ("get as close as possible"). The end may be solid or outside of the
+/// grid, only a bad start is an error.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::astar_best_effort;
+/// use pathfinding::manhattan_distance;
+///
+/// // The target is walled in, walk up to the wall.
+/// let grid = vec![
+///     vec![0, 0, 0, 1, 0],
+///     vec![0, 0, 0, 1, 0],
+/// ];
+///
+/// let result = astar_best_effort((1, 0), (0, 4), &grid, manhattan_distance, |row, col, grid| {
+///     grid[row][col] == 1
+/// });
+///
+/// assert_eq!(result.path.unwrap(), vec![(1, 0), (0, 0), (0, 1), (0, 2)]);
+/// assert_eq!(result.cost, Some(3));
+/// ```
+pub fn astar_best_effort<H, F>(
+    start: (i32, i32),
+    end: (i32, i32),
+    grid: &Vec<Vec<i32>>,
+    heuristic: H,
+    is_cell_solid: F,
+) -> SearchResult<(i32, i32)>
+where
+    H: Heuristic<(i32, i32)>,
+    F: Fn(usize, usize, &Vec<Vec<i32>>) -> bool,
+{
+    match check_endpoints(start, end, grid, &is_cell_solid) {
+        Err(e @ (SearchError::StartOutOfBounds | SearchError::StartBlocked)) => {
+            SearchResult::failed(e)
         }
-        open_set.remove(&current);
-        closed_set.insert(current);
+        _ => astar_graph_best_effort(&GridGraph::new(grid, is_cell_solid), start, end, heuristic),
+    }
+}
 
-        for neighbor in get_neighbors(current.0, current.1, grid, is_cell_solid) {
-            if closed_set.contains(&neighbor) {
-                continue;
-            }
+/// A* on a grid of `Cell`s: solid cells and step costs come from the cells
+/// themselves, so no predicate is needed. See `Cell` for an example.
+///
+/// ### Arguments
+///
+/// * `start` - The start position.
+/// * `end` - The end position.
+/// * `cells` - The grid (consisting of vector of vectors of cells).
+/// * `heuristic` - The heuristic, called as `heuristic.estimate(node, end)`.
+///
+/// ### Returns
+///
+/// The `Path` of nodes from start to end, or the `SearchError` telling why
+/// there is none, like `astar`.
+pub fn astar_cells<C, H>(
+    start: (i32, i32),
+    end: (i32, i32),
+    cells: &Vec<Vec<C>>,
+    heuristic: H,
+) -> Result<Path<(i32, i32)>, SearchError>
+where
+    C: Cell,
+    H: Heuristic<(i32, i32)>,
+{
+    let graph = CellGraph::new(cells);
+    let free = |node| graph.get(node).map(Cell::passable);
+    endpoint_error(free(start), free(end))?;
+    astar_graph(&graph, start, end, heuristic)
+}
 
-            let tentative_g_score = g_score[&current] + 1;
+/// A* on a `WorldGrid` of `Cell`s, the endpoints and the path being in world
+/// coordinates, moving up/down/left/right (see `WorldGraph` for other
+/// movement models).
+///
+/// ### Arguments
+///
+/// * `start` - The start position, in world coordinates.
+/// * `end` - The end position, in world coordinates.
+/// * `world` - The grid and where it lies in the world.
+/// * `heuristic` - The heuristic, called as `heuristic.estimate(node, end)`.
+///
+/// ### Returns
+///
+/// The `Path` of world cells from start to end, or the `SearchError` telling
+/// why there is none, like `astar`.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::grid::Grid;
+/// use pathfinding::search::SearchError;
+/// use pathfinding::world::WorldGrid;
+/// use pathfinding::{astar_world, manhattan_distance};
+///
+/// // A map of the cells from (-10, -10) to (9, 9).
+/// let mut world = WorldGrid::new(Grid::new(20, 20), (-10, -10));
+/// world.set((0, 0), 1);
+///
+/// let path = astar_world((-1, 0), (1, 0), &world, manhattan_distance).unwrap();
+/// assert_eq!(path.cost(), 4);
+/// assert_eq!(path.first(), Some(&(-1, 0)));
+///
+/// let error = astar_world((-1, 0), (10, 0), &world, manhattan_distance);
+/// assert_eq!(error, Err(SearchError::GoalOutOfBounds));
+/// ```
+pub fn astar_world<C, H>(
+    start: (i32, i32),
+    end: (i32, i32),
+    world: &WorldGrid<C>,
+    heuristic: H,
+) -> Result<Path<(i32, i32)>, SearchError>
+where
+    C: Cell,
+    H: Heuristic<(i32, i32)>,
+{
+    let free = |node| world.get(node).map(Cell::passable);
+    endpoint_error(free(start), free(end))?;
+    astar_graph(&WorldGraph::new(world), start, end, heuristic)
+}
 
-            if !open_set.contains(&neighbor) {
-                open_set.insert(neighbor);
-                open_set_heap.push((heuristic(neighbor, end), neighbor));
-            } else if tentative_g_score >= g_score[&neighbor] {
-                continue;
-            }
+/// A* on a `CostGrid`: moving up/down/left/right into a cell costs the value
+/// stored in it, cells holding `IMPASSABLE` can't be entered.
+///
+/// ### Arguments
+///
+/// * `start` - The start position.
+/// * `end` - The end position.
+/// * `costs` - The costs of the cells.
+/// * `heuristic` - The heuristic, called as `heuristic.estimate(node, end)`.
+///
+/// ### Returns
+///
+/// The `Path` of nodes from start to end, or the `SearchError` telling why
+/// there is none, like `astar`.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::costgrid::CostGrid;
+/// use pathfinding::graph::IMPASSABLE;
+/// use pathfinding::grid::Grid;
+/// use pathfinding::search::SearchError;
+/// use pathfinding::{astar_weighted, manhattan_distance};
+///
+/// // Walking through the swamp in the middle is more expensive than going around.
+/// let costs = CostGrid::new(Grid::from_vec(vec![
+///     vec![1, 1, 1],
+///     vec![1, 9, 1],
+///     vec![1, 1, IMPASSABLE],
+/// ]))
+/// .unwrap();
+///
+/// let path = astar_weighted((1, 0), (1, 2), &costs, manhattan_distance).unwrap();
+/// assert_eq!(path, vec![(1, 0), (0, 0), (0, 1), (0, 2), (1, 2)]);
+/// assert_eq!(path.cost(), 4);
+///
+/// let error = astar_weighted((0, 0), (2, 2), &costs, manhattan_distance);
+/// assert_eq!(error, Err(SearchError::GoalBlocked));
+/// ```
+pub fn astar_weighted<H>(
+    start: (i32, i32),
+    end: (i32, i32),
+    costs: &CostGrid,
+    heuristic: H,
+) -> Result<Path<(i32, i32)>, SearchError>
+where
+    H: Heuristic<(i32, i32)>,
+{
+    costs
+        .as_grid()
+        .validate_endpoints(start, end, |&cost| cost != IMPASSABLE)?;
+    astar_graph(costs, start, end, heuristic)
+}
 
-            came_from.insert(neighbor, current);
-            g_score.insert(neighbor, tentative_g_score);
-            f_score.insert(neighbor, tentative_g_score + heuristic(neighbor, end));
-        }
-    }
-    None
+/// A* for an agent covering `size` x `size` cells, on the clearance map of a
+/// grid (see `Grid::clearance_map`): the nodes are the top left cells of the
+/// agent, which moves up/down/left/right through the cells of clearance `size`
+/// or more. One clearance map serves agents of every size.
+///
+/// ### Arguments
+///
+/// * `start` - The start position of the top left cell of the agent.
+/// * `end` - The end position of the top left cell of the agent.
+/// * `clearance` - The clearance map of the grid.
+/// * `size` - The width and height of the agent, in cells.
+/// * `heuristic` - The heuristic, called as `heuristic.estimate(node, end)`.
+///
+/// ### Returns
+///
+/// The `Path` of nodes from start to end, or the `SearchError` telling why
+/// there is none, like `astar`. An endpoint where the agent doesn't fit is
+/// blocked.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::grid::{ascii_walls, Grid};
+/// use pathfinding::search::SearchError;
+/// use pathfinding::{astar_clearance, manhattan_distance};
+///
+/// let map = "......\n......\n###.##\n......\n......";
+/// let grid = Grid::from_ascii(map, ascii_walls).unwrap().grid;
+/// let clearance = grid.clearance_map();
+///
+/// // A scout squeezes through the gap, a tank twice its size doesn't.
+/// let scout = astar_clearance((0, 0), (3, 0), &clearance, 1, manhattan_distance).unwrap();
+/// assert_eq!(scout.cost(), 9);
+/// let tank = astar_clearance((0, 0), (3, 0), &clearance, 2, manhattan_distance);
+/// assert_eq!(tank, Err(SearchError::NoPath));
+/// assert_eq!(
+///     astar_clearance((0, 5), (3, 0), &clearance, 2, manhattan_distance),
+///     Err(SearchError::StartBlocked)
+/// );
+/// ```
+pub fn astar_clearance<H>(
+    start: (i32, i32),
+    end: (i32, i32),
+    clearance: &Grid<u32>,
+    size: u32,
+    heuristic: H,
+) -> Result<Path<(i32, i32)>, SearchError>
+where
+    H: Heuristic<(i32, i32)>,
+{
+    let fits = |&cell: &u32| cell >= size;
+    clearance.validate_endpoints(start, end, fits)?;
+    let movement = MovementModel::four_way();
+    let graph = graph::from_fn(|(row, col)| {
+        movement.neighbors_in(row, col, clearance.height, clearance.width, |row, col| {
+            clearance.is_passable((row, col), fits)
+        })
+    });
+    astar_graph(&graph, start, end, heuristic)
+}
+
+/// A* through a 3D grid of `Cell`s, e.g. a voxel level with several floors.
+///
+/// ### Arguments
+///
+/// * `start` - The start voxel.
+/// * `end` - The end voxel.
+/// * `grid` - The grid of cells.
+/// * `connectivity` - Whether to step to the 6 or to all 26 voxels around.
+/// * `heuristic` - The heuristic, `Manhattan` for 6-connected and `Diagonal`
+///   for 26-connected movement. `Euclidean` is only admissible for
+///   `Connectivity::Six`: a 26-connected diagonal step costs `1`, less than
+///   its straight-line length.
+///
+/// ### Returns
+///
+/// The `Path` of voxels from start to end, or the `SearchError` telling why
+/// there is none, like `astar`.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::astar_3d;
+/// use pathfinding::grid3::{Connectivity, Grid3};
+/// use pathfinding::heuristic::Manhattan;
+///
+/// // Two floors with a ceiling in between, open at a single stairwell.
+/// let mut level: Grid3<i32> = Grid3::new(3, 3, 3);
+/// for x in 0..3 {
+///     for y in 0..3 {
+///         level.set((x, y, 1), 1);
+///     }
+/// }
+/// level.set((2, 2, 1), 0);
+///
+/// let path = astar_3d((0, 0, 0), (0, 0, 2), &level, Connectivity::Six, Manhattan).unwrap();
+///
+/// assert!(path.contains(&(2, 2, 1)));
+/// assert_eq!(path.cost(), 10);
+///
+/// // Across an open cube, two diagonal steps, shorter than `Euclidean` says.
+/// use pathfinding::heuristic::{Diagonal, Euclidean, Heuristic};
+///
+/// let open: Grid3<i32> = Grid3::new(3, 3, 3);
+/// let path = astar_3d((0, 0, 0), (2, 2, 2), &open, Connectivity::TwentySix, Diagonal).unwrap();
+/// assert_eq!(path.cost(), 2);
+/// assert_eq!(Diagonal.estimate((0, 0, 0), (2, 2, 2)), 2);
+/// assert_eq!(Euclidean.estimate((0, 0, 0), (2, 2, 2)), 3);
+/// ```
+pub fn astar_3d<C, H>(
+    start: Voxel,
+    end: Voxel,
+    grid: &Grid3<C>,
+    connectivity: Connectivity,
+    heuristic: H,
+) -> Result<Path<Voxel>, SearchError>
+where
+    C: Cell,
+    H: Heuristic<Voxel>,
+{
+    let free = |voxel| grid.get(voxel).map(Cell::passable);
+    endpoint_error(free(start), free(end))?;
+    astar_graph(&Grid3Graph::new(grid, connectivity), start, end, heuristic)
+}
+
+/// A* over any `Graph`: road networks, navmeshes, or grids through `GridGraph`.
+/// The heuristic gets the node and the end node and must not overestimate the
+/// remaining cost for the path to be the cheapest one.
+///
+/// ### Arguments
+///
+/// * `graph` - The graph to search.
+/// * `start` - The start node.
+/// * `end` - The end node.
+/// * `heuristic` - The heuristic, called as `heuristic.estimate(node, end)`.
+///
+/// ### Returns
+///
+/// The `Path` of nodes from start to end, or `SearchError::NoPath` if the end is unreachable.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::astar_graph;
+/// use pathfinding::graph::Graph;
+///
+/// // 0 --5--> 1 --1--> 3
+/// // 0 --1--> 2 --1--> 3
+/// struct Roads;
+///
+/// impl Graph for Roads {
+///     type Node = u8;
+///
+///     fn successors(&self, node: u8) -> Vec<(u8, u32)> {
+///         match node {
+///             0 => vec![(1, 5), (2, 1)],
+///             1 | 2 => vec![(3, 1)],
+///             _ => vec![],
+///         }
+///     }
+/// }
+///
+/// let path = astar_graph(&Roads, 0, 3, |_, _| 0);
+///
+/// assert_eq!(path.unwrap(), vec![0, 2, 3]);
+/// ```
+pub fn astar_graph<G, H>(
+    graph: &G,
+    start: G::Node,
+    end: G::Node,
+    heuristic: H,
+) -> Result<Path<G::Node>, SearchError>
+where
+    G: Graph,
+    H: Heuristic<G::Node>,
+{
+    astar_graph_with_stats(graph, start, end, heuristic).path
+}
+
+/// Same as `astar_graph`, but returns a `SearchResult` carrying the path cost
+/// and statistics about the search next to the path.
+///
+/// ### Arguments
+///
+/// * `graph` - The graph to search.
+/// * `start` - The start node.
+/// * `end` - The end node.
+/// * `heuristic` - The heuristic, called as `heuristic.estimate(node, end)`.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::astar_graph_with_stats;
+/// use pathfinding::graph::GridGraph;
+/// use pathfinding::manhattan_distance;
+///
+/// let grid = vec![vec![0; 4]; 4];
+/// let graph = GridGraph::new(&grid, |row, col, grid| grid[row][col] == 1);
+///
+/// let result = astar_graph_with_stats(&graph, (0, 0), (3, 3), manhattan_distance);
+///
+/// assert_eq!(result.cost, Some(6));
+/// assert_eq!(result.path.map(|path| path.len()), Ok(7));
+/// assert!(result.nodes_expanded >= 7);
+/// assert!(result.nodes_generated >= result.nodes_expanded);
+/// ```
+pub fn astar_graph_with_stats<G, H>(
+    graph: &G,
+    start: G::Node,
+    end: G::Node,
+    heuristic: H,
+) -> SearchResult<G::Node>
+where
+    G: Graph,
+    H: Heuristic<G::Node>,
+{
+    SearchState::new(graph, start, end, heuristic).finish()
+}
+
+/// Same as `astar_graph_with_stats`, but notifies `observer` of every expanded
+/// node and every relaxed edge, see `search::Observer`.
+pub fn astar_graph_with_observer<G, H, O>(
+    graph: &G,
+    start: G::Node,
+    end: G::Node,
+    heuristic: H,
+    observer: O,
+) -> SearchResult<G::Node>
+where
+    G: Graph,
+    H: Heuristic<G::Node>,
+    O: Observer<G::Node>,
+{
+    SearchState::new(graph, start, end, heuristic)
+        .with_observer(observer)
+        .finish()
+}
+
+/// Same as `astar_graph_with_stats`, but stops with `SearchError::Aborted` when
+/// one of the `limits` is hit.
+pub fn astar_graph_with_limits<G, H>(
+    graph: &G,
+    start: G::Node,
+    end: G::Node,
+    heuristic: H,
+    limits: SearchLimits,
+) -> SearchResult<G::Node>
+where
+    G: Graph,
+    H: Heuristic<G::Node>,
+{
+    SearchState::new(graph, start, end, heuristic)
+        .with_limits(limits)
+        .finish()
+}
+
+/// Best-effort A* over any `Graph`, see `astar_best_effort` and
+/// `SearchState::finish_best_effort`.
+pub fn astar_graph_best_effort<G, H>(
+    graph: &G,
+    start: G::Node,
+    end: G::Node,
+    heuristic: H,
+) -> SearchResult<G::Node>
+where
+    G: Graph,
+    H: Heuristic<G::Node>,
+{
+    SearchState::new(graph, start, end, heuristic).finish_best_effort()
 }
 
 /// The manhattan distance is the sum of the absolute differences of their Cartesian coordinates.
 /// In a right triangle, the manhattan distance is equal to the sum of the lengths of the legs.
-/// 
+///
 /// ### Arguments
-/// 
+///
 /// * `a` - The first position.
 /// * `b` - The second position.
-/// 
+///
 /// ### Returns
-/// 
-/// An integer representing the distance between the two positions.
-/// 
+///
+/// An unsigned integer representing the distance between the two positions.
+///
 /// ### Example
-/// 
+///
 /// ```
 /// use pathfinding::manhattan_distance;
-/// 
+///
 /// let distance = manhattan_distance((1, 1), (1, 3));
-/// 
+///
 /// assert_eq!(distance, 2);
 /// ```
 #[allow(dead_code)]
-pub fn manhattan_distance(a: (i32, i32), b: (i32, i32)) -> i32 {
-    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+pub fn manhattan_distance(a: (i32, i32), b: (i32, i32)) -> u32 {
+    (a.0 - b.0).unsigned_abs() + (a.1 - b.1).unsigned_abs()
 }
 
 /// Diagonal distance is the maximum of the absolute differences of their Cartesian coordinates.
 /// In a right triangle, the diagonal distance is equal to the length of the hypotenuse.
-/// 
+///
 /// ### Arguments
-/// 
+///
 /// * `a` - The first position.
 /// * `b` - The second position.
 ///
 /// ### Example
-/// 
-/// Simple diagonal distance from (0, 0) to (3, 4) 
-/// 
+///
+/// Simple diagonal distance from (0, 0) to (3, 4)
+///
 /// ```
 /// use pathfinding::diagonal_distance;
-/// 
+///
 /// let distance = diagonal_distance((0, 0), (3, 4));
-/// 
+///
 /// assert_eq!(distance, 4);
 /// ```
-/// 
+///
 /// But if we have a grid with a diagonal movement cost of 2, then the diagonal distance is 8.
-/// 
+///
 /// ```
 /// use pathfinding::diagonal_distance;
-/// 
+///
 /// let distance = diagonal_distance((0, 0), (3, 4)) * 2;
-/// 
+///
 /// assert_eq!(distance, 8);
 /// ```
 #[allow(dead_code)]
-pub fn diagonal_distance(a: (i32, i32), b: (i32, i32)) -> i32 {
-    (a.0 - b.0).abs().max((a.1 - b.1).abs())
+pub fn diagonal_distance(a: (i32, i32), b: (i32, i32)) -> u32 {
+    (a.0 - b.0).unsigned_abs().max((a.1 - b.1).unsigned_abs())
 }
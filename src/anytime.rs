@@ -0,0 +1,150 @@
+//! Anytime search under a time budget.
+
+use std::time::{Duration, Instant};
+
+use crate::graph::Graph;
+use crate::heuristic::{Heuristic, Scaled};
+use crate::path::Path;
+use crate::search::{SearchError, SearchLimits, SearchState, Step};
+
+/// The heuristic weights tried one after the other by `anytime_astar`, from the
+/// fastest (and least accurate) search to plain A*.
+pub const WEIGHTS: [f32; 5] = [4.0, 2.0, 1.5, 1.2, 1.0];
+
+/// The best path `anytime_astar` came up with before its time ran out.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnytimeResult<N> {
+    /// The nodes from the start to the goal, or to the node closest to the goal
+    /// when `complete` is `false`.
+    pub path: Path<N>,
+    /// The cost of `path`.
+    pub cost: u32,
+    /// Whether `path` reaches the goal.
+    pub complete: bool,
+    /// The heuristic weight of the search that produced `path`. With an
+    /// admissible heuristic a complete path costs at most `weight` times the
+    /// optimum, so `1.0` means the path is optimal.
+    pub weight: f32,
+    /// The number of nodes expanded over all the searches.
+    pub nodes_expanded: usize,
+    /// The wall-clock time spent.
+    pub duration: Duration,
+}
+
+/// Runs weighted A* with decreasing weights (see `WEIGHTS`) until plain A* finishes
+/// or the time budget expires, and returns the cheapest path found so far.
+///
+/// If the budget expires before any path was found, the path to the expanded
+/// node closest to the goal is returned instead, with `complete` set to `false`.
+///
+/// ### Arguments
+///
+/// * `graph` - The graph to search.
+/// * `start` - The start node.
+/// * `end` - The end node.
+/// * `heuristic` - The heuristic, called as `heuristic.estimate(node, end)`.
+/// * `budget` - How long the search may take.
+///
+/// ### Returns
+///
+/// The best path, or `SearchError::NoPath` if the goal is proven unreachable.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use pathfinding::anytime::anytime_astar;
+/// use pathfinding::graph::GridGraph;
+/// use pathfinding::manhattan_distance;
+///
+/// let grid = vec![vec![0; 20]; 20];
+/// let graph = GridGraph::new(&grid, |row, col, grid| grid[row][col] == 1);
+///
+/// let result = anytime_astar(&graph, (0, 0), (19, 19), manhattan_distance, Duration::from_secs(1)).unwrap();
+///
+/// assert!(result.complete);
+/// assert_eq!(result.cost, 38);
+/// assert_eq!(result.weight, 1.0);
+///
+/// // Without any time, the agent at least knows where it stands.
+/// let result = anytime_astar(&graph, (0, 0), (19, 19), manhattan_distance, Duration::ZERO).unwrap();
+///
+/// assert!(!result.complete);
+/// assert_eq!(result.path, vec![(0, 0)]);
+/// ```
+pub fn anytime_astar<G, H>(
+    graph: &G,
+    start: G::Node,
+    end: G::Node,
+    heuristic: H,
+    budget: Duration,
+) -> Result<AnytimeResult<G::Node>, SearchError>
+where
+    G: Graph,
+    H: Heuristic<G::Node>,
+{
+    let started = Instant::now();
+    let limits = SearchLimits {
+        deadline: Some(started + budget),
+        ..Default::default()
+    };
+    let mut best: Option<AnytimeResult<G::Node>> = None;
+    let mut nodes_expanded = 0;
+
+    for weight in WEIGHTS {
+        let weighted = Scaled(
+            |node: G::Node, goal: G::Node| heuristic.estimate(node, goal),
+            weight,
+        );
+        let mut state = SearchState::new(graph, start, end, weighted).with_limits(limits.clone());
+        let last = state.run();
+        nodes_expanded += state.nodes_expanded();
+        match last {
+            Step::Found { cost } => {
+                // Weights only decrease, so an equally cheap path comes with a tighter bound.
+                if best.as_ref().is_none_or(|best| cost <= best.cost) {
+                    best = Some(AnytimeResult {
+                        path: state.path().unwrap_or_else(|| Path::new(vec![], vec![])),
+                        cost,
+                        complete: true,
+                        weight,
+                        nodes_expanded: 0,
+                        duration: Duration::ZERO,
+                    });
+                }
+            }
+            // The weight doesn't change which nodes are reachable.
+            Step::Exhausted => return Err(SearchError::NoPath),
+            _ => {
+                if best.is_none() {
+                    best = state.closest_node().map(|closest| AnytimeResult {
+                        path: state
+                            .path_to(closest)
+                            .unwrap_or_else(|| Path::new(vec![], vec![])),
+                        cost: state.g_score(closest).unwrap_or_default(),
+                        complete: false,
+                        weight,
+                        nodes_expanded: 0,
+                        duration: Duration::ZERO,
+                    });
+                }
+                break;
+            }
+        }
+    }
+
+    // Not even the start could be expanded before the deadline.
+    let mut best = best.unwrap_or(AnytimeResult {
+        path: Path::new(vec![start], vec![0]),
+        cost: 0,
+        complete: start == end,
+        weight: WEIGHTS[0],
+        nodes_expanded: 0,
+        duration: Duration::ZERO,
+    });
+    best.nodes_expanded = nodes_expanded;
+    best.duration = started.elapsed();
+    Ok(best)
+}
@@ -0,0 +1,145 @@
+//! Breadth-first search: the path with the fewest steps, whatever they cost.
+
+use alloc::collections::VecDeque;
+#[cfg(not(feature = "std"))]
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use crate::collections::HashMap;
+use crate::graph::Graph;
+use crate::path::Path;
+use crate::reconstruct_path;
+use crate::search::{Observer, SearchError, SearchResult};
+
+/// Breadth-first search - visits the nodes in the order they were reached, so
+/// the path found has the fewest steps. The step costs are not looked at while
+/// searching, the path only carries them: on a graph where every step costs the
+/// same the path is also the cheapest, otherwise use `astar_graph`.
+///
+/// ### Arguments
+///
+/// * `graph` - The graph to search.
+/// * `start` - The start node.
+/// * `end` - The end node.
+///
+/// ### Returns
+///
+/// The `Path` from start to end, with the cost of every node on it, or
+/// `SearchError::NoPath` if `end` can't be reached.
+///
+/// ### Example
+///
+/// Both roads take two steps, breadth-first search takes the one it reached
+/// first even though it costs more:
+///
+/// ```
+/// use pathfinding::astar_graph;
+/// use pathfinding::bfs::breadth_first;
+/// use pathfinding::graph;
+///
+/// let roads = graph::from_fn(|node: u32| match node {
+///     0 => vec![(1, 5), (2, 1)],
+///     1 | 2 => vec![(3, 1)],
+///     _ => vec![],
+/// });
+///
+/// let path = breadth_first(&roads, 0, 3).unwrap();
+///
+/// assert_eq!(path, vec![0, 1, 3]);
+/// assert_eq!(path.cost(), 6);
+/// assert_eq!(astar_graph(&roads, 0, 3, |_, _| 0).unwrap(), vec![0, 2, 3]);
+/// ```
+pub fn breadth_first<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    end: G::Node,
+) -> Result<Path<G::Node>, SearchError> {
+    breadth_first_with_stats(graph, start, end).path
+}
+
+/// Same as `breadth_first`, but returns a `SearchResult` carrying the path
+/// cost and statistics about the search next to the path.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::bfs::breadth_first_with_stats;
+/// use pathfinding::graph::GridGraph;
+///
+/// let grid = vec![vec![0; 4]; 4];
+/// let graph = GridGraph::new(&grid, |row, col, grid| grid[row][col] == 1);
+///
+/// let result = breadth_first_with_stats(&graph, (0, 0), (3, 3));
+///
+/// assert_eq!(result.cost, Some(6));
+/// // Every cell closer to the start than the goal was visited first.
+/// assert!(result.nodes_expanded >= 10);
+/// ```
+pub fn breadth_first_with_stats<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    end: G::Node,
+) -> SearchResult<G::Node> {
+    breadth_first_with_observer(graph, start, end, ())
+}
+
+/// Same as `breadth_first_with_stats`, but notifies `observer` of every expanded node and
+/// of every node reached for the first time, see `search::Observer`. As the
+/// search has no heuristic, the f-score given to `on_expand` is the g-score.
+pub fn breadth_first_with_observer<G, O>(
+    graph: &G,
+    start: G::Node,
+    end: G::Node,
+    mut observer: O,
+) -> SearchResult<G::Node>
+where
+    G: Graph,
+    O: Observer<G::Node>,
+{
+    #[cfg(feature = "std")]
+    let started = Instant::now();
+    let mut queue = VecDeque::from([start]);
+    let mut came_from = HashMap::new();
+    let mut g_score = HashMap::new();
+    g_score.insert(start, 0);
+    let mut nodes_expanded = 0;
+    let mut found = false;
+
+    while let Some(current) = queue.pop_front() {
+        let g = g_score[&current];
+        nodes_expanded += 1;
+        observer.on_expand(current, g, g);
+        if current == end {
+            found = true;
+            break;
+        }
+        for (neighbor, cost) in graph.successors(current) {
+            if g_score.contains_key(&neighbor) {
+                continue;
+            }
+            observer.on_relax(current, neighbor);
+            came_from.insert(neighbor, current);
+            g_score.insert(neighbor, g.saturating_add(cost));
+            queue.push_back(neighbor);
+        }
+    }
+
+    let (path, cost) = if found {
+        let nodes = reconstruct_path(&came_from, end);
+        let costs = nodes.iter().map(|node| g_score[node]).collect();
+        (Ok(Path::new(nodes, costs)), Some(g_score[&end]))
+    } else {
+        (Err(SearchError::NoPath), None)
+    };
+    SearchResult {
+        path,
+        cost,
+        nodes_expanded,
+        nodes_generated: g_score.len(),
+        #[cfg(feature = "std")]
+        duration: started.elapsed(),
+        #[cfg(not(feature = "std"))]
+        duration: Duration::ZERO,
+    }
+}
@@ -0,0 +1,136 @@
+//! Grids of booleans packed one bit per cell, for the obstacle masks of large
+//! maps.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::grid::{Grid, MemoryUsage};
+
+/// A `width` x `height` grid of booleans, stored row by row with one bit per
+/// cell. It has the same `get`/`set`/`fill` API as `Grid`, and converts from
+/// and to `Grid<i32>`: non-zero cells are `true`, and `true` becomes `1`.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::bitgrid::BitGrid;
+/// use pathfinding::grid::Grid;
+///
+/// let mut walls = BitGrid::new(3, 2);
+/// walls.set(0, 1, true);
+///
+/// assert_eq!(walls.get(0, 1), Some(true));
+/// assert_eq!(walls.get(1, 1), Some(false));
+/// assert_eq!(walls.get(2, 0), None);
+///
+/// walls.fill(true);
+/// assert_eq!(walls.count_ones(), 6);
+/// walls.fill(false);
+/// walls.set(0, 1, true);
+///
+/// let grid = Grid::from(&walls);
+/// assert_eq!(grid.to_vec(), vec![vec![0, 1, 0], vec![0, 0, 0]]);
+/// assert_eq!(BitGrid::from(&grid), walls);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    /// The number of columns.
+    pub width: usize,
+    /// The number of rows.
+    pub height: usize,
+    bits: Vec<u64>,
+}
+
+impl BitGrid {
+    /// Creates a grid with every cell `false`.
+    pub fn new(width: usize, height: usize) -> Self {
+        BitGrid {
+            width,
+            height,
+            bits: vec![0; (width * height).div_ceil(64)],
+        }
+    }
+
+    /// How much memory the grid takes, see `Grid::memory_usage`.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage {
+            cells: self.width * self.height,
+            bytes: core::mem::size_of::<Self>() + self.bits.capacity() * 8,
+            chunks: 0,
+        }
+    }
+
+    /// The word and the mask of the bit of `row`, `col`, `None` if it is
+    /// outside of the grid.
+    fn bit(&self, row: usize, col: usize) -> Option<(usize, u64)> {
+        if row < self.height && col < self.width {
+            let index = row * self.width + col;
+            Some((index / 64, 1 << (index % 64)))
+        } else {
+            None
+        }
+    }
+
+    /// The cell at `row`, `col`, `None` if it is outside of the grid.
+    pub fn get(&self, row: usize, col: usize) -> Option<bool> {
+        self.bit(row, col)
+            .map(|(word, mask)| self.bits[word] & mask != 0)
+    }
+
+    /// Replaces the cell at `row`, `col` and returns the old value, `None` if
+    /// it is outside of the grid.
+    pub fn set(&mut self, row: usize, col: usize, value: bool) -> Option<bool> {
+        let (word, mask) = self.bit(row, col)?;
+        let old = self.bits[word] & mask != 0;
+        if value {
+            self.bits[word] |= mask;
+        } else {
+            self.bits[word] &= !mask;
+        }
+        Some(old)
+    }
+
+    /// Sets every cell to `value`.
+    pub fn fill(&mut self, value: bool) {
+        self.bits.fill(if value { u64::MAX } else { 0 });
+        // The bits past the last cell stay clear, for `Eq` and `Hash`.
+        let used = (self.width * self.height) % 64;
+        if used > 0 {
+            if let Some(last) = self.bits.last_mut() {
+                *last &= (1 << used) - 1;
+            }
+        }
+    }
+
+    /// The number of `true` cells.
+    pub fn count_ones(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+}
+
+impl From<&Grid<i32>> for BitGrid {
+    fn from(grid: &Grid<i32>) -> Self {
+        let mut bits = BitGrid::new(grid.width, grid.height);
+        for ((row, col), &cell) in grid.enumerate_cells() {
+            bits.set(row, col, cell != 0);
+        }
+        bits
+    }
+}
+
+impl From<&BitGrid> for Grid<i32> {
+    fn from(bits: &BitGrid) -> Self {
+        let mut grid = Grid::new(bits.width, bits.height);
+        for row in 0..bits.height {
+            for col in 0..bits.width {
+                if bits.get(row, col) == Some(true) {
+                    grid.set(row, col, 1);
+                }
+            }
+        }
+        grid
+    }
+}
@@ -0,0 +1,234 @@
+//! Builder-style configuration of grid searches.
+
+use alloc::vec::Vec;
+
+use crate::graph::{Graph, GridGraph};
+use crate::heuristic::Heuristic;
+use crate::movement::MovementModel;
+use crate::search::{SearchError, SearchLimits, SearchResult, SearchState, TieBreak};
+use crate::{check_endpoints, manhattan_distance};
+
+type Cell = (i32, i32);
+
+/// The type of the step cost before one is set, the costs of the movement model are used.
+type ModelCost = fn(Cell, Cell) -> u32;
+
+/// The default solidity predicate: every non-zero cell is solid.
+type NonZeroSolid = fn(usize, usize, &Vec<Vec<i32>>) -> bool;
+
+/// Collects the options of a grid search and runs it, instead of passing them
+/// all to a function.
+///
+/// By default the search moves up/down/left/right, every step costs `1`,
+/// non-zero cells are solid and `manhattan_distance` is the heuristic. With
+/// other movement models use a heuristic that doesn't overestimate their moves
+/// (`diagonal_distance` for diagonal steps), or the paths may not be the shortest.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::builder::AstarBuilder;
+/// use pathfinding::diagonal_distance;
+/// use pathfinding::movement::MovementModel;
+///
+/// let grid = vec![
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+/// ];
+///
+/// let result = AstarBuilder::new().run((0, 0), (2, 2), &grid);
+/// assert_eq!(result.cost, Some(4));
+///
+/// let result = AstarBuilder::new()
+///     .movement(MovementModel::eight_way())
+///     .heuristic(diagonal_distance)
+///     .run((0, 0), (2, 2), &grid);
+/// assert_eq!(result.cost, Some(3));
+/// assert_eq!(result.path.unwrap().len(), 4);
+///
+/// let result = AstarBuilder::new()
+///     .movement(MovementModel::knight())
+///     .heuristic(|_, _| 0)
+///     .run((0, 0), (2, 1), &grid);
+/// assert_eq!(result.path.unwrap(), vec![(0, 0), (2, 1)]);
+///
+/// // Stepping down is twice as expensive as any other step.
+/// let result = AstarBuilder::new()
+///     .cost(|from: (i32, i32), to: (i32, i32)| if to.0 > from.0 { 2 } else { 1 })
+///     .run((0, 0), (2, 2), &grid);
+/// assert_eq!(result.cost, Some(6));
+/// ```
+#[derive(Debug, Clone)]
+pub struct AstarBuilder<H, C, S> {
+    heuristic: H,
+    step_cost: Option<C>,
+    is_solid: S,
+    movement: MovementModel,
+    limits: SearchLimits,
+    tie_break: TieBreak,
+    best_effort: bool,
+}
+
+impl AstarBuilder<ModelCost, ModelCost, NonZeroSolid> {
+    /// Creates a builder with the default options.
+    pub fn new() -> Self {
+        AstarBuilder {
+            heuristic: manhattan_distance,
+            step_cost: None,
+            is_solid: |row, col, grid| grid[row][col] != 0,
+            movement: MovementModel::four_way(),
+            limits: SearchLimits::default(),
+            tie_break: TieBreak::default(),
+            best_effort: false,
+        }
+    }
+}
+
+impl Default for AstarBuilder<ModelCost, ModelCost, NonZeroSolid> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H, C, S> AstarBuilder<H, C, S> {
+    /// Sets the heuristic, called as `heuristic.estimate(cell, end)`.
+    pub fn heuristic<H2>(self, heuristic: H2) -> AstarBuilder<H2, C, S>
+    where
+        H2: Heuristic<Cell>,
+    {
+        AstarBuilder {
+            heuristic,
+            step_cost: self.step_cost,
+            is_solid: self.is_solid,
+            movement: self.movement,
+            limits: self.limits,
+            tie_break: self.tie_break,
+            best_effort: self.best_effort,
+        }
+    }
+
+    /// Sets the cost of a step, called as `step_cost(from, to)`. It replaces
+    /// the costs of the movement model.
+    pub fn cost<C2>(self, step_cost: C2) -> AstarBuilder<H, C2, S>
+    where
+        C2: Fn(Cell, Cell) -> u32,
+    {
+        AstarBuilder {
+            heuristic: self.heuristic,
+            step_cost: Some(step_cost),
+            is_solid: self.is_solid,
+            movement: self.movement,
+            limits: self.limits,
+            tie_break: self.tie_break,
+            best_effort: self.best_effort,
+        }
+    }
+
+    /// Sets the predicate telling which cells are solid, see `astar`.
+    pub fn solid<S2>(self, is_solid: S2) -> AstarBuilder<H, C, S2>
+    where
+        S2: Fn(usize, usize, &Vec<Vec<i32>>) -> bool,
+    {
+        AstarBuilder {
+            heuristic: self.heuristic,
+            step_cost: self.step_cost,
+            is_solid,
+            movement: self.movement,
+            limits: self.limits,
+            tie_break: self.tie_break,
+            best_effort: self.best_effort,
+        }
+    }
+
+    /// Sets the movement model, see `MovementModel`.
+    pub fn movement(mut self, movement: MovementModel) -> Self {
+        self.movement = movement;
+        self
+    }
+
+    /// Shorthand for `MovementModel::eight_way` (`true`) or
+    /// `MovementModel::four_way` (`false`).
+    pub fn diagonal(self, diagonal: bool) -> Self {
+        self.movement(if diagonal {
+            MovementModel::eight_way()
+        } else {
+            MovementModel::four_way()
+        })
+    }
+
+    /// Sets the limits of the search, see `SearchLimits`.
+    pub fn limits(mut self, limits: SearchLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets which node is expanded first among equally good ones.
+    pub fn tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Whether to return the path to the closest reachable cell when the end
+    /// can't be reached, see `astar_best_effort`.
+    pub fn best_effort(mut self, best_effort: bool) -> Self {
+        self.best_effort = best_effort;
+        self
+    }
+}
+
+impl<H, C, S> AstarBuilder<H, C, S>
+where
+    H: Heuristic<Cell>,
+    C: Fn(Cell, Cell) -> u32,
+    S: Fn(usize, usize, &Vec<Vec<i32>>) -> bool,
+{
+    /// Searches a path from `start` to `end` in `grid` with the configured options.
+    pub fn run(&self, start: Cell, end: Cell, grid: &Vec<Vec<i32>>) -> SearchResult<Cell> {
+        match check_endpoints(start, end, grid, &self.is_solid) {
+            Err(e @ (SearchError::StartOutOfBounds | SearchError::StartBlocked)) => {
+                return SearchResult::failed(e)
+            }
+            Err(e) if !self.best_effort => return SearchResult::failed(e),
+            _ => {}
+        }
+        let graph = ConfiguredGrid {
+            grid: GridGraph::new(grid, &self.is_solid).with_movement(self.movement.clone()),
+            step_cost: self.step_cost.as_ref(),
+        };
+        let heuristic = |cell: Cell, goal: Cell| self.heuristic.estimate(cell, goal);
+        let state = SearchState::new(graph, start, end, heuristic)
+            .with_limits(self.limits.clone())
+            .with_tie_break(self.tie_break);
+        if self.best_effort {
+            state.finish_best_effort()
+        } else {
+            state.finish()
+        }
+    }
+}
+
+/// The grid as seen by a search configured with `AstarBuilder`.
+struct ConfiguredGrid<'a, C, S> {
+    grid: GridGraph<'a, &'a S>,
+    step_cost: Option<&'a C>,
+}
+
+impl<C, S> Graph for ConfiguredGrid<'_, C, S>
+where
+    C: Fn(Cell, Cell) -> u32,
+    S: Fn(usize, usize, &Vec<Vec<i32>>) -> bool,
+{
+    type Node = Cell;
+
+    fn successors(&self, node: Cell) -> Vec<(Cell, u32)> {
+        let successors = self.grid.successors(node);
+        match self.step_cost {
+            Some(step_cost) => successors
+                .into_iter()
+                .map(|(neighbor, _)| (neighbor, step_cost(node, neighbor)))
+                .collect(),
+            None => successors,
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! Precomputed exact heuristics, shared between threads.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::hash::Hash;
+use std::sync::{Arc, RwLock};
+
+use crate::collections::HashMap;
+use crate::graph::Graph;
+use crate::grid::Rect;
+use crate::heuristic::Heuristic;
+
+/// The exact cost from every node to one goal, computed once with Dijkstra's
+/// algorithm. As a heuristic it is perfect: A* expands only nodes on a
+/// shortest path.
+///
+/// Nodes that can't reach the goal are estimated as `u32::MAX`.
+#[derive(Debug, Clone)]
+pub struct DistanceField<N> {
+    goal: N,
+    distances: HashMap<N, u32>,
+}
+
+impl<N: Copy + Eq + Hash> DistanceField<N> {
+    /// Computes the distances to `goal`.
+    ///
+    /// ### Arguments
+    ///
+    /// * `reversed` - A graph whose successors are the predecessors of the
+    ///   searched graph. For graphs where every step costs the same in both
+    ///   directions, like `GridGraph`, that is the searched graph itself.
+    /// * `goal` - The goal the distances lead to.
+    pub fn compute<G: Graph<Node = N>>(reversed: &G, goal: N) -> Self {
+        let mut distances: HashMap<N, u32> = HashMap::new();
+        let mut nodes = vec![goal];
+        let mut open: BinaryHeap<Reverse<(u32, usize)>> = BinaryHeap::new();
+        distances.insert(goal, 0);
+        open.push(Reverse((0, 0)));
+        while let Some(Reverse((distance, index))) = open.pop() {
+            let node = nodes[index];
+            if distances[&node] < distance {
+                continue;
+            }
+            for (previous, cost) in reversed.successors(node) {
+                let through = distance.saturating_add(cost);
+                if distances
+                    .get(&previous)
+                    .is_none_or(|&known| through < known)
+                {
+                    distances.insert(previous, through);
+                    nodes.push(previous);
+                    open.push(Reverse((through, nodes.len() - 1)));
+                }
+            }
+        }
+        DistanceField { goal, distances }
+    }
+
+    /// The goal of the field.
+    pub fn goal(&self) -> N {
+        self.goal
+    }
+
+    /// The cost from `node` to the goal, `None` if the goal can't be reached from it.
+    pub fn distance(&self, node: N) -> Option<u32> {
+        self.distances.get(&node).copied()
+    }
+
+    /// The number of nodes that can reach the goal.
+    pub fn len(&self) -> usize {
+        self.distances.len()
+    }
+
+    /// Whether no node was reached, which never happens as the goal reaches itself.
+    pub fn is_empty(&self) -> bool {
+        self.distances.is_empty()
+    }
+}
+
+impl<N: Copy + Eq + Hash> Heuristic<N> for DistanceField<N> {
+    fn estimate(&self, node: N, _goal: N) -> u32 {
+        self.distance(node).unwrap_or(u32::MAX)
+    }
+}
+
+impl<N: Copy + Eq + Hash> Heuristic<N> for Arc<DistanceField<N>> {
+    fn estimate(&self, node: N, goal: N) -> u32 {
+        (**self).estimate(node, goal)
+    }
+}
+
+/// The distance fields of a `HeuristicCache` by goal.
+type Fields<N> = HashMap<N, Arc<DistanceField<N>>>;
+
+/// A thread-safe cache of `DistanceField`s by goal. Put it in an `Arc` and
+/// share it between the worker threads searching the same graph: the field
+/// of a goal is computed by the first search toward it and reused by all the
+/// others.
+///
+/// ### Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// use pathfinding::astar_graph_with_stats;
+/// use pathfinding::cache::HeuristicCache;
+/// use pathfinding::graph::GridGraph;
+///
+/// let grid = vec![
+///     vec![0, 0, 0, 0],
+///     vec![1, 1, 1, 0],
+///     vec![0, 0, 0, 0],
+/// ];
+/// let is_solid = |row: usize, col: usize, grid: &Vec<Vec<i32>>| grid[row][col] == 1;
+/// let cache = Arc::new(HeuristicCache::new(GridGraph::new(&grid, is_solid)));
+///
+/// thread::scope(|scope| {
+///     for start in [(0, 0), (2, 0)] {
+///         let cache = Arc::clone(&cache);
+///         scope.spawn(move || {
+///             let heuristic = cache.get((2, 3));
+///             let result = astar_graph_with_stats(cache.graph(), start, (2, 3), heuristic);
+///             // A perfect heuristic leads straight to the goal.
+///             assert_eq!(result.nodes_expanded, result.path.unwrap().len());
+///         });
+///     }
+/// });
+///
+/// assert_eq!(cache.len(), 1);
+/// ```
+pub struct HeuristicCache<G: Graph> {
+    graph: G,
+    fields: RwLock<Fields<G::Node>>,
+}
+
+impl<G: Graph> HeuristicCache<G> {
+    /// Creates an empty cache for `graph`. Its step costs must be the same in
+    /// both directions, see `DistanceField::compute`.
+    pub fn new(graph: G) -> Self {
+        HeuristicCache {
+            graph,
+            fields: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// The graph the distances are computed on.
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+
+    /// The distance field of `goal`, computed on the first request.
+    pub fn get(&self, goal: G::Node) -> Arc<DistanceField<G::Node>> {
+        if let Some(field) = self.fields.read().unwrap().get(&goal) {
+            return Arc::clone(field);
+        }
+        // Computed outside of the lock, so other goals aren't blocked meanwhile.
+        // Two threads may both compute a new goal, the first one to finish wins.
+        let field = Arc::new(DistanceField::compute(&self.graph, goal));
+        Arc::clone(self.fields.write().unwrap().entry(goal).or_insert(field))
+    }
+
+    /// Drops the distance field of `goal`, e.g. after the map changed around it.
+    pub fn remove(&self, goal: G::Node) {
+        self.fields.write().unwrap().remove(&goal);
+    }
+
+    /// Drops all distance fields.
+    pub fn clear(&self) {
+        self.fields.write().unwrap().clear();
+    }
+
+    /// The number of goals with a distance field.
+    pub fn len(&self) -> usize {
+        self.fields.read().unwrap().len()
+    }
+
+    /// Whether no distance field was computed yet.
+    pub fn is_empty(&self) -> bool {
+        self.fields.read().unwrap().is_empty()
+    }
+}
+
+impl<G: Graph<Node = (i32, i32)>> HeuristicCache<G> {
+    /// Drops the distance fields that may have changed with the cells of
+    /// `rect`, keeping the others: when a few cells of a grid change, only
+    /// the goals whose distances reach them are computed again.
+    ///
+    /// A field is dropped if it reached a cell of `rect` or one next to it
+    /// (a freed cell is entered from its neighbors). For moves longer than one
+    /// cell, like the knight jumps, grow `rect` by their reach.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use std::sync::RwLock;
+    ///
+    /// use pathfinding::cache::HeuristicCache;
+    /// use pathfinding::graph;
+    /// use pathfinding::grid::Rect;
+    /// use pathfinding::movement::MovementModel;
+    ///
+    /// // Two rooms on both sides of a wall.
+    /// let grid = RwLock::new(vec![
+    ///     vec![0, 0, 1, 0, 0],
+    ///     vec![0, 0, 1, 0, 0],
+    /// ]);
+    /// let is_solid = |row: usize, col: usize, grid: &Vec<Vec<i32>>| grid[row][col] == 1;
+    /// let cache = HeuristicCache::new(graph::from_fn(|(row, col)| {
+    ///     MovementModel::four_way().neighbors(row, col, &grid.read().unwrap(), is_solid)
+    /// }));
+    /// cache.get((0, 0));
+    /// cache.get((0, 4));
+    ///
+    /// // A crate is dropped in the right room: only its field is computed again.
+    /// grid.write().unwrap()[1][3] = 1;
+    /// cache.invalidate(Rect::cell((1, 3)));
+    /// assert_eq!(cache.len(), 1);
+    /// assert_eq!(cache.get((0, 4)).distance((1, 3)), None);
+    ///
+    /// // A door opens between the rooms, both fields change.
+    /// grid.write().unwrap()[0][2] = 0;
+    /// cache.invalidate(Rect::cell((0, 2)));
+    /// assert!(cache.is_empty());
+    /// assert_eq!(cache.get((0, 4)).distance((0, 0)), Some(4));
+    /// ```
+    pub fn invalidate(&self, rect: Rect) {
+        let touched = rect.grow(1);
+        self.fields
+            .write()
+            .unwrap()
+            .retain(|_, field| !touched.cells().any(|cell| field.distance(cell).is_some()));
+    }
+}
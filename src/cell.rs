@@ -0,0 +1,82 @@
+//! Cell types that know whether they can be entered and what entering costs.
+
+use crate::graph::IMPASSABLE;
+
+/// A cell of a grid that can be searched as it is, with `CellGraph` or
+/// `astar_cells`, without writing an index-based predicate.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::astar_cells;
+/// use pathfinding::cell::Cell;
+/// use pathfinding::manhattan_distance;
+///
+/// #[derive(Clone, Copy)]
+/// enum Terrain {
+///     Road,
+///     Forest,
+///     Water,
+/// }
+///
+/// impl Cell for Terrain {
+///     fn passable(&self) -> bool {
+///         !matches!(self, Terrain::Water)
+///     }
+///
+///     fn cost(&self) -> u32 {
+///         match self {
+///             Terrain::Forest => 3,
+///             _ => 1,
+///         }
+///     }
+/// }
+///
+/// use Terrain::*;
+/// let map = vec![
+///     vec![Road, Forest, Road],
+///     vec![Road, Water, Road],
+///     vec![Road, Road, Road],
+/// ];
+///
+/// let path = astar_cells((0, 0), (0, 2), &map, manhattan_distance).unwrap();
+///
+/// // Through the forest (3 + 1) is cheaper than around the lake (6).
+/// assert_eq!(path, vec![(0, 0), (0, 1), (0, 2)]);
+/// assert_eq!(path.cost(), 4);
+/// ```
+pub trait Cell {
+    /// Whether the cell can be entered.
+    fn passable(&self) -> bool;
+
+    /// The cost of entering the cell, `1` by default.
+    fn cost(&self) -> u32 {
+        1
+    }
+}
+
+/// Cells of the grids taken by `astar`: `0` is free, anything else is solid.
+impl Cell for i32 {
+    fn passable(&self) -> bool {
+        *self == 0
+    }
+}
+
+/// Cost cells like those of `WeightedGridGraph`: the value is the cost,
+/// `IMPASSABLE` can't be entered.
+impl Cell for u32 {
+    fn passable(&self) -> bool {
+        *self != IMPASSABLE
+    }
+
+    fn cost(&self) -> u32 {
+        *self
+    }
+}
+
+/// `true` is walkable, `false` is a wall.
+impl Cell for bool {
+    fn passable(&self) -> bool {
+        *self
+    }
+}
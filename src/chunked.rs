@@ -0,0 +1,129 @@
+//! Sparse grids made of chunks allocated on demand, for open worlds too large
+//! to allocate up front.
+
+use alloc::boxed::Box;
+use alloc::vec;
+
+use crate::collections::HashMap;
+use crate::grid::MemoryUsage;
+
+/// The number of rows and columns of a chunk.
+pub const CHUNK_SIZE: usize = 32;
+
+/// The chunk holding `coord` along one axis, and the position in it.
+fn split(coord: i32) -> (i32, usize) {
+    let size = CHUNK_SIZE as i32;
+    (coord.div_euclid(size), coord.rem_euclid(size) as usize)
+}
+
+/// A grid without bounds, indexed by any `(row, col)`, negative ones
+/// included. Every cell holds the default value until it is set, and only the
+/// chunks of `CHUNK_SIZE` x `CHUNK_SIZE` cells that were written to take
+/// memory.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::chunked::ChunkedGrid;
+/// use pathfinding::movement::MovementModel;
+/// use pathfinding::{astar_graph, graph, manhattan_distance};
+///
+/// // An open world with a wall far away from the origin.
+/// let mut world = ChunkedGrid::new(0);
+/// for row in -5..=5 {
+///     world.set((row, 1000), 1);
+/// }
+/// assert_eq!(world.get((0, 1000)), &1);
+/// assert_eq!(world.get((-7, -7)), &0);
+/// assert_eq!(world.chunk_count(), 2);
+///
+/// let movement = MovementModel::four_way();
+/// let paths = graph::from_fn(|(row, col)| {
+///     movement.neighbors_where(row, col, |row, col| *world.get((row, col)) == 0)
+/// });
+/// let path = astar_graph(&paths, (0, 998), (0, 1002), manhattan_distance).unwrap();
+/// assert_eq!(path.cost(), 4 + 2 * 6);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChunkedGrid<T> {
+    default: T,
+    chunks: HashMap<(i32, i32), Box<[T]>>,
+}
+
+impl<T: Clone> ChunkedGrid<T> {
+    /// Creates a grid with every cell holding `default`.
+    pub fn new(default: T) -> Self {
+        ChunkedGrid {
+            default,
+            chunks: HashMap::new(),
+        }
+    }
+
+    /// The cell at `(row, col)`.
+    pub fn get(&self, (row, col): (i32, i32)) -> &T {
+        let ((chunk_row, row), (chunk_col, col)) = (split(row), split(col));
+        self.chunks
+            .get(&(chunk_row, chunk_col))
+            .map_or(&self.default, |chunk| &chunk[row * CHUNK_SIZE + col])
+    }
+
+    /// The cell at `(row, col)` for modification, allocating its chunk if it
+    /// has none yet.
+    pub fn get_mut(&mut self, (row, col): (i32, i32)) -> &mut T {
+        let ((chunk_row, row), (chunk_col, col)) = (split(row), split(col));
+        let chunk = self
+            .chunks
+            .entry((chunk_row, chunk_col))
+            .or_insert_with(|| vec![self.default.clone(); CHUNK_SIZE * CHUNK_SIZE].into());
+        &mut chunk[row * CHUNK_SIZE + col]
+    }
+
+    /// Replaces the cell at `(row, col)` and returns the old value.
+    pub fn set(&mut self, cell: (i32, i32), value: T) -> T {
+        core::mem::replace(self.get_mut(cell), value)
+    }
+
+    /// The value of the cells that were never set.
+    pub fn default_value(&self) -> &T {
+        &self.default
+    }
+
+    /// The number of allocated chunks.
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// How much memory the grid takes: its chunks and the table finding them,
+    /// see `Grid::memory_usage`.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::chunked::{ChunkedGrid, CHUNK_SIZE};
+    ///
+    /// let mut world = ChunkedGrid::new(0u8);
+    /// world.set((0, 0), 1);
+    /// world.set((-1, 5000), 1);
+    ///
+    /// let usage = world.memory_usage();
+    /// assert_eq!(usage.chunks, 2);
+    /// assert_eq!(usage.cells, 2 * CHUNK_SIZE * CHUNK_SIZE);
+    /// assert!(usage.bytes > usage.cells);
+    /// ```
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let cells = self.chunks.len() * CHUNK_SIZE * CHUNK_SIZE;
+        let entry = core::mem::size_of::<((i32, i32), Box<[T]>)>();
+        MemoryUsage {
+            cells,
+            bytes: core::mem::size_of::<Self>()
+                + self.chunks.capacity() * entry
+                + cells * core::mem::size_of::<T>(),
+            chunks: self.chunks.len(),
+        }
+    }
+
+    /// Resets every cell to the default value, freeing the chunks.
+    pub fn clear(&mut self) {
+        self.chunks.clear();
+    }
+}
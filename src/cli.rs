@@ -0,0 +1,123 @@
+use std::path::PathBuf;
+
+use pathfinding::diagonal_distance;
+use pathfinding::diff;
+use pathfinding::manhattan_distance;
+
+const DIFF_USAGE: &str = "usage: pathfinding diff <corpus-dir> [--baseline <heuristic>] \
+[--candidate <heuristic>] [--tolerance <ratio>] [--out <dir>]
+
+heuristics: manhattan, diagonal, zero";
+
+type Heuristic = fn((i32, i32), (i32, i32)) -> u32;
+
+fn heuristic_by_name(name: &str) -> Option<Heuristic> {
+    match name {
+        "manhattan" => Some(manhattan_distance),
+        "diagonal" => Some(diagonal_distance),
+        "zero" => Some(|_, _| 0),
+        _ => None,
+    }
+}
+
+struct DiffArgs {
+    corpus: PathBuf,
+    baseline: String,
+    candidate: String,
+    tolerance: f64,
+    out: PathBuf,
+}
+
+fn parse_diff_args(args: &[String]) -> Result<DiffArgs, String> {
+    let mut corpus = None;
+    let mut parsed = DiffArgs {
+        corpus: PathBuf::new(),
+        baseline: "manhattan".to_string(),
+        candidate: "manhattan".to_string(),
+        tolerance: 0.0,
+        out: PathBuf::from("diff-failures"),
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .cloned()
+                .ok_or_else(|| format!("missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--baseline" => parsed.baseline = value()?,
+            "--candidate" => parsed.candidate = value()?,
+            "--tolerance" => {
+                parsed.tolerance = value()?
+                    .parse()
+                    .map_err(|e| format!("invalid tolerance: {}", e))?
+            }
+            "--out" => parsed.out = PathBuf::from(value()?),
+            other if other.starts_with("--") => return Err(format!("unknown option {}", other)),
+            other => corpus = Some(PathBuf::from(other)),
+        }
+    }
+    parsed.corpus = corpus.ok_or("missing corpus directory")?;
+    Ok(parsed)
+}
+
+/// Runs the `diff` subcommand and returns the process exit code:
+/// `0` when nothing regressed, `1` on regressions and `2` on usage or I/O errors.
+pub fn run_diff(args: &[String]) -> i32 {
+    let args = match parse_diff_args(args) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n{}", e, DIFF_USAGE);
+            return 2;
+        }
+    };
+    let (Some(baseline), Some(candidate)) = (
+        heuristic_by_name(&args.baseline),
+        heuristic_by_name(&args.candidate),
+    ) else {
+        eprintln!("unknown heuristic\n{}", DIFF_USAGE);
+        return 2;
+    };
+
+    let corpus = match diff::load_corpus(&args.corpus) {
+        Ok(corpus) => corpus,
+        Err(e) => {
+            eprintln!("failed to load corpus {}: {}", args.corpus.display(), e);
+            return 2;
+        }
+    };
+    let regressions = diff::diff_corpus(
+        &corpus,
+        |grid, start, end| diff::run_astar(grid, start, end, baseline),
+        |grid, start, end| diff::run_astar(grid, start, end, candidate),
+        args.tolerance,
+    );
+
+    let queries: usize = corpus.iter().map(|s| s.queries.len()).sum();
+    for regression in &regressions {
+        println!("{}", regression);
+    }
+    println!(
+        "{} of {} queries regressed ({} vs {}, tolerance {})",
+        regressions.len(),
+        queries,
+        args.baseline,
+        args.candidate,
+        args.tolerance
+    );
+    if regressions.is_empty() {
+        return 0;
+    }
+    match diff::write_failures(&corpus, &regressions, &args.out) {
+        Ok(written) => println!(
+            "wrote {} scenarios to {}",
+            written.len(),
+            args.out.display()
+        ),
+        Err(e) => {
+            eprintln!("failed to write failures to {}: {}", args.out.display(), e);
+            return 2;
+        }
+    }
+    1
+}
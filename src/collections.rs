@@ -0,0 +1,7 @@
+//! The hash collections used by the searches: the ones of `std`, or of
+//! `hashbrown` when the `std` feature is off.
+
+#[cfg(not(feature = "std"))]
+pub use hashbrown::{HashMap, HashSet};
+#[cfg(feature = "std")]
+pub use std::collections::{HashMap, HashSet};
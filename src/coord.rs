@@ -0,0 +1,138 @@
+//! Cell coordinates with arithmetic, instead of bare `(i32, i32)` and
+//! `(usize, usize)` tuples.
+//!
+//! The search nodes are `(i32, i32)` so that the steps off the grid can be
+//! represented, while the storage of the grids is indexed by `usize`. A
+//! `Coord` converts from and into the first, and checks the conversion to the
+//! second, so the bounds checks aren't written by hand every time.
+//!
+//! ### Example
+//!
+//! ```
+//! use pathfinding::coord::Coord;
+//! use pathfinding::direction::Direction;
+//! use pathfinding::grid::Grid;
+//!
+//! let grid: Grid<i32> = Grid::new(3, 3);
+//! let cell = Coord::new(1, 0);
+//!
+//! assert_eq!(cell + Coord::RIGHT, Coord::new(1, 1));
+//! assert_eq!(cell.step(Direction::UpRight), Coord::from((0, 1)));
+//! assert_eq!((cell + Coord::LEFT).to_usize(), None);
+//! assert!(!grid.in_bounds(cell + Coord::LEFT));
+//! assert!(grid.in_bounds((2, 2)));
+//!
+//! let node: (i32, i32) = (cell - Coord::UP).into();
+//! assert_eq!(node, (2, 0));
+//! assert_eq!(Coord::try_from((2usize, 0usize)), Ok(Coord::new(2, 0)));
+//! ```
+
+use core::num::TryFromIntError;
+use core::ops;
+
+use crate::direction::Direction;
+
+/// The `row` and `col` of a cell. Rows grow downwards, like the nodes of the
+/// grid searches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Coord {
+    pub row: i32,
+    pub col: i32,
+}
+
+impl Coord {
+    /// The top left cell, or no offset at all.
+    pub const ZERO: Coord = Coord::new(0, 0);
+    /// The offset of a step up.
+    pub const UP: Coord = Coord::new(-1, 0);
+    /// The offset of a step down.
+    pub const DOWN: Coord = Coord::new(1, 0);
+    /// The offset of a step left.
+    pub const LEFT: Coord = Coord::new(0, -1);
+    /// The offset of a step right.
+    pub const RIGHT: Coord = Coord::new(0, 1);
+
+    /// Creates a coordinate.
+    pub const fn new(row: i32, col: i32) -> Self {
+        Coord { row, col }
+    }
+
+    /// The cell reached by stepping in `direction`.
+    pub fn step(self, direction: Direction) -> Coord {
+        self + Coord::from(direction)
+    }
+
+    /// The coordinate as indices into the storage of a grid, `None` if the
+    /// row or the column is negative. The upper bounds are left to the grid.
+    pub fn to_usize(self) -> Option<(usize, usize)> {
+        Some((
+            usize::try_from(self.row).ok()?,
+            usize::try_from(self.col).ok()?,
+        ))
+    }
+}
+
+impl From<(i32, i32)> for Coord {
+    fn from((row, col): (i32, i32)) -> Self {
+        Coord { row, col }
+    }
+}
+
+impl From<Coord> for (i32, i32) {
+    fn from(coord: Coord) -> Self {
+        (coord.row, coord.col)
+    }
+}
+
+/// Fails if the row or the column doesn't fit in an `i32`.
+impl TryFrom<(usize, usize)> for Coord {
+    type Error = TryFromIntError;
+
+    fn try_from((row, col): (usize, usize)) -> Result<Self, Self::Error> {
+        Ok(Coord::new(i32::try_from(row)?, i32::try_from(col)?))
+    }
+}
+
+/// The offset of a step in the direction.
+impl From<Direction> for Coord {
+    fn from(direction: Direction) -> Self {
+        Coord::from(direction.offset())
+    }
+}
+
+impl ops::Add for Coord {
+    type Output = Coord;
+
+    fn add(self, other: Coord) -> Coord {
+        Coord::new(self.row + other.row, self.col + other.col)
+    }
+}
+
+impl ops::Sub for Coord {
+    type Output = Coord;
+
+    fn sub(self, other: Coord) -> Coord {
+        Coord::new(self.row - other.row, self.col - other.col)
+    }
+}
+
+impl ops::AddAssign for Coord {
+    fn add_assign(&mut self, other: Coord) {
+        *self = *self + other;
+    }
+}
+
+impl ops::SubAssign for Coord {
+    fn sub_assign(&mut self, other: Coord) {
+        *self = *self - other;
+    }
+}
+
+impl ops::Neg for Coord {
+    type Output = Coord;
+
+    fn neg(self) -> Coord {
+        Coord::new(-self.row, -self.col)
+    }
+}
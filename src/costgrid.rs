@@ -0,0 +1,146 @@
+//! Grids of per-cell costs, checked once so the weighted searches can trust
+//! them.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::coord::Coord;
+use crate::graph::{Graph, IMPASSABLE};
+use crate::grid::Grid;
+
+/// Error produced when a cost grid holds a cell costing `0`. Free steps would
+/// make the heuristics overestimate, and the paths found not be the cheapest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidCost {
+    /// The `(row, col)` of the first cell costing `0`.
+    pub cell: (usize, usize),
+}
+
+impl fmt::Display for InvalidCost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cell ({}, {}) costs 0, costs must be at least 1",
+            self.cell.0, self.cell.1
+        )
+    }
+}
+
+impl core::error::Error for InvalidCost {}
+
+/// A grid of the costs of entering its cells, `IMPASSABLE` (`u32::MAX`) for
+/// the cells that can't be entered. Every cost is at least `1`, so the
+/// heuristics of unit grids (`manhattan_distance`, ...) don't overestimate.
+///
+/// It is a `Graph` moving up/down/left/right like `WeightedGridGraph`, and
+/// `astar_weighted` searches it directly.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::costgrid::{CostGrid, InvalidCost};
+/// use pathfinding::graph::IMPASSABLE;
+/// use pathfinding::grid::Grid;
+///
+/// let mut costs = CostGrid::from(&Grid::from_vec(vec![vec![0, 1], vec![0, 0]]));
+/// assert_eq!(costs.get(0, 1), Some(IMPASSABLE));
+/// assert_eq!(costs.get(1, 1), Some(1));
+///
+/// costs.set(1, 1, 7);
+/// assert_eq!(costs.as_grid().to_vec(), vec![vec![1, IMPASSABLE], vec![1, 7]]);
+///
+/// let free = Grid::from_vec(vec![vec![1, 0]]);
+/// assert_eq!(CostGrid::new(free), Err(InvalidCost { cell: (0, 1) }));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CostGrid(Grid<u32>);
+
+impl CostGrid {
+    /// Checks that every cell of `costs` costs at least `1`.
+    pub fn new(costs: Grid<u32>) -> Result<Self, InvalidCost> {
+        let zero = costs.cells_where(|&cost| cost == 0).next();
+        match zero {
+            Some(cell) => Err(InvalidCost { cell }),
+            None => Ok(CostGrid(costs)),
+        }
+    }
+
+    /// The number of columns.
+    pub fn width(&self) -> usize {
+        self.0.width
+    }
+
+    /// The number of rows.
+    pub fn height(&self) -> usize {
+        self.0.height
+    }
+
+    /// The cost of the cell at `row`, `col`, `None` if it is outside of the
+    /// grid.
+    pub fn get(&self, row: usize, col: usize) -> Option<u32> {
+        self.0.get(row, col).copied()
+    }
+
+    /// Replaces the cost of the cell at `row`, `col` and returns the old one,
+    /// `None` if it is outside of the grid.
+    ///
+    /// ### Panics
+    ///
+    /// If `cost` is `0`.
+    pub fn set(&mut self, row: usize, col: usize, cost: u32) -> Option<u32> {
+        assert!(cost > 0, "costs must be at least 1");
+        self.0.set(row, col, cost)
+    }
+
+    /// Whether the cell at `row`, `col` is in the grid and can be entered.
+    pub fn passable(&self, row: usize, col: usize) -> bool {
+        self.get(row, col).is_some_and(|cost| cost != IMPASSABLE)
+    }
+
+    /// The costs as a plain grid.
+    pub fn as_grid(&self) -> &Grid<u32> {
+        &self.0
+    }
+
+    /// Unwraps the costs.
+    pub fn into_inner(self) -> Grid<u32> {
+        self.0
+    }
+
+    /// The cost of entering `(row, col)`, `None` if it can't be entered.
+    fn entry_cost(&self, cell: (i32, i32)) -> Option<u32> {
+        let (row, col) = Coord::from(cell).to_usize()?;
+        let cost = self.get(row, col)?;
+        (cost != IMPASSABLE).then_some(cost)
+    }
+}
+
+impl TryFrom<Grid<u32>> for CostGrid {
+    type Error = InvalidCost;
+
+    fn try_from(costs: Grid<u32>) -> Result<Self, InvalidCost> {
+        CostGrid::new(costs)
+    }
+}
+
+/// The free cells (`0`) of a grid like the ones of `astar` cost `1`, the
+/// others are `IMPASSABLE`.
+impl From<&Grid<i32>> for CostGrid {
+    fn from(grid: &Grid<i32>) -> Self {
+        CostGrid(grid.map(|&cell| if cell == 0 { 1 } else { IMPASSABLE }))
+    }
+}
+
+impl Graph for CostGrid {
+    type Node = (i32, i32);
+
+    fn successors(&self, (row, col): (i32, i32)) -> Vec<((i32, i32), u32)> {
+        [(-1, 0), (0, -1), (1, 0), (0, 1)]
+            .iter()
+            .filter_map(|(dr, dc)| {
+                let cell = (row + dr, col + dc);
+                Some((cell, self.entry_cost(cell)?))
+            })
+            .collect()
+    }
+}
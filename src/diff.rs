@@ -0,0 +1,390 @@
+//! Batch regression diffing of two search configurations over a scenario corpus.
+//!
+//! A corpus is a directory of `.scenario` files. Each file holds one map and any
+//! number of queries:
+//!
+//! ```text
+//! // lines starting with '//' are comments
+//! query 1 1 3 3
+//! query 0 0 4 4
+//! .....
+//! .###.
+//! .#...
+//! .#.#.
+//! .....
+//! ```
+//!
+//! `query r0 c0 r1 c1` asks for a path from `(r0, c0)` to `(r1, c1)`, a cell of
+//! the map. Map rows use `.` for free cells and `#` for obstacles (stored as `0`
+//! and `1` in the grid), so a row may start with `#`: comments use `//` instead.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::heuristic::Heuristic;
+
+/// A single scenario: one map with the queries that should be run on it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scenario {
+    pub name: String,
+    pub grid: Vec<Vec<i32>>,
+    pub queries: Vec<((i32, i32), (i32, i32))>,
+}
+
+/// Error produced when a scenario file cannot be parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseScenarioError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseScenarioError {}
+
+impl Scenario {
+    /// Parses a scenario from its text representation (see the module docs).
+    ///
+    /// ### Arguments
+    ///
+    /// * `name` - The name of the scenario (usually the file stem).
+    /// * `text` - The scenario text.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::diff::Scenario;
+    ///
+    /// let scenario = Scenario::parse("tiny", "query 0 0 0 2\n.#.\n...\n").unwrap();
+    ///
+    /// assert_eq!(scenario.grid, vec![vec![0, 1, 0], vec![0, 0, 0]]);
+    /// assert_eq!(scenario.queries, vec![((0, 0), (0, 2))]);
+    ///
+    /// // Rows may start with a wall, queries must stay on the map.
+    /// let walled = Scenario::parse("walled", "// a comment\nquery 0 1 2 1\n#..\n...\n..#\n").unwrap();
+    /// assert_eq!(walled.grid.len(), 3);
+    /// assert!(Scenario::parse("off", "query 0 0 3 0\n...\n").is_err());
+    /// ```
+    pub fn parse(name: &str, text: &str) -> Result<Scenario, ParseScenarioError> {
+        let mut grid = vec![];
+        let mut queries = vec![];
+        let mut query_lines = vec![];
+        for (index, line) in text.lines().enumerate() {
+            let error = |message: String| ParseScenarioError {
+                line: index + 1,
+                message,
+            };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+            if let Some(rest) = line.strip_prefix("query") {
+                let numbers = rest
+                    .split_whitespace()
+                    .map(|n| n.parse::<i32>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| error(format!("invalid query coordinate: {}", e)))?;
+                if numbers.len() != 4 {
+                    return Err(error(format!(
+                        "expected 4 coordinates, got {}",
+                        numbers.len()
+                    )));
+                }
+                queries.push(((numbers[0], numbers[1]), (numbers[2], numbers[3])));
+                query_lines.push(index + 1);
+                continue;
+            }
+            let row = line
+                .chars()
+                .map(|c| match c {
+                    '.' => Ok(0),
+                    '#' => Ok(1),
+                    other => Err(error(format!("unexpected map character {:?}", other))),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if let Some(width) = grid.first().map(Vec::len) {
+                if width != row.len() {
+                    return Err(error(format!(
+                        "row has {} cells, expected {}",
+                        row.len(),
+                        width
+                    )));
+                }
+            }
+            grid.push(row);
+        }
+        if grid.is_empty() {
+            return Err(ParseScenarioError {
+                line: text.lines().count(),
+                message: "scenario has no map".to_string(),
+            });
+        }
+        let (width, height) = (grid[0].len() as i32, grid.len() as i32);
+        let on_map =
+            |(row, col): (i32, i32)| (0..height).contains(&row) && (0..width).contains(&col);
+        for (&(start, end), &line) in queries.iter().zip(&query_lines) {
+            if let Some(cell) = [start, end].into_iter().find(|&cell| !on_map(cell)) {
+                return Err(ParseScenarioError {
+                    line,
+                    message: format!(
+                        "query cell {:?} is outside of the {}x{} map",
+                        cell, width, height
+                    ),
+                });
+            }
+        }
+        Ok(Scenario {
+            name: name.to_string(),
+            grid,
+            queries,
+        })
+    }
+
+    /// Serializes the scenario back into the text format accepted by `Scenario::parse`.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for ((r0, c0), (r1, c1)) in &self.queries {
+            text.push_str(&format!("query {} {} {} {}\n", r0, c0, r1, c1));
+        }
+        for row in &self.grid {
+            text.extend(row.iter().map(|&cell| if cell == 0 { '.' } else { '#' }));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+/// Loads every `.scenario` file of a directory, sorted by file name.
+pub fn load_corpus(dir: &Path) -> io::Result<Vec<Scenario>> {
+    let mut paths = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    paths.retain(|path| path.extension().is_some_and(|ext| ext == "scenario"));
+    paths.sort();
+
+    let mut corpus = vec![];
+    for path in paths {
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let text = fs::read_to_string(&path)?;
+        let scenario = Scenario::parse(&name, &text).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), e),
+            )
+        })?;
+        corpus.push(scenario);
+    }
+    Ok(corpus)
+}
+
+/// What a configuration produced for a single query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Outcome {
+    /// The path cost, `None` if no path was found.
+    pub cost: Option<u32>,
+    /// The number of nodes the search expanded.
+    pub nodes_expanded: usize,
+}
+
+/// The way in which a candidate got worse than the baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegressionKind {
+    /// The baseline and the candidate disagree on whether a path exists.
+    Reachability,
+    /// The candidate path is more expensive than allowed by the tolerance.
+    Cost,
+    /// The candidate expanded more nodes than allowed by the tolerance.
+    Expansions,
+}
+
+/// A query on which the candidate regressed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    pub scenario: String,
+    pub query: ((i32, i32), (i32, i32)),
+    pub baseline: Outcome,
+    pub candidate: Outcome,
+    pub kinds: Vec<RegressionKind>,
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (start, end) = self.query;
+        write!(
+            f,
+            "{} {:?} -> {:?}: {:?} (cost {:?} -> {:?}, expanded {} -> {})",
+            self.scenario,
+            start,
+            end,
+            self.kinds,
+            self.baseline.cost,
+            self.candidate.cost,
+            self.baseline.nodes_expanded,
+            self.candidate.nodes_expanded,
+        )
+    }
+}
+
+fn exceeds(baseline: u64, candidate: u64, tolerance: f64) -> bool {
+    candidate as f64 > baseline as f64 * (1.0 + tolerance)
+}
+
+/// Compares two outcomes of the same query and lists the ways the candidate regressed.
+///
+/// ### Arguments
+///
+/// * `baseline` - The outcome of the reference configuration.
+/// * `candidate` - The outcome of the configuration under test.
+/// * `tolerance` - The allowed relative increase (`0.1` allows 10% more cost or expansions).
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::diff::{compare, Outcome, RegressionKind};
+///
+/// let baseline = Outcome { cost: Some(10), nodes_expanded: 100 };
+/// let candidate = Outcome { cost: Some(10), nodes_expanded: 120 };
+///
+/// assert_eq!(compare(&baseline, &candidate, 0.1), vec![RegressionKind::Expansions]);
+/// assert!(compare(&baseline, &candidate, 0.25).is_empty());
+/// ```
+pub fn compare(baseline: &Outcome, candidate: &Outcome, tolerance: f64) -> Vec<RegressionKind> {
+    let mut kinds = vec![];
+    match (baseline.cost, candidate.cost) {
+        (Some(a), Some(b)) => {
+            if exceeds(a as u64, b as u64, tolerance) {
+                kinds.push(RegressionKind::Cost);
+            }
+        }
+        (None, None) => {}
+        _ => kinds.push(RegressionKind::Reachability),
+    }
+    if exceeds(
+        baseline.nodes_expanded as u64,
+        candidate.nodes_expanded as u64,
+        tolerance,
+    ) {
+        kinds.push(RegressionKind::Expansions);
+    }
+    kinds
+}
+
+/// Runs two configurations over every query of the corpus and reports the regressions.
+///
+/// ### Arguments
+///
+/// * `corpus` - The scenarios to run.
+/// * `baseline` - The reference configuration, called as `baseline(grid, start, end)`.
+/// * `candidate` - The configuration under test, called the same way.
+/// * `tolerance` - The allowed relative increase, see `compare`.
+///
+/// ### Returns
+///
+/// Every query on which the candidate regressed, in corpus order.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::diff::{diff_corpus, Scenario};
+/// use pathfinding::diff::run_astar;
+/// use pathfinding::manhattan_distance;
+///
+/// let corpus = vec![Scenario::parse("open", "query 0 0 4 4\n.....\n.....\n.....\n.....\n.....\n").unwrap()];
+///
+/// let regressions = diff_corpus(
+///     &corpus,
+///     |grid, start, end| run_astar(grid, start, end, manhattan_distance),
+///     |grid, start, end| run_astar(grid, start, end, manhattan_distance),
+///     0.0,
+/// );
+///
+/// assert!(regressions.is_empty());
+/// ```
+pub fn diff_corpus<A, B>(
+    corpus: &[Scenario],
+    baseline: A,
+    candidate: B,
+    tolerance: f64,
+) -> Vec<Regression>
+where
+    A: Fn(&Vec<Vec<i32>>, (i32, i32), (i32, i32)) -> Outcome,
+    B: Fn(&Vec<Vec<i32>>, (i32, i32), (i32, i32)) -> Outcome,
+{
+    let mut regressions = vec![];
+    for scenario in corpus {
+        for &(start, end) in &scenario.queries {
+            let a = baseline(&scenario.grid, start, end);
+            let b = candidate(&scenario.grid, start, end);
+            let kinds = compare(&a, &b, tolerance);
+            if !kinds.is_empty() {
+                regressions.push(Regression {
+                    scenario: scenario.name.clone(),
+                    query: (start, end),
+                    baseline: a,
+                    candidate: b,
+                    kinds,
+                });
+            }
+        }
+    }
+    regressions
+}
+
+/// Runs A* on a 0/1 grid (`1` is solid) and reports its outcome. Queries with
+/// an endpoint outside of the grid or on a solid cell have no path.
+pub fn run_astar<H>(
+    grid: &Vec<Vec<i32>>,
+    start: (i32, i32),
+    end: (i32, i32),
+    heuristic: H,
+) -> Outcome
+where
+    H: Heuristic<(i32, i32)>,
+{
+    let result = crate::astar_with_stats(start, end, grid, heuristic, |row, col, grid| {
+        grid[row][col] == 1
+    });
+    Outcome {
+        cost: result.cost,
+        nodes_expanded: result.nodes_expanded,
+    }
+}
+
+/// Writes every regressed query as a standalone scenario file into `out_dir`, so it can
+/// be inspected or replayed on its own. The files are named `<scenario>-<n>.scenario`
+/// and start with a comment describing the regression.
+///
+/// ### Returns
+///
+/// The paths of the written files.
+pub fn write_failures(
+    corpus: &[Scenario],
+    regressions: &[Regression],
+    out_dir: &Path,
+) -> io::Result<Vec<std::path::PathBuf>> {
+    fs::create_dir_all(out_dir)?;
+    let mut written = vec![];
+    for (index, regression) in regressions.iter().enumerate() {
+        let Some(scenario) = corpus.iter().find(|s| s.name == regression.scenario) else {
+            continue;
+        };
+        let failing = Scenario {
+            name: regression.scenario.clone(),
+            grid: scenario.grid.clone(),
+            queries: vec![regression.query],
+        };
+        let path = out_dir.join(format!("{}-{}.scenario", regression.scenario, index));
+        fs::write(&path, format!("// {}\n{}", regression, failing.to_text()))?;
+        written.push(path);
+    }
+    Ok(written)
+}
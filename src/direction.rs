@@ -0,0 +1,106 @@
+//! Paths as sequences of movement commands.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// A step to one of the eight neighbors of a grid cell. Rows grow downwards,
+/// so `Up` decreases the row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    /// All directions, the four straight ones first.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+        Direction::UpLeft,
+        Direction::UpRight,
+        Direction::DownLeft,
+        Direction::DownRight,
+    ];
+
+    /// The `(row, col)` offset of the step.
+    pub fn offset(self) -> (i32, i32) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+            Direction::UpLeft => (-1, -1),
+            Direction::UpRight => (-1, 1),
+            Direction::DownLeft => (1, -1),
+            Direction::DownRight => (1, 1),
+        }
+    }
+
+    /// The direction of a step by `offset`, `None` if it isn't a step to a neighbor.
+    pub fn from_offset(offset: (i32, i32)) -> Option<Direction> {
+        Direction::ALL
+            .into_iter()
+            .find(|direction| direction.offset() == offset)
+    }
+
+    /// The direction going back.
+    pub fn opposite(self) -> Direction {
+        let (dr, dc) = self.offset();
+        Direction::from_offset((-dr, -dc)).unwrap()
+    }
+
+    /// The cell reached by stepping from `cell` in this direction.
+    pub fn step(self, cell: (i32, i32)) -> (i32, i32) {
+        let (dr, dc) = self.offset();
+        (cell.0 + dr, cell.1 + dc)
+    }
+}
+
+/// Converts a path of cells into the directions of its steps.
+///
+/// ### Arguments
+///
+/// * `path` - The cells of the path, e.g. a `Path` returned by `astar`.
+///
+/// ### Returns
+///
+/// One direction per step, or `None` if two consecutive cells aren't
+/// neighbors (e.g. the jumps of `MovementModel::knight`).
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::direction::{from_directions, to_directions, Direction::*};
+///
+/// let path = vec![(1, 1), (0, 1), (0, 2), (1, 3)];
+/// let directions = to_directions(&path).unwrap();
+///
+/// assert_eq!(directions, vec![Up, Right, DownRight]);
+/// assert_eq!(from_directions((1, 1), &directions), path);
+/// assert_eq!(to_directions(&[(0, 0), (2, 1)]), None);
+/// ```
+pub fn to_directions(path: &[(i32, i32)]) -> Option<Vec<Direction>> {
+    path.windows(2)
+        .map(|step| Direction::from_offset((step[1].0 - step[0].0, step[1].1 - step[0].1)))
+        .collect()
+}
+
+/// Follows `directions` from `start` and returns the cells visited, `start` included.
+pub fn from_directions(start: (i32, i32), directions: &[Direction]) -> Vec<(i32, i32)> {
+    let mut cells = vec![start];
+    let mut current = start;
+    for direction in directions {
+        current = direction.step(current);
+        cells.push(current);
+    }
+    cells
+}
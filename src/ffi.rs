@@ -0,0 +1,155 @@
+//! A C interface to the grid search, for calling it from C and C++ engines.
+//!
+//! The declarations are in `include/pathfinding.h`. Build the shared library
+//! with `cargo rustc --lib --release --no-default-features --features ffi --crate-type cdylib`.
+//!
+//! ### Example
+//!
+//! ```
+//! use pathfinding::ffi::{pf_astar, PfCell, PF_OK};
+//!
+//! let cells = [
+//!     0, 0, 0,
+//!     1, 1, 0,
+//!     0, 0, 0,
+//! ];
+//! let mut path = [PfCell::default(); 16];
+//! let (mut length, mut cost) = (0, 0);
+//!
+//! let status = unsafe {
+//!     pf_astar(
+//!         cells.as_ptr(), 3, 3,
+//!         PfCell { row: 0, col: 0 }, PfCell { row: 2, col: 0 },
+//!         false,
+//!         path.as_mut_ptr(), path.len(), &mut length, &mut cost,
+//!     )
+//! };
+//!
+//! assert_eq!(status, PF_OK);
+//! assert_eq!((length, cost), (7, 6));
+//! assert_eq!(path[3], PfCell { row: 1, col: 2 });
+//! ```
+
+use core::slice;
+
+use crate::graph;
+use crate::movement::MovementModel;
+use crate::{diagonal_distance, manhattan_distance};
+
+/// A grid cell, `PfCell` in C.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PfCell {
+    pub row: i32,
+    pub col: i32,
+}
+
+/// A path was found.
+pub const PF_OK: i32 = 0;
+/// The whole reachable area was explored without finding the goal.
+pub const PF_NO_PATH: i32 = 1;
+/// The start is outside of the grid.
+pub const PF_START_OUT_OF_BOUNDS: i32 = 2;
+/// The goal is outside of the grid.
+pub const PF_GOAL_OUT_OF_BOUNDS: i32 = 3;
+/// The start is on a wall.
+pub const PF_START_BLOCKED: i32 = 4;
+/// The goal is on a wall.
+pub const PF_GOAL_BLOCKED: i32 = 5;
+/// A path was found but doesn't fit in the output buffer, its length is set.
+pub const PF_BUFFER_TOO_SMALL: i32 = 6;
+/// A required pointer is null or the grid size overflows.
+pub const PF_INVALID_ARGUMENT: i32 = -1;
+
+/// Searches the shortest path on a grid stored row by row, like `astar`
+/// (or `astar_cells` with `MovementModel::eight_way` when `diagonal`).
+///
+/// ### Arguments
+///
+/// * `cells` - The `width * height` cells, row after row, `0` for the free
+///   cells and anything else for walls.
+/// * `start` / `end` - The first and last cells of the path.
+/// * `diagonal` - Whether diagonal steps are allowed.
+/// * `path` / `capacity` - The buffer receiving the cells of the path, from
+///   `start` to `end`. It may be null if `capacity` is `0`.
+/// * `length` - Receives the number of cells of the path.
+/// * `cost` - Receives the cost of the path, may be null.
+///
+/// ### Returns
+///
+/// `PF_OK`, or one of the other `PF_` codes. `length` and `cost` are also set
+/// for `PF_BUFFER_TOO_SMALL`, to retry with a large enough buffer.
+///
+/// ### Safety
+///
+/// `cells` must point to `width * height` readable cells, `path` to
+/// `capacity` writable ones, and `length` and `cost` (unless null) must be
+/// valid for writes.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn pf_astar(
+    cells: *const i32,
+    width: usize,
+    height: usize,
+    start: PfCell,
+    end: PfCell,
+    diagonal: bool,
+    path: *mut PfCell,
+    capacity: usize,
+    length: *mut usize,
+    cost: *mut u32,
+) -> i32 {
+    let Some(size) = width.checked_mul(height) else {
+        return PF_INVALID_ARGUMENT;
+    };
+    if (cells.is_null() && size > 0) || (path.is_null() && capacity > 0) || length.is_null() {
+        return PF_INVALID_ARGUMENT;
+    }
+    let cells = if size == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(cells, size)
+    };
+    let in_bounds = |row: i32, col: i32| {
+        row >= 0 && col >= 0 && (row as usize) < height && (col as usize) < width
+    };
+    let is_free =
+        |row: i32, col: i32| in_bounds(row, col) && cells[row as usize * width + col as usize] == 0;
+    let (start, end) = ((start.row, start.col), (end.row, end.col));
+    if !in_bounds(start.0, start.1) {
+        return PF_START_OUT_OF_BOUNDS;
+    }
+    if !in_bounds(end.0, end.1) {
+        return PF_GOAL_OUT_OF_BOUNDS;
+    }
+    if !is_free(start.0, start.1) {
+        return PF_START_BLOCKED;
+    }
+    if !is_free(end.0, end.1) {
+        return PF_GOAL_BLOCKED;
+    }
+
+    let (movement, heuristic): (_, fn(_, _) -> u32) = if diagonal {
+        (MovementModel::eight_way(), diagonal_distance)
+    } else {
+        (MovementModel::four_way(), manhattan_distance)
+    };
+    let grid = graph::from_fn(|(row, col)| movement.neighbors_in(row, col, height, width, is_free));
+    // The endpoints are checked and there are no limits, so the only error
+    // left is `NoPath`.
+    let Ok(found) = crate::astar_graph(&grid, start, end, heuristic) else {
+        return PF_NO_PATH;
+    };
+
+    *length = found.len();
+    if !cost.is_null() {
+        *cost = found.cost();
+    }
+    if found.len() > capacity {
+        return PF_BUFFER_TOO_SMALL;
+    }
+    for (index, &(row, col)) in found.nodes().iter().enumerate() {
+        *path.add(index) = PfCell { row, col };
+    }
+    PF_OK
+}
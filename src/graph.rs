@@ -0,0 +1,412 @@
+use alloc::vec::Vec;
+use core::hash::Hash;
+use core::marker::PhantomData;
+
+use crate::cell::Cell;
+use crate::grid3::{Connectivity, Grid3, Voxel};
+use crate::movement::MovementModel;
+
+/// A graph that can be searched by `astar_graph`.
+///
+/// Only the outgoing edges of a node have to be known, so the graph can be
+/// stored in any shape: an adjacency list of a road network, the polygons of
+/// a navmesh, or computed on the fly like `GridGraph` does for 2D grids.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::graph::Graph;
+///
+/// struct Roads {
+///     edges: Vec<Vec<(usize, u32)>>,
+/// }
+///
+/// impl Graph for Roads {
+///     type Node = usize;
+///
+///     fn successors(&self, node: usize) -> Vec<(usize, u32)> {
+///         self.edges[node].clone()
+///     }
+/// }
+///
+/// let roads = Roads { edges: vec![vec![(1, 7)], vec![(0, 7)]] };
+///
+/// assert_eq!(roads.successors(0), vec![(1, 7)]);
+/// ```
+pub trait Graph {
+    /// The node type of the graph.
+    type Node: Copy + Eq + Hash;
+
+    /// Returns the nodes reachable from `node` in one step, together with the cost of the step.
+    fn successors(&self, node: Self::Node) -> Vec<(Self::Node, u32)>;
+}
+
+impl<G: Graph + ?Sized> Graph for &G {
+    type Node = G::Node;
+
+    fn successors(&self, node: Self::Node) -> Vec<(Self::Node, u32)> {
+        (**self).successors(node)
+    }
+}
+
+/// A `Graph` defined by a successor closure, see `from_fn`.
+pub struct FnGraph<N, F> {
+    successors: F,
+    node: PhantomData<fn(N)>,
+}
+
+/// Creates a `Graph` from a closure returning the successors of a node with
+/// their step costs, so any node type can be searched without declaring a
+/// graph type first.
+///
+/// ### Example
+///
+/// A* on a hex map in axial coordinates, with a wall along `q == 1`:
+///
+/// ```
+/// use pathfinding::astar_graph;
+/// use pathfinding::graph;
+///
+/// let hex_distance = |a: (i32, i32), b: (i32, i32)| {
+///     let (dq, dr) = (a.0 - b.0, a.1 - b.1);
+///     (dq.abs() + dr.abs() + (dq + dr).abs()) as u32 / 2
+/// };
+/// let hexes = graph::from_fn(|(q, r): (i32, i32)| {
+///     [(1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)]
+///         .iter()
+///         .map(|(dq, dr)| (q + dq, r + dr))
+///         .filter(|&(q, r)| q.abs() <= 3 && r.abs() <= 3 && !(q == 1 && r > -3))
+///         .map(|hex| (hex, 1))
+///         .collect()
+/// });
+///
+/// let path = astar_graph(&hexes, (0, 0), (2, 0), hex_distance).unwrap();
+///
+/// assert_eq!(path.first(), Some(&(0, 0)));
+/// assert_eq!(path.last(), Some(&(2, 0)));
+/// assert!(path.iter().all(|&(q, r)| !(q == 1 && r > -3)));
+/// ```
+pub fn from_fn<N, F>(successors: F) -> FnGraph<N, F>
+where
+    N: Copy + Eq + Hash,
+    F: Fn(N) -> Vec<(N, u32)>,
+{
+    FnGraph {
+        successors,
+        node: PhantomData,
+    }
+}
+
+impl<N, F> Graph for FnGraph<N, F>
+where
+    N: Copy + Eq + Hash,
+    F: Fn(N) -> Vec<(N, u32)>,
+{
+    type Node = N;
+
+    fn successors(&self, node: N) -> Vec<(N, u32)> {
+        (self.successors)(node)
+    }
+}
+
+/// A `Graph` whose step costs come from a closure, see `with_step_cost`.
+pub struct StepCostGraph<G, F> {
+    graph: G,
+    step_cost: F,
+}
+
+/// Replaces the step costs of `graph` by `step_cost(from, to)`, so a step may
+/// cost more by its kind (diagonal or straight) or by both of its ends
+/// (uphill or downhill), not only by the cell it enters.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::graph::{self, Graph, GridGraph};
+///
+/// let grid = vec![vec![0; 3]; 2];
+/// let graph = GridGraph::new(&grid, |row, col, grid| grid[row][col] == 1);
+/// // Going down a row costs 5.
+/// let graph = graph::with_step_cost(graph, |from: (i32, i32), to: (i32, i32)| {
+///     if to.0 > from.0 { 5 } else { 1 }
+/// });
+///
+/// assert_eq!(graph.successors((0, 0)), vec![((1, 0), 5), ((0, 1), 1)]);
+/// ```
+pub fn with_step_cost<G, F>(graph: G, step_cost: F) -> StepCostGraph<G, F>
+where
+    G: Graph,
+    F: Fn(G::Node, G::Node) -> u32,
+{
+    StepCostGraph { graph, step_cost }
+}
+
+impl<G, F> Graph for StepCostGraph<G, F>
+where
+    G: Graph,
+    F: Fn(G::Node, G::Node) -> u32,
+{
+    type Node = G::Node;
+
+    fn successors(&self, node: G::Node) -> Vec<(G::Node, u32)> {
+        self.graph
+            .successors(node)
+            .into_iter()
+            .map(|(neighbor, _)| (neighbor, (self.step_cost)(node, neighbor)))
+            .collect()
+    }
+}
+
+/// Adapter exposing a 2D grid (vector of vectors) as a `Graph`.
+///
+/// Nodes are `(row, col)` tuples, a node is connected to its non-solid
+/// up/down/left/right neighbors (see `get_neighbors`) and every step costs `1`,
+/// unless another `MovementModel` is given with `with_movement`.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::graph::{Graph, GridGraph};
+///
+/// let grid = vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+/// ];
+/// let graph = GridGraph::new(&grid, |row, col, grid| grid[row][col] == 1);
+///
+/// assert_eq!(graph.successors((0, 0)), vec![((1, 0), 1)]);
+/// assert_eq!(graph.successors((1, 1)), vec![((1, 0), 1), ((1, 2), 1)]);
+/// ```
+pub struct GridGraph<'a, F> {
+    grid: &'a Vec<Vec<i32>>,
+    is_solid: F,
+    movement: MovementModel,
+}
+
+impl<'a, F> GridGraph<'a, F>
+where
+    F: Fn(usize, usize, &Vec<Vec<i32>>) -> bool,
+{
+    /// Creates the adapter from a grid and the predicate telling which cells are solid.
+    pub fn new(grid: &'a Vec<Vec<i32>>, is_solid: F) -> Self {
+        GridGraph {
+            grid,
+            is_solid,
+            movement: MovementModel::four_way(),
+        }
+    }
+
+    /// Replaces the movement model, e.g. to allow diagonal steps.
+    pub fn with_movement(mut self, movement: MovementModel) -> Self {
+        self.movement = movement;
+        self
+    }
+}
+
+impl<F> Graph for GridGraph<'_, F>
+where
+    F: Fn(usize, usize, &Vec<Vec<i32>>) -> bool,
+{
+    type Node = (i32, i32);
+
+    fn successors(&self, node: (i32, i32)) -> Vec<((i32, i32), u32)> {
+        self.movement
+            .neighbors(node.0, node.1, self.grid, &self.is_solid)
+    }
+}
+
+/// The cost marking a cell of a cost grid as impassable.
+pub const IMPASSABLE: u32 = u32::MAX;
+
+/// The costs of the any-angle graphs, `WaypointGraph` and `NavMesh`, are
+/// distances in hundredths of a cell, as the searches add up integer costs.
+/// Their steps round the distances up and their heuristics round them down,
+/// so the heuristics never overestimate.
+pub const COST_SCALE: u32 = 100;
+
+/// Adapter exposing a cost grid (vector of vectors of per-cell costs) as a `Graph`.
+///
+/// Moving up/down/left/right into a cell costs the value stored in that cell,
+/// cells holding `IMPASSABLE` can't be entered.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::astar_graph;
+/// use pathfinding::graph::{WeightedGridGraph, IMPASSABLE};
+/// use pathfinding::manhattan_distance;
+///
+/// // Walking through the swamp in the middle is more expensive than going around.
+/// let costs = vec![
+///     vec![1, 1, 1],
+///     vec![1, 9, 1],
+///     vec![1, 1, IMPASSABLE],
+/// ];
+/// let graph = WeightedGridGraph::new(&costs);
+///
+/// let path = astar_graph(&graph, (1, 0), (1, 2), manhattan_distance);
+///
+/// assert_eq!(path.unwrap(), vec![(1, 0), (0, 0), (0, 1), (0, 2), (1, 2)]);
+/// ```
+pub struct WeightedGridGraph<'a> {
+    costs: &'a Vec<Vec<u32>>,
+}
+
+impl<'a> WeightedGridGraph<'a> {
+    /// Creates the adapter from a cost grid.
+    pub fn new(costs: &'a Vec<Vec<u32>>) -> Self {
+        WeightedGridGraph { costs }
+    }
+}
+
+impl Graph for WeightedGridGraph<'_> {
+    type Node = (i32, i32);
+
+    fn successors(&self, (row, col): (i32, i32)) -> Vec<((i32, i32), u32)> {
+        [(-1, 0), (0, -1), (1, 0), (0, 1)]
+            .iter()
+            .map(|(dr, dc)| (row + dr, col + dc))
+            .filter_map(|(r, c)| {
+                let cost = *self
+                    .costs
+                    .get(usize::try_from(r).ok()?)?
+                    .get(usize::try_from(c).ok()?)?;
+                (cost != IMPASSABLE).then_some(((r, c), cost))
+            })
+            .collect()
+    }
+}
+
+/// Adapter exposing a grid of `Cell`s as a `Graph`.
+///
+/// Nodes are `(row, col)` tuples. A step into a passable cell costs the cost of
+/// the move (see `MovementModel`, `1` for up/down/left/right by default) times
+/// the cost of the cell.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::graph::{CellGraph, Graph};
+///
+/// let walkable = vec![
+///     vec![true, false],
+///     vec![true, true],
+/// ];
+/// let graph = CellGraph::new(&walkable);
+///
+/// assert_eq!(graph.successors((0, 0)), vec![((1, 0), 1)]);
+/// assert_eq!(graph.get((0, 1)), Some(&false));
+/// ```
+pub struct CellGraph<'a, C> {
+    cells: &'a Vec<Vec<C>>,
+    movement: MovementModel,
+}
+
+impl<'a, C: Cell> CellGraph<'a, C> {
+    /// Creates the adapter from a grid of cells, moving up/down/left/right.
+    pub fn new(cells: &'a Vec<Vec<C>>) -> Self {
+        CellGraph {
+            cells,
+            movement: MovementModel::four_way(),
+        }
+    }
+
+    /// Replaces the movement model, e.g. to allow diagonal steps.
+    pub fn with_movement(mut self, movement: MovementModel) -> Self {
+        self.movement = movement;
+        self
+    }
+
+    /// The cell at `(row, col)`, `None` if it is outside of the grid.
+    pub fn get(&self, (row, col): (i32, i32)) -> Option<&C> {
+        self.cells
+            .get(usize::try_from(row).ok()?)?
+            .get(usize::try_from(col).ok()?)
+    }
+}
+
+impl<C: Cell> Graph for CellGraph<'_, C> {
+    type Node = (i32, i32);
+
+    fn successors(&self, (row, col): (i32, i32)) -> Vec<((i32, i32), u32)> {
+        let width = self.cells.first().map_or(0, Vec::len);
+        self.movement
+            .neighbors_in(row, col, self.cells.len(), width, |r, c| {
+                self.get((r, c)).is_some_and(Cell::passable)
+            })
+            .into_iter()
+            .filter_map(|(cell, cost)| Some((cell, cost.saturating_mul(self.get(cell)?.cost()))))
+            .collect()
+    }
+}
+
+/// Adapter exposing a `Grid3` of `Cell`s as a `Graph` of voxels.
+///
+/// A step into a passable cell costs the cost of the cell, see `Connectivity`
+/// for the steps taken.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::graph::{Graph, Grid3Graph};
+/// use pathfinding::grid3::{Connectivity, Grid3};
+///
+/// let mut grid: Grid3<i32> = Grid3::new(2, 2, 2);
+/// grid.set((1, 0, 0), 1);
+///
+/// let six = Grid3Graph::new(&grid, Connectivity::Six);
+/// assert_eq!(six.successors((0, 0, 0)), vec![((0, 1, 0), 1), ((0, 0, 1), 1)]);
+///
+/// // The diagonal steps passing the wall at (1, 0, 0) aren't taken.
+/// let twenty_six = Grid3Graph::new(&grid, Connectivity::TwentySix);
+/// assert_eq!(
+///     twenty_six.successors((0, 0, 0)),
+///     vec![((0, 1, 0), 1), ((0, 0, 1), 1), ((0, 1, 1), 1)]
+/// );
+/// ```
+pub struct Grid3Graph<'a, C> {
+    grid: &'a Grid3<C>,
+    offsets: Vec<Voxel>,
+}
+
+impl<'a, C: Cell> Grid3Graph<'a, C> {
+    /// Creates the adapter from a grid and the voxels connected to each other.
+    pub fn new(grid: &'a Grid3<C>, connectivity: Connectivity) -> Self {
+        Grid3Graph {
+            grid,
+            offsets: connectivity.offsets(),
+        }
+    }
+
+    fn is_free(&self, voxel: Voxel) -> bool {
+        self.grid.get(voxel).is_some_and(Cell::passable)
+    }
+}
+
+impl<C: Cell> Graph for Grid3Graph<'_, C> {
+    type Node = Voxel;
+
+    fn successors(&self, (x, y, z): Voxel) -> Vec<(Voxel, u32)> {
+        self.offsets
+            .iter()
+            .filter(|&&(dx, dy, dz)| {
+                // Every voxel of the box spanned by the step must be free.
+                (0..=dx.abs()).all(|ix| {
+                    (0..=dy.abs()).all(|iy| {
+                        (0..=dz.abs()).all(|iz| {
+                            self.is_free((
+                                x + ix * dx.signum(),
+                                y + iy * dy.signum(),
+                                z + iz * dz.signum(),
+                            ))
+                        })
+                    })
+                })
+            })
+            .filter_map(|&(dx, dy, dz)| {
+                let neighbor = (x + dx, y + dy, z + dz);
+                Some((neighbor, self.grid.get(neighbor)?.cost()))
+            })
+            .collect()
+    }
+}
@@ -1,73 +1,2113 @@
-use std::ops;
+//! A fixed-size 2D grid of cells.
 
+use alloc::vec;
+use alloc::vec::Vec;
+use core::hash::{Hash, Hasher};
+use core::{fmt, ops};
 
+use crate::cell::Cell;
+use crate::coord::Coord;
+use crate::movement::MovementModel;
+use crate::search::SearchError;
 
+/// A `width` x `height` grid of cells, stored row by row in one vector.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::astar;
+/// use pathfinding::grid::Grid;
+/// use pathfinding::manhattan_distance;
+///
+/// let mut grid: Grid<i32> = Grid::new(3, 2);
+/// grid.set(0, 1, 1);
+///
+/// assert_eq!(grid.get(0, 1), Some(&1));
+/// assert_eq!(grid.get(2, 0), None);
+///
+/// let path = astar((0, 0), (0, 2), &grid.to_vec(), manhattan_distance, |row, col, grid| {
+///     grid[row][col] == 1
+/// });
+/// assert_eq!(path.map(|path| path.cost()), Ok(4));
+/// assert_eq!(grid.as_slice(), &[0, 1, 0, 0, 0, 0]);
+/// ```
+///
+/// Grids are `Clone`, `PartialEq`, `Eq` and `Hash` when their cells are, so
+/// they can be compared or used as keys, e.g. of memoized searches. Only the
+/// size and the cells count, not whether the grid tracks its changes:
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use pathfinding::grid::Grid;
+/// use pathfinding::{astar, manhattan_distance};
+///
+/// let mut costs: HashMap<Grid<i32>, Option<u32>> = HashMap::new();
+/// let mut cost_across = |grid: &Grid<i32>| {
+///     *costs.entry(grid.clone()).or_insert_with(|| {
+///         let cells = grid.to_vec();
+///         let end = (0, grid.width as i32 - 1);
+///         astar((0, 0), end, &cells, manhattan_distance, |row, col, grid| grid[row][col] == 1)
+///             .ok()
+///             .map(|path| path.cost())
+///     })
+/// };
+///
+/// let mut grid: Grid<i32> = Grid::new(3, 2);
+/// assert_eq!(cost_across(&grid), Some(2));
+/// grid.set(0, 1, 1);
+/// assert_eq!(cost_across(&grid), Some(4));
+/// assert_eq!(costs.len(), 2);
+/// ```
+///
+/// With the `serde` feature grids serialize as their size and their cells row
+/// after row, and the size is checked against the cells on load:
+///
+/// ```
+/// # #[cfg(feature = "serde")]
+/// # {
+/// use pathfinding::grid::Grid;
+///
+/// let grid = Grid::from_vec(vec![vec![0, 1, 0], vec![0, 0, 1]]);
+/// let json = serde_json::to_string(&grid).unwrap();
+///
+/// assert_eq!(json, r#"{"width":3,"height":2,"cells":[0,1,0,0,0,1]}"#);
+/// assert_eq!(serde_json::from_str::<Grid<i32>>(&json).unwrap(), grid);
+/// assert!(serde_json::from_str::<Grid<i32>>(r#"{"width":3,"height":2,"cells":[0,1]}"#).is_err());
+/// # }
+/// ```
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "GridParts<T>"))]
 pub struct Grid<T> {
+    /// The number of columns.
     pub width: usize,
+    /// The number of rows.
     pub height: usize,
-    cells: Vec<Vec<T>>,
+    cells: Vec<T>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    dirty: DirtyRegion,
 }
 
-impl<T: Copy + std::default::Default> Grid<T>{
+/// The cells changed since the last `Grid::take_dirty`, recorded only while
+/// the grid tracks them. It isn't part of the value of the grid: compared,
+/// hashed and serialized grids ignore it.
+#[derive(Debug, Clone, Copy, Default)]
+struct DirtyRegion {
+    tracking: bool,
+    rect: Option<Rect>,
+}
+
+impl<T: PartialEq> PartialEq for Grid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width && self.height == other.height && self.cells == other.cells
+    }
+}
+
+impl<T: Eq> Eq for Grid<T> {}
+
+impl<T: Hash> Hash for Grid<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.width.hash(state);
+        self.height.hash(state);
+        self.cells.hash(state);
+    }
+}
+
+/// What a `Grid` is deserialized from, checked before it becomes a `Grid`.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct GridParts<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<T> TryFrom<GridParts<T>> for Grid<T> {
+    type Error = &'static str;
+
+    fn try_from(parts: GridParts<T>) -> Result<Self, Self::Error> {
+        if parts.width.checked_mul(parts.height) != Some(parts.cells.len()) {
+            return Err("a grid needs width * height cells");
+        }
+        Ok(Grid {
+            width: parts.width,
+            height: parts.height,
+            cells: parts.cells,
+            dirty: DirtyRegion::default(),
+        })
+    }
+}
+
+impl<T: Clone + Default> Grid<T> {
+    /// Creates a grid filled with the default value of `T`.
     pub fn new(width: usize, height: usize) -> Self {
-        let cells = vec![vec![T::default(); width]; height];
-        Grid { width, height, cells }
+        Grid::new_with(width, height, T::default())
     }
+}
 
+impl<T: Clone> Grid<T> {
+    /// Creates a grid filled with clones of `value`, for cell types without a
+    /// `Default`.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::Grid;
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// enum Tile {
+    ///     Grass,
+    ///     Sign(String),
+    /// }
+    ///
+    /// let mut map = Grid::new_with(3, 2, Tile::Grass);
+    /// map.set(0, 2, Tile::Sign("Exit".to_string()));
+    ///
+    /// assert_eq!(map.get(0, 2), Some(&Tile::Sign("Exit".to_string())));
+    /// assert_eq!(map.iter().filter(|&tile| *tile == Tile::Grass).count(), 5);
+    /// ```
+    pub fn new_with(width: usize, height: usize, value: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+            dirty: DirtyRegion::default(),
+        }
+    }
+
+    /// Creates a grid from its rows. Without rows the grid is empty.
+    ///
+    /// ### Panics
+    ///
+    /// If the rows aren't all as long as the first one, see `try_from_vec`.
     pub fn from_vec(cells: Vec<Vec<T>>) -> Self {
-        let width = cells[0].len();
+        Grid::try_from_vec(cells).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Creates a grid from its rows, e.g. ones read from user input, checking
+    /// that they are all as long as the first one. Without rows the grid is
+    /// empty.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::{Grid, RaggedRow};
+    ///
+    /// let grid = Grid::try_from_vec(vec![vec![0, 1], vec![1, 0]]).unwrap();
+    /// assert_eq!((grid.width, grid.height), (2, 2));
+    ///
+    /// let ragged = Grid::try_from_vec(vec![vec![0, 1], vec![0, 0], vec![1]]);
+    /// assert_eq!(ragged, Err(RaggedRow { row: 2, len: 1, expected: 2 }));
+    /// assert!(Grid::<i32>::try_from_vec(vec![]).unwrap().as_slice().is_empty());
+    /// ```
+    pub fn try_from_vec(cells: Vec<Vec<T>>) -> Result<Self, RaggedRow> {
+        let width = cells.first().map_or(0, Vec::len);
+        if let Some((row, cells)) = cells.iter().enumerate().find(|(_, row)| row.len() != width) {
+            return Err(RaggedRow {
+                row,
+                len: cells.len(),
+                expected: width,
+            });
+        }
         let height = cells.len();
-        Grid { width, height, cells }
+        Ok(Grid {
+            width,
+            height,
+            cells: cells.concat(),
+            dirty: DirtyRegion::default(),
+        })
     }
 
+    /// Sets every cell to `value`.
+    pub fn fill(&mut self, value: T) {
+        self.mark_all_dirty();
+        self.cells.fill(value);
+    }
+
+    /// Changes the size of the grid to `width` x `height`. The cells inside of
+    /// both sizes keep their values, the new ones are set to `fill`.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::Grid;
+    ///
+    /// let mut grid = Grid::from_vec(vec![vec![1, 2], vec![3, 4]]);
+    /// grid.resize(3, 1, 0);
+    /// assert_eq!(grid.to_vec(), vec![vec![1, 2, 0]]);
+    ///
+    /// grid.resize(2, 2, 9);
+    /// assert_eq!(grid.to_vec(), vec![vec![1, 2], vec![9, 9]]);
+    /// ```
+    pub fn resize(&mut self, width: usize, height: usize, fill: T) {
+        let mut cells = vec![fill; width * height];
+        for row in 0..self.height.min(height) {
+            let kept = self.width.min(width);
+            cells[row * width..row * width + kept]
+                .clone_from_slice(&self.cells[row * self.width..row * self.width + kept]);
+        }
+        self.width = width;
+        self.height = height;
+        self.cells = cells;
+        self.mark_all_dirty();
+    }
+
+    /// Copies the cells into a vector of rows, the form taken by `astar`.
+    pub fn to_vec(&self) -> Vec<Vec<T>> {
+        if self.width == 0 {
+            return vec![vec![]; self.height];
+        }
+        self.cells.chunks(self.width).map(<[T]>::to_vec).collect()
+    }
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid whose cell at `row`, `col` is `f(row, col)`, called row
+    /// after row.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::Grid;
+    ///
+    /// // A checkerboard, and the distances to the top left corner.
+    /// let board = Grid::from_fn(3, 2, |row, col| (row + col) as i32 % 2);
+    /// assert_eq!(board.to_vec(), vec![vec![0, 1, 0], vec![1, 0, 1]]);
+    ///
+    /// let distances = Grid::from_fn(3, 2, |row, col| row + col);
+    /// assert_eq!(distances[(1, 2)], 3);
+    /// ```
+    pub fn from_fn<F: FnMut(usize, usize) -> T>(width: usize, height: usize, mut f: F) -> Self {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(|(row, col)| f(row, col))
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+            dirty: DirtyRegion::default(),
+        }
+    }
+
+    /// Replaces the cell at `row`, `col` and returns the old value, `None` if
+    /// it is outside of the grid.
+    pub fn set(&mut self, row: usize, col: usize, value: T) -> Option<T> {
+        self.get_mut(row, col)
+            .map(|cell| core::mem::replace(cell, value))
+    }
+
+    /// The cells, row after row.
+    pub fn as_slice(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// The cell at `row`, `col`, `None` if it is outside of the grid.
     pub fn get(&self, row: usize, col: usize) -> Option<&T> {
         if row < self.height && col < self.width {
-            Some(&self.cells[row][col])
+            Some(&self.cells[row * self.width + col])
         } else {
             None
         }
     }
 
+    /// The cell at `row`, `col` for modification, `None` if it is outside of the grid.
     pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
         if row < self.height && col < self.width {
-            Some(&mut self.cells[row][col])
+            self.mark_dirty(Rect::cell((row as i32, col as i32)));
+            Some(&mut self.cells[row * self.width + col])
         } else {
             None
         }
     }
 
-    pub fn set(&mut self, row: usize, col: usize, value: T) -> Option<T> {
+    /// Whether `pos` is a cell of the grid, for the `(i32, i32)` nodes of the
+    /// grid searches or a `Coord`.
+    pub fn in_bounds(&self, pos: impl Into<Coord>) -> bool {
+        self.cell_at(pos.into()).is_some()
+    }
+
+    /// Whether `pos` is a cell of the grid that `passable` accepts.
+    pub fn is_passable<P: Fn(&T) -> bool>(&self, pos: impl Into<Coord>, passable: P) -> bool {
+        self.cell_at(pos.into()).is_some_and(passable)
+    }
+
+    /// Checks the endpoints of a search on the grid like the search functions
+    /// do before searching, `passable` telling the cells that can be entered.
+    ///
+    /// ### Returns
+    ///
+    /// The `SearchError` for an endpoint outside of the grid (checked first)
+    /// or on a cell that can't be entered.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::Grid;
+    /// use pathfinding::search::SearchError;
+    ///
+    /// let grid = Grid::from_vec(vec![vec![0, 1, 0], vec![0, 0, 0]]);
+    /// let free = |&cell: &i32| cell == 0;
+    ///
+    /// assert!(grid.in_bounds((1, 2)) && !grid.in_bounds((-1, 0)));
+    /// assert!(!grid.is_passable((0, 1), free));
+    ///
+    /// assert_eq!(grid.validate_endpoints((0, 0), (1, 2), free), Ok(()));
+    /// assert_eq!(grid.validate_endpoints((0, 0), (0, 1), free), Err(SearchError::GoalBlocked));
+    /// assert_eq!(grid.validate_endpoints((0, 1), (2, 0), free), Err(SearchError::GoalOutOfBounds));
+    /// ```
+    pub fn validate_endpoints<P: Fn(&T) -> bool>(
+        &self,
+        start: impl Into<Coord>,
+        end: impl Into<Coord>,
+        passable: P,
+    ) -> Result<(), SearchError> {
+        let free = |pos| self.cell_at(pos).map(&passable);
+        crate::endpoint_error(free(start.into()), free(end.into()))
+    }
+
+    /// Whether the straight line between the centers of the cells `a` and `b`
+    /// only crosses cells of the grid that aren't solid. Every cell the line
+    /// touches counts (a supercover line), and a line passing exactly through
+    /// the corner between two cells needs both of them free, see
+    /// `waypoint::line_of_sight`.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::{ascii_walls, Grid};
+    ///
+    /// let grid = Grid::from_ascii("....\n.#..\n....", ascii_walls).unwrap().grid;
+    /// let is_solid = |&cell: &i32| cell == 1;
+    ///
+    /// // Passing next to the wall, or through it.
+    /// assert!(grid.line_of_sight((0, 0), (0, 3), is_solid));
+    /// assert!(grid.line_of_sight((0, 2), (2, 3), is_solid));
+    /// assert!(!grid.line_of_sight((0, 0), (2, 2), is_solid));
+    /// // Through the corner between the wall and a free cell.
+    /// assert!(!grid.line_of_sight((0, 1), (1, 2), is_solid));
+    /// assert!(grid.line_of_sight((0, 2), (1, 3), is_solid));
+    /// // Off the grid.
+    /// assert!(!grid.line_of_sight((0, 0), (0, 4), is_solid));
+    /// ```
+    pub fn line_of_sight<F: Fn(&T) -> bool>(
+        &self,
+        a: (i32, i32),
+        b: (i32, i32),
+        is_solid: F,
+    ) -> bool {
+        crate::waypoint::line_of_sight(a, b, |row, col| {
+            self.is_passable((row, col), |cell| !is_solid(cell))
+        })
+    }
+
+    /// The cell at `pos`, `None` if it is outside of the grid.
+    fn cell_at(&self, pos: Coord) -> Option<&T> {
+        let (row, col) = pos.to_usize()?;
+        self.get(row, col)
+    }
+
+    /// How much memory the grid takes: the grid itself and the capacity of its
+    /// cell storage. Memory owned by the cells (e.g. of `String` cells) isn't
+    /// counted.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::Grid;
+    ///
+    /// let grid: Grid<u32> = Grid::new(100, 50);
+    /// let usage = grid.memory_usage();
+    ///
+    /// assert_eq!(usage.cells, 5000);
+    /// assert!(usage.bytes >= 5000 * 4);
+    /// assert_eq!(usage.chunks, 0);
+    /// ```
+    pub fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage {
+            cells: self.cells.len(),
+            bytes: core::mem::size_of::<Self>() + self.cells.capacity() * core::mem::size_of::<T>(),
+            chunks: 0,
+        }
+    }
+
+    /// The mapping between the cells of the grid and flat indices.
+    pub fn index(&self) -> GridIndex {
+        GridIndex::new(self.width, self.height)
+    }
+
+    /// Starts or stops recording the cells changed through the methods taking
+    /// `&mut self`, for `take_dirty`. A cell borrowed mutably (`get_mut`,
+    /// `iter_mut`, ...) counts as changed even if it was left as it was.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::{Grid, Rect};
+    ///
+    /// let mut grid: Grid<i32> = Grid::new(8, 8);
+    /// grid.track_dirty(true);
+    /// assert_eq!(grid.take_dirty(), None);
+    ///
+    /// grid.set(1, 2, 1);
+    /// grid[(4, 3)] = 1;
+    /// assert_eq!(grid.take_dirty(), Some(Rect::new((1, 2), (4, 3))));
+    /// assert_eq!(grid.take_dirty(), None);
+    ///
+    /// grid.fill(0);
+    /// assert_eq!(grid.take_dirty(), Some(Rect::new((0, 0), (7, 7))));
+    /// ```
+    pub fn track_dirty(&mut self, enabled: bool) {
+        self.dirty = DirtyRegion {
+            tracking: enabled,
+            rect: None,
+        };
+    }
+
+    /// The smallest rectangle holding the cells changed since tracking started
+    /// or since the last call, `None` if none changed or the grid doesn't
+    /// track them (see `track_dirty`).
+    pub fn take_dirty(&mut self) -> Option<Rect> {
+        self.dirty.rect.take()
+    }
+
+    /// Records that the cells of `rect` may have changed.
+    fn mark_dirty(&mut self, rect: Rect) {
+        if self.dirty.tracking {
+            self.dirty.rect = Some(match self.dirty.rect {
+                Some(dirty) => dirty.union(rect),
+                None => rect,
+            });
+        }
+    }
+
+    /// Records that every cell may have changed.
+    fn mark_all_dirty(&mut self) {
+        if self.width > 0 && self.height > 0 {
+            let last = (self.height as i32 - 1, self.width as i32 - 1);
+            self.mark_dirty(Rect::new((0, 0), last));
+        }
+    }
+
+    /// The cells, row after row.
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    /// The cells row after row, for modification.
+    pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, T> {
+        self.mark_all_dirty();
+        self.cells.iter_mut()
+    }
+
+    /// The cells row after row, with their `(row, col)`.
+    pub fn enumerate_cells(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(index, cell)| ((index / width, index % width), cell))
+    }
+
+    /// The rows, from the top.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        (0..self.height).map(move |row| self.row(row))
+    }
+
+    /// The cells of the row `row`, from the left.
+    ///
+    /// ### Panics
+    ///
+    /// If `row` is outside of the grid.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::Grid;
+    ///
+    /// let mut grid = Grid::from_vec(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+    /// assert_eq!(grid.row(1), &[4, 5, 6]);
+    /// assert_eq!(grid.col(2).collect::<Vec<_>>(), vec![&3, &6]);
+    ///
+    /// grid.row_mut(0).reverse();
+    /// grid.swap((0, 0), (1, 2));
+    /// assert_eq!(grid.to_vec(), vec![vec![6, 2, 1], vec![4, 5, 3]]);
+    /// ```
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.height, "the row is outside of the grid");
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    /// The cells of the row `row` for modification.
+    ///
+    /// ### Panics
+    ///
+    /// If `row` is outside of the grid.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::{Grid, Rect};
+    ///
+    /// let mut grid: Grid<i32> = Grid::new(3, 2);
+    /// grid.track_dirty(true);
+    /// grid.row_mut(1).fill(7);
+    /// assert_eq!(grid.take_dirty(), Some(Rect::new((1, 0), (1, 2))));
+    ///
+    /// // A row without cells changes nothing.
+    /// let mut empty: Grid<i32> = Grid::new(0, 2);
+    /// empty.track_dirty(true);
+    /// assert!(empty.row_mut(1).is_empty());
+    /// assert_eq!(empty.take_dirty(), None);
+    /// ```
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        assert!(row < self.height, "the row is outside of the grid");
+        if self.width > 0 {
+            self.mark_dirty(Rect::new(
+                (row as i32, 0),
+                (row as i32, self.width as i32 - 1),
+            ));
+        }
+        &mut self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    /// The cells of the column `col`, from the top.
+    ///
+    /// ### Panics
+    ///
+    /// If `col` is outside of the grid.
+    pub fn col(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(col < self.width, "the column is outside of the grid");
+        self.cells[col..].iter().step_by(self.width)
+    }
+
+    /// Swaps the cells at `a` and `b`, given as `(row, col)`.
+    ///
+    /// ### Panics
+    ///
+    /// If either cell is outside of the grid.
+    pub fn swap(&mut self, a: (usize, usize), b: (usize, usize)) {
+        for (row, col) in [a, b] {
+            assert!(
+                row < self.height && col < self.width,
+                "the cell is outside of the grid"
+            );
+        }
+        self.mark_dirty(Rect::cell((a.0 as i32, a.1 as i32)));
+        self.mark_dirty(Rect::cell((b.0 as i32, b.1 as i32)));
+        self.cells
+            .swap(a.0 * self.width + a.1, b.0 * self.width + b.1);
+    }
+
+    /// A grid of the same size with `f` applied to every cell.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::Grid;
+    ///
+    /// // Heights to walls: anything above 5 is too steep.
+    /// let mut heights = Grid::from_vec(vec![vec![1, 7, 3], vec![9, 2, 6]]);
+    /// let walls = heights.map(|&height| height > 5);
+    /// assert_eq!(walls.as_slice(), &[false, true, false, true, false, true]);
+    ///
+    /// heights.transform(|height| *height = (*height).min(5));
+    /// assert_eq!(heights.as_slice(), &[1, 5, 3, 5, 2, 5]);
+    /// ```
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+            dirty: DirtyRegion::default(),
+        }
+    }
+
+    /// A grid of the same size with `f` applied to the cells of `self` and
+    /// `other` at the same position, e.g. to lay dynamic obstacles over the
+    /// base terrain, or to merge two obstacle masks.
+    ///
+    /// ### Returns
+    ///
+    /// The combined grid, or `SizeMismatch` if the grids don't have the same
+    /// size.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::{Grid, SizeMismatch};
+    ///
+    /// let terrain = Grid::from_vec(vec![vec![0, 1], vec![0, 0]]);
+    /// let units = Grid::from_vec(vec![vec![false, false], vec![true, false]]);
+    ///
+    /// let walls = terrain.combine(&units, |&wall, &unit| i32::from(wall == 1 || unit));
+    /// assert_eq!(walls.unwrap().to_vec(), vec![vec![0, 1], vec![1, 0]]);
+    ///
+    /// let wide: Grid<i32> = Grid::new(3, 2);
+    /// assert_eq!(
+    ///     terrain.combine(&wide, |a, b| a + b),
+    ///     Err(SizeMismatch { expected: (2, 2), found: (3, 2) })
+    /// );
+    /// ```
+    pub fn combine<U, V, F>(&self, other: &Grid<U>, mut f: F) -> Result<Grid<V>, SizeMismatch>
+    where
+        F: FnMut(&T, &U) -> V,
+    {
+        if (self.width, self.height) != (other.width, other.height) {
+            return Err(SizeMismatch {
+                expected: (self.width, self.height),
+                found: (other.width, other.height),
+            });
+        }
+        Ok(Grid {
+            width: self.width,
+            height: self.height,
+            cells: self
+                .cells
+                .iter()
+                .zip(&other.cells)
+                .map(|(a, b)| f(a, b))
+                .collect(),
+            dirty: DirtyRegion::default(),
+        })
+    }
+
+    /// Applies `f` to every cell in place.
+    pub fn transform<F: FnMut(&mut T)>(&mut self, f: F) {
+        self.iter_mut().for_each(f);
+    }
+
+    /// The grid turned a quarter clockwise: the left column becomes the top
+    /// row, and the width and height swap.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::Grid;
+    ///
+    /// let grid = Grid::from_vec(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+    ///
+    /// assert_eq!(grid.rotate90().to_vec(), vec![vec![4, 1], vec![5, 2], vec![6, 3]]);
+    /// assert_eq!(grid.rotate180().to_vec(), vec![vec![6, 5, 4], vec![3, 2, 1]]);
+    /// assert_eq!(grid.rotate270().to_vec(), vec![vec![3, 6], vec![2, 5], vec![1, 4]]);
+    /// assert_eq!(grid.flip_horizontal().to_vec(), vec![vec![3, 2, 1], vec![6, 5, 4]]);
+    /// assert_eq!(grid.flip_vertical().to_vec(), vec![vec![4, 5, 6], vec![1, 2, 3]]);
+    /// ```
+    pub fn rotate90(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let (width, height) = (self.width, self.height);
+        Grid::from_fn(height, width, |row, col| {
+            self.cells[(height - 1 - col) * width + row].clone()
+        })
+    }
+
+    /// The grid turned half a turn.
+    pub fn rotate180(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().rev().cloned().collect(),
+            dirty: DirtyRegion::default(),
+        }
+    }
+
+    /// The grid turned a quarter counterclockwise: the right column becomes
+    /// the top row, and the width and height swap.
+    pub fn rotate270(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        Grid::from_fn(self.height, width, |row, col| {
+            self.cells[col * width + width - 1 - row].clone()
+        })
+    }
+
+    /// The grid mirrored left to right.
+    pub fn flip_horizontal(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self
+                .rows()
+                .flat_map(|row| row.iter().rev().cloned())
+                .collect(),
+            dirty: DirtyRegion::default(),
+        }
+    }
+
+    /// The grid mirrored top to bottom.
+    pub fn flip_vertical(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.rows().rev().flatten().cloned().collect(),
+            dirty: DirtyRegion::default(),
+        }
+    }
+
+    /// Reads a map drawn with one character per cell, one line per row.
+    /// Blank lines and the spaces around the rows are skipped, so the map can
+    /// be indented in the source. Letters and digits are markers: their
+    /// positions are recorded, and they are cells too.
+    ///
+    /// ### Arguments
+    ///
+    /// * `text` - The map.
+    /// * `mapping` - The cell of each character, `None` if it can't be in a
+    ///   map, e.g. `ascii_walls`.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::{ascii_walls, Grid};
+    /// use pathfinding::{astar, manhattan_distance};
+    ///
+    /// let map = Grid::from_ascii(
+    ///     "
+    ///     S.#.
+    ///     .##E
+    ///     ....
+    ///     ",
+    ///     ascii_walls,
+    /// )
+    /// .unwrap();
+    ///
+    /// let (start, end) = (map.marker('S').unwrap(), map.marker('E').unwrap());
+    /// assert_eq!((start, end), ((0, 0), (1, 3)));
+    ///
+    /// let path = astar(start, end, &map.grid.to_vec(), manhattan_distance, |row, col, grid| {
+    ///     grid[row][col] == 1
+    /// });
+    /// assert_eq!(path.map(|path| path.cost()), Ok(6));
+    /// ```
+    pub fn from_ascii<F>(text: &str, mut mapping: F) -> Result<AsciiMap<T>, ParseAsciiError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let (mut width, mut height) = (None, 0);
+        let mut cells = vec![];
+        let mut markers = vec![];
+        for (line, row) in text.lines().enumerate() {
+            let row = row.trim();
+            if row.is_empty() {
+                continue;
+            }
+            let mut count = 0;
+            for (col, character) in row.chars().enumerate() {
+                let cell = mapping(character).ok_or(ParseAsciiError::UnknownCharacter {
+                    line: line + 1,
+                    character,
+                })?;
+                if character.is_ascii_alphanumeric() {
+                    markers.push((character, (height as i32, col as i32)));
+                }
+                cells.push(cell);
+                count += 1;
+            }
+            let expected = *width.get_or_insert(count);
+            if count != expected {
+                return Err(ParseAsciiError::RaggedRow {
+                    line: line + 1,
+                    width: count,
+                    expected,
+                });
+            }
+            height += 1;
+        }
+        Ok(AsciiMap {
+            grid: Grid {
+                width: width.unwrap_or(0),
+                height,
+                cells,
+                dirty: DirtyRegion::default(),
+            },
+            markers,
+        })
+    }
+
+    /// Draws the grid as text, one character per cell from `glyph` and one
+    /// line per row. Grids of `i32` also implement `Display`, drawing `0` as
+    /// `.` and anything else as `#`.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::{ascii_walls, Grid};
+    /// use pathfinding::{astar, manhattan_distance};
+    ///
+    /// let map = Grid::from_ascii("S.#.\n.##E\n....", ascii_walls).unwrap();
+    /// let (start, end) = (map.marker('S').unwrap(), map.marker('E').unwrap());
+    /// let path = astar(start, end, &map.grid.to_vec(), manhattan_distance, |row, col, grid| {
+    ///     grid[row][col] == 1
+    /// })
+    /// .unwrap();
+    ///
+    /// assert_eq!(map.grid.to_string(), "..#.\n.##.\n....");
+    /// assert_eq!(map.grid.display(|&cell| if cell == 1 { '█' } else { ' ' }).to_string(), "  █ \n ██ \n    ");
+    /// assert_eq!(map.grid.to_string_with_path(&path), "S.#.\n*##E\n****");
+    /// ```
+    pub fn display<F: Fn(&T) -> char>(&self, glyph: F) -> GridDisplay<'_, T, F> {
+        GridDisplay {
+            grid: self,
+            glyph,
+            path: &[],
+        }
+    }
+
+    /// The cells that differ between `self` and `other`, row after row, as
+    /// changes turning `self` into `other`. Storing the changes of every edit
+    /// is enough to undo and redo them, or to replay them on a copy of the map.
+    ///
+    /// ### Panics
+    ///
+    /// If the grids don't have the same size.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::{CellChange, Grid};
+    ///
+    /// let before = Grid::from_vec(vec![vec![0, 0], vec![0, 1]]);
+    /// let mut after = before.clone();
+    /// after.set(0, 1, 1);
+    /// after.set(1, 1, 0);
+    ///
+    /// let patch = before.diff(&after);
+    /// assert_eq!(patch[0], CellChange { cell: (0, 1), old: 0, new: 1 });
+    /// assert_eq!(patch.len(), 2);
+    ///
+    /// let mut copy = before.clone();
+    /// copy.apply_patch(&patch);
+    /// assert_eq!(copy, after);
+    /// copy.revert_patch(&patch);
+    /// assert_eq!(copy, before);
+    /// ```
+    pub fn diff(&self, other: &Grid<T>) -> Vec<CellChange<T>>
+    where
+        T: Clone + PartialEq,
+    {
+        assert!(
+            self.width == other.width && self.height == other.height,
+            "can't diff grids of different sizes"
+        );
+        self.enumerate_cells()
+            .zip(other.iter())
+            .filter(|((_, old), new)| old != new)
+            .map(|((cell, old), new)| CellChange {
+                cell,
+                old: old.clone(),
+                new: new.clone(),
+            })
+            .collect()
+    }
+
+    /// Sets the cells of `patch` to their new values. Changes of cells outside
+    /// of the grid are skipped.
+    pub fn apply_patch(&mut self, patch: &[CellChange<T>])
+    where
+        T: Clone,
+    {
+        for change in patch {
+            if let Some(cell) = self.get_mut(change.cell.0, change.cell.1) {
+                *cell = change.new.clone();
+            }
+        }
+    }
+
+    /// Sets the cells of `patch` back to their old values, undoing
+    /// `apply_patch`.
+    pub fn revert_patch(&mut self, patch: &[CellChange<T>])
+    where
+        T: Clone,
+    {
+        for change in patch.iter().rev() {
+            if let Some(cell) = self.get_mut(change.cell.0, change.cell.1) {
+                *cell = change.old.clone();
+            }
+        }
+    }
+
+    /// Labels the connected regions of free cells: cells get the same label
+    /// when a path of up, down, left and right steps joins them. Diagonal
+    /// steps that can't squeeze between two obstacles (see `CornerCutting`)
+    /// join the same regions.
+    ///
+    /// ### Returns
+    ///
+    /// A grid of the same size holding the label of every cell: `0` for solid
+    /// cells, the regions are numbered from `1` row by row.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::Grid;
+    ///
+    /// let grid = Grid::from_vec(vec![
+    ///     vec![0, 1, 0, 0],
+    ///     vec![0, 1, 1, 1],
+    ///     vec![0, 1, 0, 0],
+    /// ]);
+    /// let regions = grid.label_regions(|&cell| cell == 1);
+    ///
+    /// assert_eq!(regions.to_vec(), vec![vec![1, 0, 2, 2], vec![1, 0, 0, 0], vec![1, 0, 3, 3]]);
+    /// assert_eq!(regions.region_sizes(), vec![5, 3, 2, 2]);
+    /// // No path can join different regions.
+    /// assert_ne!(regions[(0, 0)], regions[(2, 3)]);
+    /// ```
+    pub fn label_regions<F: Fn(&T) -> bool>(&self, is_solid: F) -> Grid<u32> {
+        let mut labels = Grid {
+            width: self.width,
+            height: self.height,
+            cells: vec![0; self.cells.len()],
+            dirty: DirtyRegion::default(),
+        };
+        let mut count = 0;
+        let mut stack = vec![];
+        for first in 0..self.cells.len() {
+            if labels.cells[first] != 0 || is_solid(&self.cells[first]) {
+                continue;
+            }
+            count += 1;
+            labels.cells[first] = count;
+            stack.push(first);
+            while let Some(index) = stack.pop() {
+                let (row, col) = (index / self.width, index % self.width);
+                let neighbors = [
+                    (row > 0).then(|| index - self.width),
+                    (row + 1 < self.height).then(|| index + self.width),
+                    (col > 0).then(|| index - 1),
+                    (col + 1 < self.width).then(|| index + 1),
+                ];
+                for neighbor in neighbors.into_iter().flatten() {
+                    if labels.cells[neighbor] == 0 && !is_solid(&self.cells[neighbor]) {
+                        labels.cells[neighbor] = count;
+                        stack.push(neighbor);
+                    }
+                }
+            }
+        }
+        labels
+    }
+
+    /// Sets the cell at `(row, col)` to `value`, if it is in the grid.
+    fn paint(&mut self, (row, col): (i32, i32), value: &T)
+    where
+        T: Clone,
+    {
+        if let (Ok(row), Ok(col)) = (usize::try_from(row), usize::try_from(col)) {
+            if let Some(cell) = self.get_mut(row, col) {
+                *cell = value.clone();
+            }
+        }
+    }
+
+    /// Sets the cells of `rect` to `value`. Like the other drawing operations,
+    /// the parts of the shape outside of the grid are left out.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::{Grid, Rect};
+    ///
+    /// let mut grid: Grid<i32> = Grid::new(8, 6);
+    /// grid.fill_rect(Rect::new((0, 0), (1, 1)), 1);
+    /// grid.draw_line((5, 0), (3, 7), 1);
+    /// grid.draw_circle((2, 5), 1, 1);
+    /// assert_eq!(grid.to_string(), "##......\n##...#..\n....#.#.\n.....###\n..####..\n##......");
+    ///
+    /// // Everything above the line, but the inside of the circle.
+    /// assert_eq!(grid.flood_fill((0, 7), 2), 23);
+    /// assert_eq!((grid[(2, 5)], grid[(5, 7)]), (0, 0));
+    /// ```
+    pub fn fill_rect(&mut self, rect: Rect, value: T)
+    where
+        T: Clone,
+    {
+        for cell in self.view(rect).rect().cells() {
+            self.paint(cell, &value);
+        }
+    }
+
+    /// Sets the cells of the straight line from `from` to `to` to `value`
+    /// (Bresenham's line), both ends included.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::Grid;
+    ///
+    /// let mut grid: Grid<i32> = Grid::new(5, 2);
+    /// grid.draw_line((0, 0), (1, 3), 1);
+    /// grid.draw_line((1, 4), (0, 4), 1);
+    /// assert_eq!(grid.to_string(), "##..#\n..###");
+    ///
+    /// // Halfway between two cells, the line takes the lower one.
+    /// grid.fill(0);
+    /// grid.draw_line((0, 0), (1, 2), 1);
+    /// assert_eq!(grid.to_string(), "#....\n.##..");
+    /// ```
+    pub fn draw_line(&mut self, from: (i32, i32), to: (i32, i32), value: T)
+    where
+        T: Clone,
+    {
+        let (rows, cols) = (-(to.0 - from.0).abs(), (to.1 - from.1).abs());
+        let (step_row, step_col) = ((to.0 - from.0).signum(), (to.1 - from.1).signum());
+        let mut error = cols + rows;
+        let mut cell = from;
+        loop {
+            self.paint(cell, &value);
+            if cell == to {
+                break;
+            }
+            // Both tests look at the error before this step changes it.
+            let doubled = 2 * error;
+            if doubled >= rows {
+                error += rows;
+                cell.1 += step_col;
+            }
+            if doubled <= cols {
+                error += cols;
+                cell.0 += step_row;
+            }
+        }
+    }
+
+    /// Sets the cells of the outline of the circle of `radius` cells around
+    /// `center` to `value` (the midpoint circle algorithm).
+    pub fn draw_circle(&mut self, center: (i32, i32), radius: i32, value: T)
+    where
+        T: Clone,
+    {
+        let (mut x, mut y, mut error) = (radius, 0, 1 - radius);
+        while x >= y {
+            for (dr, dc) in [(y, x), (x, y)] {
+                for (row, col) in [(dr, dc), (dr, -dc), (-dr, dc), (-dr, -dc)] {
+                    self.paint((center.0 + row, center.1 + col), &value);
+                }
+            }
+            y += 1;
+            if error < 0 {
+                error += 2 * y + 1;
+            } else {
+                x -= 1;
+                error += 2 * (y - x) + 1;
+            }
+        }
+    }
+
+    /// Sets the cell at `start` and all the cells joined to it by up, down,
+    /// left and right steps over cells equal to it to `value`, like the paint
+    /// bucket of an image editor.
+    ///
+    /// ### Returns
+    ///
+    /// The number of cells set, `0` if `start` is outside of the grid or
+    /// already holds `value`.
+    pub fn flood_fill(&mut self, (row, col): (usize, usize), value: T) -> usize
+    where
+        T: Clone + PartialEq,
+    {
+        let Some(target) = self.get(row, col).cloned() else {
+            return 0;
+        };
+        if target == value {
+            return 0;
+        }
+        let mut count = 0;
+        let mut stack = vec![(row as i32, col as i32)];
+        while let Some((row, col)) = stack.pop() {
+            if row < 0 || col < 0 || self.get(row as usize, col as usize) != Some(&target) {
+                continue;
+            }
+            self.mark_dirty(Rect::cell((row, col)));
+            self.cells[row as usize * self.width + col as usize] = value.clone();
+            count += 1;
+            stack.extend([
+                (row - 1, col),
+                (row + 1, col),
+                (row, col - 1),
+                (row, col + 1),
+            ]);
+        }
+        count
+    }
+
+    /// The part of the grid inside of `rect`, without copying it. The parts of
+    /// `rect` outside of the grid are left out.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::{Grid, Rect};
+    ///
+    /// let grid = Grid::from_vec(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]);
+    ///
+    /// let view = grid.view(Rect::new((1, 1), (5, 5)));
+    /// assert_eq!((view.width, view.height), (2, 2));
+    /// assert_eq!(view.origin(), (1, 1));
+    /// assert_eq!(view[(0, 1)], 6);
+    /// assert_eq!(view.get(2, 0), None);
+    ///
+    /// assert_eq!(grid.crop(Rect::new((0, 1), (1, 2))).to_vec(), vec![vec![2, 3], vec![5, 6]]);
+    /// ```
+    pub fn view(&self, rect: Rect) -> GridView<'_, T> {
+        let clamp = |first: i32, last: i32, len: usize| {
+            let first = first.max(0) as usize;
+            let end = usize::try_from(last.saturating_add(1))
+                .unwrap_or(0)
+                .min(len);
+            (first.min(end), end.saturating_sub(first))
+        };
+        let (top, height) = clamp(rect.top, rect.bottom, self.height);
+        let (left, width) = clamp(rect.left, rect.right, self.width);
+        GridView {
+            grid: self,
+            top,
+            left,
+            width,
+            height,
+        }
+    }
+
+    /// A copy of the part of the grid inside of `rect`, see `view`.
+    pub fn crop(&self, rect: Rect) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.view(rect).to_grid()
+    }
+
+    /// The `(row, col)` of the cells matching `predicate`, row after row.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::Grid;
+    ///
+    /// let grid = Grid::from_vec(vec![vec![0, 1, 0], vec![1, 0, 0]]);
+    ///
+    /// let walls: Vec<_> = grid.cells_where(|&cell| cell == 1).collect();
+    /// assert_eq!(walls, vec![(0, 1), (1, 0)]);
+    /// assert_eq!(grid.iter().filter(|&&cell| cell == 0).count(), 4);
+    /// assert_eq!(grid.rows().nth(1), Some(&[1, 0, 0][..]));
+    /// ```
+    pub fn cells_where<'a, P>(
+        &'a self,
+        mut predicate: P,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a
+    where
+        P: FnMut(&T) -> bool + 'a,
+    {
+        self.enumerate_cells()
+            .filter_map(move |(cell, value)| predicate(value).then_some(cell))
+    }
+}
+
+impl<T: Cell> Grid<T> {
+    /// The passable cells reachable in one step of `movement` from `pos`, with
+    /// the cost of the step times the cost of the cell entered, like the
+    /// successors of `CellGraph`. The steps off the grid are left out, or wrap
+    /// around if the model does.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::Grid;
+    /// use pathfinding::movement::MovementModel;
+    ///
+    /// let grid = Grid::from_vec(vec![vec![0, 1, 0], vec![0, 0, 0]]);
+    ///
+    /// let neighbors: Vec<_> = grid.neighbors((1, 1), &MovementModel::four_way()).collect();
+    /// assert_eq!(neighbors, vec![((1, 0), 1), ((1, 2), 1)]);
+    ///
+    /// let wrapping = MovementModel::four_way().with_wrapping(true);
+    /// assert_eq!(grid.neighbors((0, 0), &wrapping).count(), 3);
+    /// ```
+    pub fn neighbors(
+        &self,
+        (row, col): (i32, i32),
+        movement: &MovementModel,
+    ) -> impl Iterator<Item = ((i32, i32), u32)> + '_ {
+        movement
+            .neighbors_in(row, col, self.height, self.width, |r, c| {
+                self.is_passable((r, c), Cell::passable)
+            })
+            .into_iter()
+            .filter_map(|(cell, cost)| {
+                Some((cell, cost.saturating_mul(self.cell_at(cell.into())?.cost())))
+            })
+    }
+}
+
+impl Grid<i32> {
+    /// Makes every cell a wall (`1`) with probability `density` and frees the
+    /// others (`0`). The same seed gives the same walls on every run and
+    /// platform, like `testing::random_grid` with `testing::Rng::new(seed)`.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::Grid;
+    /// use pathfinding::testing::{random_grid, Rng};
+    ///
+    /// let mut grid: Grid<i32> = Grid::new(16, 8);
+    /// grid.random_fill(0.25, 42);
+    ///
+    /// assert_eq!(grid.to_vec(), random_grid(&mut Rng::new(42), 16, 8, 0.25));
+    /// let walls = grid.iter().filter(|&&cell| cell == 1).count();
+    /// assert!((16..48).contains(&walls));
+    /// ```
+    pub fn random_fill(&mut self, density: f64, seed: u64) {
+        let mut rng = crate::testing::Rng::new(seed);
+        self.transform(|cell| *cell = rng.chance(density) as i32);
+    }
+
+    /// Like `random_fill`, but the walls follow `symmetry`: every cell is
+    /// drawn once for all the cells it is mirrored to, so the layout is fair
+    /// to players starting from symmetric corners, as on competitive maps.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::{Grid, Symmetry};
+    ///
+    /// let mut grid: Grid<i32> = Grid::new(9, 6);
+    /// grid.symmetric_fill(0.3, Symmetry::Rotational, 7);
+    ///
+    /// assert_eq!(grid.rotate180(), grid);
+    /// let mut same: Grid<i32> = Grid::new(9, 6);
+    /// same.symmetric_fill(0.3, Symmetry::Rotational, 7);
+    /// assert_eq!(same, grid);
+    ///
+    /// grid.symmetric_fill(0.3, Symmetry::FourWay, 7);
+    /// assert_eq!(grid.flip_horizontal(), grid);
+    /// assert_eq!(grid.flip_vertical(), grid);
+    /// ```
+    pub fn symmetric_fill(&mut self, density: f64, symmetry: Symmetry, seed: u64) {
+        let mut rng = crate::testing::Rng::new(seed);
+        let (width, height) = (self.width, self.height);
+        for row in 0..height {
+            for col in 0..width {
+                let (r, c) = symmetry.source((row, col), width, height);
+                let wall = if (r, c) == (row, col) {
+                    rng.chance(density) as i32
+                } else {
+                    self.cells[r * width + c]
+                };
+                self.set(row, col, wall);
+            }
+        }
+    }
+
+    /// The grid with every wall (any non-zero cell) grown by `radius` cells in
+    /// every direction, diagonals included: the cells within `radius` rows and
+    /// columns of a wall become walls (`1`), the others stay free (`0`).
+    ///
+    /// A path found on the dilated grid keeps an agent covering the
+    /// `2 * radius + 1` cells wide square around its cell clear of the walls.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::{ascii_walls, Grid};
+    ///
+    /// let grid = Grid::from_ascii(".......\n.......\n...#...\n.......\n.......", ascii_walls).unwrap().grid;
+    ///
+    /// let dilated = grid.dilate(1);
+    /// assert_eq!(dilated.to_string(), ".......\n..###..\n..###..\n..###..\n.......");
+    /// // Eroding shrinks the walls back, removing the ones thinner than 3 cells.
+    /// assert_eq!(dilated.erode(1), grid);
+    /// assert_eq!(grid.erode(1), Grid::new(7, 5));
+    /// ```
+    pub fn dilate(&self, radius: usize) -> Grid<i32> {
+        self.map(|&cell| cell != 0).spread(radius)
+    }
+
+    /// The grid with every free cell grown by `radius` cells in every
+    /// direction, the reverse of `dilate`: only the walls with walls all around
+    /// them within `radius` rows and columns stay walls (`1`). The outside of
+    /// the grid counts as walls.
+    pub fn erode(&self, radius: usize) -> Grid<i32> {
+        self.map(|&cell| cell == 0)
+            .spread(radius)
+            .map(|&cell| 1 - cell)
+    }
+
+    /// The clearance of every cell: the size of the largest square of free
+    /// cells (`0`) having the cell as its top left corner, `0` for the walls.
+    /// An agent covering `size` x `size` cells fits with its top left cell on
+    /// the cells of clearance `size` or more, see `astar_clearance`.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::{ascii_walls, Grid};
+    ///
+    /// let grid = Grid::from_ascii("...#\n....\n....", ascii_walls).unwrap().grid;
+    ///
+    /// assert_eq!(grid.clearance_map().to_vec(), vec![vec![3, 2, 1, 0], vec![2, 2, 2, 1], vec![1, 1, 1, 1]]);
+    /// ```
+    pub fn clearance_map(&self) -> Grid<u32> {
+        let mut clearance = Grid::new(self.width, self.height);
+        for row in (0..self.height).rev() {
+            for col in (0..self.width).rev() {
+                if self[(row, col)] != 0 {
+                    continue;
+                }
+                let around = |row: usize, col: usize| clearance.get(row, col).copied().unwrap_or(0);
+                let smallest = around(row + 1, col)
+                    .min(around(row, col + 1))
+                    .min(around(row + 1, col + 1));
+                clearance.cells[row * self.width + col] = smallest + 1;
+            }
+        }
+        clearance
+    }
+
+    /// The grid drawn like `Display` does, with `path` drawn over it: `S` on
+    /// its first cell, `E` on its last one and `*` in between.
+    pub fn to_string_with_path(&self, path: &[(i32, i32)]) -> alloc::string::String {
+        alloc::string::ToString::to_string(&self.display(walls_glyph).with_path(path))
+    }
+}
+
+impl Grid<bool> {
+    /// `1` for the cells within `radius` rows and columns of a `true` cell,
+    /// `0` for the others. Rows then columns are swept with running counts,
+    /// whatever the radius.
+    fn spread(&self, radius: usize) -> Grid<i32> {
+        let sweep = |line: &[bool]| -> Vec<bool> {
+            let mut counts = Vec::with_capacity(line.len() + 1);
+            counts.push(0);
+            for &set in line {
+                counts.push(counts[counts.len() - 1] + usize::from(set));
+            }
+            (0..line.len())
+                .map(|index| {
+                    let (low, high) = (
+                        index.saturating_sub(radius),
+                        (index + radius + 1).min(line.len()),
+                    );
+                    counts[high] > counts[low]
+                })
+                .collect()
+        };
+        let rows: Vec<bool> = self.rows().flat_map(sweep).collect();
+        let mut cells = vec![0; rows.len()];
+        for col in 0..self.width {
+            let column: Vec<bool> = rows[col..].iter().step_by(self.width).copied().collect();
+            for (row, set) in sweep(&column).into_iter().enumerate() {
+                cells[row * self.width + col] = i32::from(set);
+            }
+        }
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells,
+            dirty: DirtyRegion::default(),
+        }
+    }
+}
+
+impl Grid<u32> {
+    /// The number of cells with each label of a grid made by
+    /// `Grid::label_regions`, indexed by the label: the solid cells first,
+    /// then the size of every region.
+    pub fn region_sizes(&self) -> Vec<usize> {
+        let count = self.cells.iter().max().map_or(0, |&max| max as usize);
+        let mut sizes = vec![0; count + 1];
+        for &label in &self.cells {
+            sizes[label as usize] += 1;
+        }
+        sizes
+    }
+}
+
+fn walls_glyph(cell: &i32) -> char {
+    if *cell == 0 {
+        '.'
+    } else {
+        '#'
+    }
+}
+
+impl fmt::Display for Grid<i32> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(walls_glyph).fmt(f)
+    }
+}
+
+/// Shows the size and the rows of the grid, one row per line with `{:#?}`.
+impl<T: fmt::Debug> fmt::Debug for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Rows<'a, T>(&'a Grid<T>);
+
+        impl<T: fmt::Debug> fmt::Debug for Rows<'_, T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_list().entries(self.0.rows()).finish()
+            }
+        }
+
+        f.debug_struct("Grid")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("rows", &Rows(self))
+            .finish()
+    }
+}
+
+/// A grid drawn as text, see `Grid::display`.
+pub struct GridDisplay<'a, T, F> {
+    grid: &'a Grid<T>,
+    glyph: F,
+    path: &'a [(i32, i32)],
+}
+
+impl<'a, T, F: Fn(&T) -> char> GridDisplay<'a, T, F> {
+    /// Draws `path` over the grid: `S` on its first cell, `E` on its last one
+    /// and `*` in between. Cells of the path outside of the grid are left out.
+    pub fn with_path(mut self, path: &'a [(i32, i32)]) -> Self {
+        self.path = path;
+        self
+    }
+}
+
+impl<T, F: Fn(&T) -> char> fmt::Display for GridDisplay<'_, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let index = self.grid.index();
+        let mut glyphs: Vec<char> = self.grid.iter().map(&self.glyph).collect();
+        for (step, &cell) in self.path.iter().enumerate() {
+            if let Some(cell) = index.index(cell) {
+                glyphs[cell] = match step {
+                    0 => 'S',
+                    step if step == self.path.len() - 1 => 'E',
+                    _ => '*',
+                };
+            }
+        }
+        for row in 0..self.grid.height {
+            if row > 0 {
+                f.write_str("\n")?;
+            }
+            for glyph in &glyphs[row * self.grid.width..(row + 1) * self.grid.width] {
+                fmt::Write::write_char(f, *glyph)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A cell of a grid changing from `old` to `new`, see `Grid::diff`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CellChange<T> {
+    /// The `(row, col)` of the cell.
+    pub cell: (usize, usize),
+    pub old: T,
+    pub new: T,
+}
+
+/// The mapping of the usual ASCII maps for `Grid::from_ascii`: `#` is a wall
+/// (`1`), `.` and the markers are free cells (`0`).
+pub fn ascii_walls(character: char) -> Option<i32> {
+    match character {
+        '#' => Some(1),
+        '.' => Some(0),
+        marker if marker.is_ascii_alphanumeric() => Some(0),
+        _ => None,
+    }
+}
+
+/// A grid read by `Grid::from_ascii`, with the markers found on it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsciiMap<T> {
+    pub grid: Grid<T>,
+    /// The marker characters with their `(row, col)`, row by row.
+    pub markers: Vec<(char, (i32, i32))>,
+}
+
+impl<T> AsciiMap<T> {
+    /// The position of the first `marker`, `None` if the map has none.
+    pub fn marker(&self, marker: char) -> Option<(i32, i32)> {
+        self.markers
+            .iter()
+            .find(|&&(character, _)| character == marker)
+            .map(|&(_, cell)| cell)
+    }
+}
+
+/// Why `Grid::from_ascii` couldn't read a map. `line` counts from `1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseAsciiError {
+    /// The mapping doesn't know a character.
+    UnknownCharacter { line: usize, character: char },
+    /// A row isn't as long as the first one.
+    RaggedRow {
+        line: usize,
+        width: usize,
+        expected: usize,
+    },
+}
+
+impl fmt::Display for ParseAsciiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseAsciiError::UnknownCharacter { line, character } => {
+                write!(f, "line {}: unexpected map character {:?}", line, character)
+            }
+            ParseAsciiError::RaggedRow {
+                line,
+                width,
+                expected,
+            } => write!(
+                f,
+                "line {}: row has {} cells, expected {}",
+                line, width, expected
+            ),
+        }
+    }
+}
+
+impl core::error::Error for ParseAsciiError {}
+
+/// How much memory a grid takes, to budget applications holding many maps.
+/// See `Grid::memory_usage` and the same method of the other grids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct MemoryUsage {
+    /// The number of cells stored: all of them for dense grids, only those of
+    /// the allocated chunks for sparse ones.
+    pub cells: usize,
+    /// The number of bytes taken, the grid itself included.
+    pub bytes: usize,
+    /// The number of chunks allocated by sparse grids, `0` for the others.
+    pub chunks: usize,
+}
+
+impl fmt::Display for MemoryUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} cells, ", self.cells)?;
+        match self.bytes {
+            bytes if bytes < 1 << 10 => write!(f, "{} B", bytes)?,
+            bytes if bytes < 1 << 20 => write!(f, "{:.1} KiB", bytes as f64 / 1024.0)?,
+            bytes => write!(f, "{:.1} MiB", bytes as f64 / (1 << 20) as f64)?,
+        }
+        if self.chunks > 0 {
+            write!(f, " in {} chunks", self.chunks)?;
+        }
+        Ok(())
+    }
+}
+
+/// Error produced when two grids that must have the same size don't, see
+/// `Grid::combine`. Sizes are `(width, height)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SizeMismatch {
+    /// The size of the grid the other one is combined into.
+    pub expected: (usize, usize),
+    /// The size of the other grid.
+    pub found: (usize, usize),
+}
+
+impl fmt::Display for SizeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected a {}x{} grid, found {}x{}",
+            self.expected.0, self.expected.1, self.found.0, self.found.1
+        )
+    }
+}
+
+impl core::error::Error for SizeMismatch {}
+
+/// Error produced when the rows given to `Grid::try_from_vec` don't all have
+/// the same length. `row` counts from `0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RaggedRow {
+    /// The first row that isn't as long as the first one.
+    pub row: usize,
+    /// Its length.
+    pub len: usize,
+    /// The length of the first row.
+    pub expected: usize,
+}
+
+impl fmt::Display for RaggedRow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "row {} has {} cells, the rows of a grid must all have {}",
+            self.row, self.len, self.expected
+        )
+    }
+}
+
+impl core::error::Error for RaggedRow {}
+
+/// A borrowed rectangle of a `Grid`, indexed from its own top left cell.
+/// Copying a view only copies the reference to the grid.
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    top: usize,
+    left: usize,
+    /// The number of columns.
+    pub width: usize,
+    /// The number of rows.
+    pub height: usize,
+}
+
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<'a, T> GridView<'a, T> {
+    /// The cells of the view, as a rectangle of the grid.
+    pub fn rect(&self) -> Rect {
+        let (top, left) = (self.top as i32, self.left as i32);
+        Rect {
+            top,
+            left,
+            bottom: top + self.height as i32 - 1,
+            right: left + self.width as i32 - 1,
+        }
+    }
+
+    /// The `(row, col)` of the top left cell of the view in the grid.
+    pub fn origin(&self) -> (usize, usize) {
+        (self.top, self.left)
+    }
+
+    /// The cell at `row`, `col` of the view, `None` if it is outside of it.
+    pub fn get(&self, row: usize, col: usize) -> Option<&'a T> {
         if row < self.height && col < self.width {
-            let old = std::mem::replace(&mut self.cells[row][col], value);
-            Some(old)
+            self.grid.get(self.top + row, self.left + col)
         } else {
             None
         }
     }
 
-    pub fn fill(&mut self, value: T) {
-        for row in 0..self.height {
-            for col in 0..self.width {
-                self.cells[row][col] = value.clone();
-            }
+    /// The rows of the view, from the top.
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> + '_ {
+        (self.top..self.top + self.height).map(move |row| {
+            let start = row * self.grid.width + self.left;
+            &self.grid.cells[start..start + self.width]
+        })
+    }
+
+    /// Copies the view into a grid of its own.
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.rows().flatten().cloned().collect(),
+            dirty: DirtyRegion::default(),
         }
     }
+}
 
-    pub fn to_vec(&self) -> Vec<Vec<T>> {
-        self.cells.clone()
+impl<T> ops::Index<(usize, usize)> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        self.get(row, col).expect("the cell is outside of the view")
+    }
+}
+
+/// How the walls of `Grid::symmetric_fill` are mirrored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    /// The grid looks the same turned half a turn around its center.
+    Rotational,
+    /// The left half mirrors the right one.
+    LeftRight,
+    /// The top half mirrors the bottom one.
+    TopBottom,
+    /// Both halves mirror each other, left and right as well as top and bottom.
+    FourWay,
+}
+
+impl Symmetry {
+    /// The cell `cell` is a copy of: the first one, row after row, of the cells
+    /// it is mirrored to.
+    fn source(self, (row, col): (usize, usize), width: usize, height: usize) -> (usize, usize) {
+        let (mirror_row, mirror_col) = (height - 1 - row, width - 1 - col);
+        match self {
+            Symmetry::Rotational => (row, col).min((mirror_row, mirror_col)),
+            Symmetry::LeftRight => (row, col.min(mirror_col)),
+            Symmetry::TopBottom => (row.min(mirror_row), col),
+            Symmetry::FourWay => (row.min(mirror_row), col.min(mirror_col)),
+        }
+    }
+}
+
+/// A rectangle of grid cells, its corners included. Coordinates are
+/// `(row, col)` like the nodes of the grid graphs, and may lie outside of a grid.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::grid::Rect;
+///
+/// let rect = Rect::new((2, 3), (1, 1));
+///
+/// assert_eq!((rect.top, rect.left, rect.bottom, rect.right), (1, 1, 2, 3));
+/// assert_eq!((rect.width(), rect.height()), (3, 2));
+/// assert!(rect.contains((2, 1)));
+/// assert!(!rect.contains((0, 1)));
+/// assert_eq!(Rect::cell((0, 0)).grow(1).cells().count(), 9);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rect {
+    /// The first row.
+    pub top: i32,
+    /// The first column.
+    pub left: i32,
+    /// The last row.
+    pub bottom: i32,
+    /// The last column.
+    pub right: i32,
+}
+
+impl Rect {
+    /// The rectangle spanned by two opposite corners, in any order.
+    pub fn new(a: (i32, i32), b: (i32, i32)) -> Self {
+        Rect {
+            top: a.0.min(b.0),
+            left: a.1.min(b.1),
+            bottom: a.0.max(b.0),
+            right: a.1.max(b.1),
+        }
+    }
+
+    /// The rectangle holding the single cell `cell`.
+    pub fn cell(cell: (i32, i32)) -> Self {
+        Rect::new(cell, cell)
+    }
+
+    /// The number of columns.
+    pub fn width(&self) -> usize {
+        (self.right - self.left + 1) as usize
+    }
+
+    /// The number of rows.
+    pub fn height(&self) -> usize {
+        (self.bottom - self.top + 1) as usize
+    }
+
+    /// Whether `(row, col)` is inside of the rectangle.
+    pub fn contains(&self, (row, col): (i32, i32)) -> bool {
+        (self.top..=self.bottom).contains(&row) && (self.left..=self.right).contains(&col)
+    }
+
+    /// The smallest rectangle holding both rectangles.
+    pub fn union(self, other: Rect) -> Self {
+        Rect {
+            top: self.top.min(other.top),
+            left: self.left.min(other.left),
+            bottom: self.bottom.max(other.bottom),
+            right: self.right.max(other.right),
+        }
+    }
+
+    /// The rectangle extended by `by` cells on every side.
+    pub fn grow(self, by: i32) -> Self {
+        Rect {
+            top: self.top - by,
+            left: self.left - by,
+            bottom: self.bottom + by,
+            right: self.right + by,
+        }
+    }
+
+    /// The cells of the rectangle, row by row.
+    pub fn cells(self) -> impl Iterator<Item = (i32, i32)> {
+        (self.top..=self.bottom)
+            .flat_map(move |row| (self.left..=self.right).map(move |col| (row, col)))
+    }
+}
+
+/// Maps the cells of a `width` x `height` grid to flat indices, row by row,
+/// and back. Searches can then run on `usize` nodes, e.g. to keep their scores
+/// in arrays or to store the adjacency of a compact graph.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::grid::GridIndex;
+/// use pathfinding::{astar, manhattan_distance};
+///
+/// let index = GridIndex::new(3, 2);
+///
+/// assert_eq!(index.index((1, 2)), Some(5));
+/// assert_eq!(index.index((2, 0)), None);
+/// assert_eq!(index.cell(5), Some((1, 2)));
+///
+/// let grid = vec![vec![0; 3]; 2];
+/// let path = astar((0, 0), (1, 2), &grid, manhattan_distance, |row, col, grid| grid[row][col] == 1).unwrap();
+/// let path = path.map(|cell| index.index(cell).unwrap());
+///
+/// assert_eq!(path, vec![0, 3, 4, 5]);
+/// assert_eq!(path.cost(), 3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GridIndex {
+    /// The number of columns.
+    pub width: usize,
+    /// The number of rows.
+    pub height: usize,
+}
+
+impl GridIndex {
+    /// The mapping of a `width` x `height` grid.
+    pub fn new(width: usize, height: usize) -> Self {
+        GridIndex { width, height }
+    }
+
+    /// The number of cells, one more than the largest index.
+    pub fn len(&self) -> usize {
+        self.width * self.height
+    }
+
+    /// Whether the grid has no cells.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The index of `(row, col)`, `None` if it is outside of the grid.
+    pub fn index(&self, (row, col): (i32, i32)) -> Option<usize> {
+        let row = usize::try_from(row).ok().filter(|&row| row < self.height)?;
+        let col = usize::try_from(col).ok().filter(|&col| col < self.width)?;
+        Some(row * self.width + col)
+    }
+
+    /// The `(row, col)` of `index`, `None` if it is not the index of a cell.
+    pub fn cell(&self, index: usize) -> Option<(i32, i32)> {
+        (index < self.len()).then(|| ((index / self.width) as i32, (index % self.width) as i32))
+    }
+}
+
+/// The part of a grid shown in a scrolling screen area, mapping the points of
+/// the area to cells and back, for the GUI and any other frontend.
+///
+/// Screen points are `(x, y)` pixels from the top left corner of the area,
+/// which shows the point `origin` of the grid (in pixels from the top left
+/// corner of the grid). Cells are `(row, col)` and may lie outside of the grid.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::grid::{GridViewport, Rect};
+///
+/// let mut viewport = GridViewport::new((100.0, 50.0), 20.0);
+/// viewport.scroll_by((30.0, -10.0));
+///
+/// assert_eq!(viewport.cell_at((0.0, 0.0)), (-1, 1));
+/// assert_eq!(viewport.cell_at((15.0, 25.0)), (0, 2));
+/// assert_eq!(viewport.cell_origin((0, 2)), (10.0, 10.0));
+///
+/// assert_eq!(viewport.visible(), Rect::new((-1, 1), (2, 6)));
+/// assert_eq!(viewport.visible_in(4, 3), Some(Rect::new((0, 1), (2, 3))));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridViewport {
+    /// The point of the grid shown at the top left corner of the area.
+    pub origin: (f32, f32),
+    /// The width and height of the area, in pixels.
+    pub size: (f32, f32),
+    /// The width and height of a cell, in pixels.
+    pub cell_size: f32,
+}
+
+/// The largest integer not above `value`, which `core` doesn't provide for
+/// floats without `std`.
+fn floor(value: f32) -> i32 {
+    let truncated = value as i32;
+    if (truncated as f32) > value {
+        truncated - 1
+    } else {
+        truncated
+    }
+}
+
+impl GridViewport {
+    /// A viewport of an area of `size` pixels showing the top left corner of
+    /// the grid.
+    pub fn new(size: (f32, f32), cell_size: f32) -> Self {
+        GridViewport {
+            origin: (0.0, 0.0),
+            size,
+            cell_size,
+        }
+    }
+
+    /// The cell under the screen point `(x, y)`.
+    pub fn cell_at(&self, (x, y): (f32, f32)) -> (i32, i32) {
+        (
+            floor((y + self.origin.1) / self.cell_size),
+            floor((x + self.origin.0) / self.cell_size),
+        )
+    }
+
+    /// The screen point of the top left corner of `cell`.
+    pub fn cell_origin(&self, (row, col): (i32, i32)) -> (f32, f32) {
+        (
+            col as f32 * self.cell_size - self.origin.0,
+            row as f32 * self.cell_size - self.origin.1,
+        )
+    }
+
+    /// The cells touching the area, whether they are in a grid or not.
+    pub fn visible(&self) -> Rect {
+        Rect::new(self.cell_at((0.0, 0.0)), self.cell_at(self.size))
+    }
+
+    /// The cells of a `width` x `height` grid touching the area, `None` if
+    /// the grid is scrolled out of it.
+    pub fn visible_in(&self, width: usize, height: usize) -> Option<Rect> {
+        let visible = self.visible();
+        let clipped = Rect {
+            top: visible.top.max(0),
+            left: visible.left.max(0),
+            bottom: visible.bottom.min(height as i32 - 1),
+            right: visible.right.min(width as i32 - 1),
+        };
+        (clipped.top <= clipped.bottom && clipped.left <= clipped.right).then_some(clipped)
+    }
+
+    /// Scrolls by `(dx, dy)` pixels, positive values moving towards the bottom
+    /// right of the grid.
+    pub fn scroll_by(&mut self, (dx, dy): (f32, f32)) {
+        self.origin = (self.origin.0 + dx, self.origin.1 + dy);
+    }
+
+    /// Scrolls so that the middle of `cell` is in the middle of the area.
+    pub fn center_on(&mut self, (row, col): (i32, i32)) {
+        self.origin = (
+            (col as f32 + 0.5) * self.cell_size - self.size.0 / 2.0,
+            (row as f32 + 0.5) * self.cell_size - self.size.1 / 2.0,
+        );
+    }
+
+    /// Sets the size of the cells, scrolling so that the point of the grid
+    /// under the screen point `(x, y)` stays under it, like zooming with the
+    /// mouse wheel.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::GridViewport;
+    ///
+    /// let mut viewport = GridViewport::new((100.0, 100.0), 10.0);
+    /// assert_eq!(viewport.cell_at((25.0, 45.0)), (4, 2));
+    ///
+    /// viewport.zoom_at((25.0, 45.0), 20.0);
+    /// assert_eq!(viewport.cell_at((25.0, 45.0)), (4, 2));
+    /// assert_eq!(viewport.origin, (25.0, 45.0));
+    /// assert_eq!(viewport.visible().bottom - viewport.visible().top, 5);
+    /// ```
+    pub fn zoom_at(&mut self, (x, y): (f32, f32), cell_size: f32) {
+        let scale = cell_size / self.cell_size;
+        self.origin = (
+            (self.origin.0 + x) * scale - x,
+            (self.origin.1 + y) * scale - y,
+        );
+        self.cell_size = cell_size;
+    }
+
+    /// Scrolls back to the top left corner and sizes the cells so that a
+    /// `width` x `height` grid fits in the area, with cells of at least one
+    /// pixel.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::{GridViewport, Rect};
+    ///
+    /// let mut viewport = GridViewport::new((200.0, 100.0), 20.0);
+    /// viewport.scroll_by((30.0, 30.0));
+    /// viewport.fit(40, 5);
+    ///
+    /// assert_eq!((viewport.origin, viewport.cell_size), ((0.0, 0.0), 5.0));
+    /// assert_eq!(viewport.visible_in(40, 5), Some(Rect::new((0, 0), (4, 39))));
+    /// ```
+    pub fn fit(&mut self, width: usize, height: usize) {
+        self.origin = (0.0, 0.0);
+        self.cell_size = (self.size.0 / width.max(1) as f32)
+            .min(self.size.1 / height.max(1) as f32)
+            .max(1.0);
     }
 }
 
+/// The cell at `(row, col)`.
+///
+/// ### Panics
+///
+/// If the cell is outside of the grid, see `Grid::get` otherwise.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::grid::Grid;
+///
+/// let mut grid: Grid<char> = Grid::new(3, 2);
+/// grid[(1, 2)] = '#';
+///
+/// assert_eq!(grid[(1, 2)], '#');
+/// assert_eq!(grid.get(1, 2), Some(&'#'));
+/// ```
+impl<T> ops::Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        assert!(
+            row < self.height && col < self.width,
+            "the cell is outside of the grid"
+        );
+        &self.cells[row * self.width + col]
+    }
+}
+
+impl<T> ops::IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        self.get_mut(row, col)
+            .expect("the cell is outside of the grid")
+    }
+}
+
+/// The row `index`, so cells read as `grid[row][col]`.
 impl ops::Index<usize> for Grid<i32> {
-    type Output = Vec<i32>;
+    type Output = [i32];
 
     fn index(&self, index: usize) -> &Self::Output {
-        &self.cells[index]
+        self.row(index)
     }
 }
 
 impl ops::IndexMut<usize> for Grid<i32> {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.cells[index]
+        self.row_mut(index)
     }
-}
\ No newline at end of file
+}
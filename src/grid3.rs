@@ -0,0 +1,103 @@
+//! 3D grids of voxels, for levels with several floors.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// A voxel position: `x` and `y` on a floor, `z` the floor.
+pub type Voxel = (i32, i32, i32);
+
+/// A `width` x `height` x `depth` grid of cells, indexed by `(x, y, z)`.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::grid3::Grid3;
+///
+/// let mut grid: Grid3<bool> = Grid3::new(4, 3, 2);
+/// grid.set((3, 2, 1), true);
+///
+/// assert_eq!(grid.get((3, 2, 1)), Some(&true));
+/// assert_eq!(grid.get((0, 0, 0)), Some(&false));
+/// assert_eq!(grid.get((4, 0, 0)), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid3<T> {
+    /// The number of cells along `x`.
+    pub width: usize,
+    /// The number of cells along `y`.
+    pub height: usize,
+    /// The number of cells along `z`, i.e. the floors.
+    pub depth: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone + Default> Grid3<T> {
+    /// Creates a grid filled with the default value of `T`.
+    pub fn new(width: usize, height: usize, depth: usize) -> Self {
+        Grid3 {
+            width,
+            height,
+            depth,
+            cells: vec![T::default(); width * height * depth],
+        }
+    }
+}
+
+impl<T> Grid3<T> {
+    /// The position of `voxel` in `cells`, `None` if it is outside of the grid.
+    fn index(&self, (x, y, z): Voxel) -> Option<usize> {
+        let x = usize::try_from(x).ok().filter(|&x| x < self.width)?;
+        let y = usize::try_from(y).ok().filter(|&y| y < self.height)?;
+        let z = usize::try_from(z).ok().filter(|&z| z < self.depth)?;
+        Some((z * self.height + y) * self.width + x)
+    }
+
+    /// The cell at `voxel`, `None` if it is outside of the grid.
+    pub fn get(&self, voxel: Voxel) -> Option<&T> {
+        self.index(voxel).map(|index| &self.cells[index])
+    }
+
+    /// The cell at `voxel` for modification, `None` if it is outside of the grid.
+    pub fn get_mut(&mut self, voxel: Voxel) -> Option<&mut T> {
+        self.index(voxel).map(|index| &mut self.cells[index])
+    }
+
+    /// Replaces the cell at `voxel` and returns the old value, `None` if it is
+    /// outside of the grid.
+    pub fn set(&mut self, voxel: Voxel, value: T) -> Option<T> {
+        self.get_mut(voxel)
+            .map(|cell| core::mem::replace(cell, value))
+    }
+}
+
+/// Which voxels are neighbors in a 3D search. Every step costs `1` (times the
+/// cost of the entered cell).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Connectivity {
+    /// The six voxels sharing a face, `Manhattan` is the matching heuristic.
+    #[default]
+    Six,
+    /// All the voxels around, edges and corners included, `Diagonal` is the
+    /// matching heuristic. A step may only go diagonally if all the voxels it
+    /// passes are free, so it never cuts through a corner, a floor or a ceiling.
+    TwentySix,
+}
+
+impl Connectivity {
+    /// The offsets of the neighbors.
+    pub fn offsets(self) -> Vec<Voxel> {
+        let mut offsets = vec![];
+        for dz in -1..=1 {
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    let axes = [dx, dy, dz].iter().filter(|&&d| d != 0).count();
+                    if axes == 1 || (axes > 1 && self == Connectivity::TwentySix) {
+                        offsets.push((dx, dy, dz));
+                    }
+                }
+            }
+        }
+        offsets
+    }
+}
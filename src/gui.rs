@@ -0,0 +1,1194 @@
+//! The egui visualisation of the search, see `run`.
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use eframe::egui;
+use egui::text::LayoutJob;
+use egui::FontId;
+use egui::Frame;
+use egui::RichText;
+use egui::TextFormat;
+
+use crate::bfs::breadth_first_with_observer;
+use crate::coord::Coord;
+use crate::graph::{Graph, GridGraph};
+use crate::grid::{Grid, GridViewport, Rect};
+use crate::heuristic::{Diagonal, Euclidean, Heuristic, Manhattan, Octile, Scaled, Zero};
+use crate::history::GridHistory;
+use crate::jps::jump_point_search_with_observer;
+use crate::manhattan_distance;
+use crate::path::Path;
+#[cfg(feature = "image")]
+use crate::render::Render;
+use crate::save::MapFile;
+use crate::scenarios::Preset;
+use crate::search::{Observer, SearchError, SearchResult, SearchState};
+
+/// The size of the cells when the visualisation opens, in pixels.
+const CELL_SIZE: f32 = 20.0;
+/// The smallest and largest cells zooming goes to, in pixels.
+const MIN_CELL_SIZE: f32 = 1.0;
+const MAX_CELL_SIZE: f32 = 80.0;
+
+/// The number of cell changes the undo history keeps.
+const HISTORY_BUDGET: usize = 100_000;
+
+/// The seeds `randomize` tries, from the chosen one on, to join the start to
+/// the end before carving a corridor between them.
+const RANDOMIZE_ATTEMPTS: u64 = 100;
+
+/// The color of the cells expanded by the search.
+const CLOSED_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 210, 150);
+/// The color of the cells reached but not expanded by the search.
+const OPEN_COLOR: egui::Color32 = egui::Color32::from_rgb(170, 230, 170);
+
+/// The heatmap colors of the cells closest to and farthest from the start.
+const NEAR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 240, 110);
+const FAR_COLOR: egui::Color32 = egui::Color32::from_rgb(200, 30, 110);
+
+/// The heatmap color of a cell `g_score` away from the start, `max_g_score`
+/// being the farthest cell explored.
+fn heat_color(g_score: u32, max_g_score: u32) -> egui::Color32 {
+    let t = g_score as f32 / max_g_score.max(1) as f32;
+    let lerp = |near: u8, far: u8| (near as f32 + (far as f32 - near as f32) * t) as u8;
+    egui::Color32::from_rgb(
+        lerp(NEAR_COLOR.r(), FAR_COLOR.r()),
+        lerp(NEAR_COLOR.g(), FAR_COLOR.g()),
+        lerp(NEAR_COLOR.b(), FAR_COLOR.b()),
+    )
+}
+
+/// Whether `end` can be reached from `start` by up, down, left and right
+/// steps over free cells.
+fn joined(grid: &Grid<i32>, start: (usize, usize), end: (usize, usize)) -> bool {
+    let mut reached = grid.clone();
+    reached.flood_fill(start, 2);
+    reached.get(end.0, end.1) == Some(&2)
+}
+
+/// Frees the cells of an L-shaped corridor from `start` to `end`, along the
+/// row of `start` then the column of `end`.
+fn carve(grid: &mut Grid<i32>, start: (usize, usize), end: (usize, usize)) {
+    for col in start.1.min(end.1)..=start.1.max(end.1) {
+        grid.set(start.0, col, 0);
+    }
+    for row in start.0.min(end.0)..=start.0.max(end.0) {
+        grid.set(row, end.1, 0);
+    }
+}
+
+/// The cells covered by a brush `size` cells across on `center`: a square, or
+/// the cells of that square inside of the circle it bounds if `round`.
+fn brush_cells(center: (i32, i32), size: i32, round: bool) -> Vec<(i32, i32)> {
+    let low = -(size - 1) / 2;
+    let offsets = low..low + size;
+    let middle = (size - 1) as f32 / 2.0 + low as f32;
+    let radius_sq = (size as f32 * size as f32 / 4.0 - 0.5).max(0.0);
+    let inside = |row: i32, col: i32| {
+        let (dr, dc) = (row as f32 - middle, col as f32 - middle);
+        !round || dr * dr + dc * dc <= radius_sq
+    };
+    offsets
+        .clone()
+        .flat_map(|row| offsets.clone().map(move |col| (row, col)))
+        .filter(|&(row, col)| inside(row, col))
+        .map(|(row, col)| (center.0 + row, center.1 + col))
+        .collect()
+}
+
+/// The color of a cell in the static map layer.
+fn map_color(cell: i32) -> egui::Color32 {
+    if cell == 1 {
+        egui::Color32::from_rgb(0, 0, 0)
+    } else {
+        egui::Color32::from_rgb(255, 255, 255)
+    }
+}
+
+/// The searches the visualisation can run: A* with another heuristic or none,
+/// breadth-first search and jump point search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Algorithm {
+    AStar,
+    /// No heuristic: the search widens evenly around the start.
+    Dijkstra,
+    /// A first-in first-out queue instead of the open set. Every step of the
+    /// map costs the same, so it widens like `Dijkstra`, without the heap.
+    BreadthFirst,
+    /// The heuristic weighted so much that the cost so far hardly counts:
+    /// fast, but the paths aren't the shortest.
+    Greedy,
+    /// The heuristic doubled, paths at most twice as long as the shortest.
+    WeightedAStar,
+    /// A* jumping over the cells where no shortest path turns, so only the
+    /// corners of the obstacles are explored.
+    JumpPoint,
+}
+
+impl Algorithm {
+    const ALL: [Algorithm; 6] = [
+        Algorithm::AStar,
+        Algorithm::Dijkstra,
+        Algorithm::BreadthFirst,
+        Algorithm::Greedy,
+        Algorithm::WeightedAStar,
+        Algorithm::JumpPoint,
+    ];
+
+    /// Whether the search is guided by the heuristic.
+    fn uses_heuristic(self) -> bool {
+        !matches!(self, Algorithm::Dijkstra | Algorithm::BreadthFirst)
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Algorithm::AStar => "A*",
+            Algorithm::Dijkstra => "Dijkstra",
+            Algorithm::BreadthFirst => "Breadth-first",
+            Algorithm::Greedy => "Greedy best-first",
+            Algorithm::WeightedAStar => "Weighted A* (x2)",
+            Algorithm::JumpPoint => "Jump point search",
+        }
+    }
+}
+
+/// The heuristics the searches can be guided by. The map has unit costs and
+/// four-way moves, so only `Manhattan` is exact on open ground, the others
+/// underestimate more and explore more.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HeuristicKind {
+    Manhattan,
+    Diagonal,
+    /// With diagonal steps costing about `1.4`.
+    Octile,
+    Euclidean,
+    Zero,
+}
+
+impl HeuristicKind {
+    const ALL: [HeuristicKind; 5] = [
+        HeuristicKind::Manhattan,
+        HeuristicKind::Diagonal,
+        HeuristicKind::Octile,
+        HeuristicKind::Euclidean,
+        HeuristicKind::Zero,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            HeuristicKind::Manhattan => "Manhattan",
+            HeuristicKind::Diagonal => "Diagonal",
+            HeuristicKind::Octile => "Octile",
+            HeuristicKind::Euclidean => "Euclidean",
+            HeuristicKind::Zero => "Zero",
+        }
+    }
+}
+
+impl Heuristic<(i32, i32)> for HeuristicKind {
+    fn estimate(&self, node: (i32, i32), goal: (i32, i32)) -> u32 {
+        match self {
+            HeuristicKind::Manhattan => Manhattan.estimate(node, goal),
+            HeuristicKind::Diagonal => Diagonal.estimate(node, goal),
+            HeuristicKind::Octile => Scaled(
+                Octile {
+                    straight: 10,
+                    diagonal: 14,
+                },
+                0.1,
+            )
+            .estimate(node, goal),
+            HeuristicKind::Euclidean => Euclidean.estimate(node, goal),
+            HeuristicKind::Zero => Zero.estimate(node, goal),
+        }
+    }
+}
+
+/// The nodes a search went through, to draw how much of the map it touched.
+#[derive(Default)]
+struct Explored {
+    open: Vec<(i32, i32)>,
+    closed: Vec<(i32, i32)>,
+    /// The g-score of every open and closed node, and the largest one.
+    g_scores: Vec<((i32, i32), u32)>,
+    max_g_score: u32,
+}
+
+/// Runs `state` to its end, keeping the nodes it explored.
+fn explore<G, H>(mut state: SearchState<G, H>) -> (SearchResult<(i32, i32)>, Explored)
+where
+    G: Graph<Node = (i32, i32)>,
+    H: Heuristic<(i32, i32)>,
+{
+    let result = state.result();
+    let g_scores: Vec<_> = state
+        .open_nodes()
+        .chain(state.closed_nodes())
+        .filter_map(|node| Some((node, state.g_score(node)?)))
+        .collect();
+    let explored = Explored {
+        open: state.open_nodes().collect(),
+        closed: state.closed_nodes().collect(),
+        max_g_score: g_scores.iter().map(|&(_, g)| g).max().unwrap_or(0),
+        g_scores,
+    };
+    (result, explored)
+}
+
+/// What a search without a `SearchState` explored, collected as its
+/// `Observer`. Every step of the map costs `1`, so a node reached from another
+/// one costs the number of cells between them more.
+#[derive(Default)]
+struct ExploreLog {
+    closed: Vec<(i32, i32)>,
+    g_scores: HashMap<(i32, i32), u32>,
+}
+
+impl Observer<(i32, i32)> for ExploreLog {
+    fn on_expand(&mut self, node: (i32, i32), g: u32, _f: u32) {
+        self.closed.push(node);
+        self.g_scores.insert(node, g);
+    }
+
+    fn on_relax(&mut self, from: (i32, i32), to: (i32, i32)) {
+        let g = self.g_scores[&from] + manhattan_distance(from, to);
+        self.g_scores.insert(to, g);
+    }
+}
+
+impl From<ExploreLog> for Explored {
+    fn from(log: ExploreLog) -> Self {
+        let closed: HashSet<_> = log.closed.iter().copied().collect();
+        Explored {
+            open: log
+                .g_scores
+                .keys()
+                .filter(|node| !closed.contains(node))
+                .copied()
+                .collect(),
+            closed: log.closed,
+            max_g_score: log.g_scores.values().copied().max().unwrap_or(0),
+            g_scores: log.g_scores.into_iter().collect(),
+        }
+    }
+}
+
+/// The figures of the last search, from its `SearchResult`.
+struct SearchStats {
+    cost: Option<u32>,
+    nodes_expanded: usize,
+    nodes_generated: usize,
+    duration: Duration,
+}
+
+#[derive(PartialEq)]
+enum PaintTile {
+    Nothing,
+    ObstaclePlacement,
+    Start,
+    End,
+    /// A straight wall, dragged from one end to the other.
+    Line,
+    /// A rectangle of walls, dragged from one corner to the opposite one.
+    Rectangle,
+}
+
+/// The color of the shape being dragged, before it is drawn on the map.
+const SHAPE_COLOR: egui::Color32 = egui::Color32::from_rgb(120, 120, 120);
+
+/// Draws the shape of `tool` from `from` to `to` on `grid` with `value`; a
+/// rectangle is only outlined unless `filled`.
+fn draw_shape<T: Clone>(
+    grid: &mut Grid<T>,
+    tool: &PaintTile,
+    filled: bool,
+    (from, to): ((i32, i32), (i32, i32)),
+    value: T,
+) {
+    match tool {
+        PaintTile::Line => grid.draw_line(from, to, value),
+        PaintTile::Rectangle if filled => grid.fill_rect(Rect::new(from, to), value),
+        PaintTile::Rectangle => {
+            let corners = [from, (from.0, to.1), to, (to.0, from.1)];
+            for (index, &corner) in corners.iter().enumerate() {
+                grid.draw_line(corner, corners[(index + 1) % 4], value.clone());
+            }
+        }
+        _ => {}
+    }
+}
+
+struct MyApp {
+    history: GridHistory<i32>,
+    start: (i32, i32),
+    end: (i32, i32),
+    path: Option<Path<(i32, i32)>>,
+    path_error: Option<SearchError>,
+    stats: Option<SearchStats>,
+    paint_mode: PaintTile,
+    highlited: Option<(usize, usize)>,
+    map_texture: Option<egui::TextureHandle>,
+    viewport: GridViewport,
+    /// Whether the cells are sized to fit the grid in the canvas, rather than
+    /// zoomed by hand.
+    fit_cells: bool,
+    /// The width of the obstacle brush in cells, and whether it is round.
+    brush_size: i32,
+    brush_round: bool,
+    /// What the current obstacle stroke paints, picked on the cell where it
+    /// started: walls over free cells, free cells over walls.
+    stroke_value: Option<i32>,
+    /// The cells where the shape being dragged starts and ends.
+    shape: Option<((i32, i32), (i32, i32))>,
+    /// Whether the rectangles drawn are filled rather than outlined.
+    shape_filled: bool,
+    preset: Option<Preset>,
+    algorithm: Algorithm,
+    heuristic: HeuristicKind,
+    explored: Explored,
+    show_explored: bool,
+    show_heatmap: bool,
+    /// The map file saved or loaded last, where the file dialogs open.
+    map_path: Option<PathBuf>,
+    /// The outcome of the last save or load, an error message if it failed.
+    file_status: Option<Result<String, String>>,
+    /// The share of walls, the seed and whether the endpoints must stay
+    /// joined when the map is randomized.
+    random_density: f64,
+    random_seed: u64,
+    random_connected: bool,
+}
+
+impl Default for MyApp {
+    fn default() -> Self {
+        let mut grid = Grid::from_vec(vec![vec![0; 10]; 10]);
+        grid.track_dirty(true);
+        let start = (1, 1);
+        let end = (8, 8);
+        let path = None;
+        MyApp {
+            history: GridHistory::new(grid, HISTORY_BUDGET),
+            start,
+            end,
+            path,
+            path_error: None,
+            stats: None,
+            paint_mode: PaintTile::Nothing,
+            highlited: None,
+            map_texture: None,
+            viewport: GridViewport::new((0.0, 0.0), CELL_SIZE),
+            fit_cells: true,
+            brush_size: 1,
+            brush_round: false,
+            stroke_value: None,
+            shape: None,
+            shape_filled: false,
+            preset: None,
+            algorithm: Algorithm::AStar,
+            heuristic: HeuristicKind::Manhattan,
+            explored: Explored::default(),
+            show_explored: true,
+            show_heatmap: false,
+            map_path: None,
+            file_status: None,
+            random_density: 0.3,
+            random_seed: 0,
+            random_connected: true,
+        }
+    }
+}
+
+impl MyApp {
+    fn grid(&self) -> &Grid<i32> {
+        self.history.grid()
+    }
+
+    /// Reverts the last edit of the map, or applies it again.
+    fn undo(&mut self, redo: bool) {
+        let changed = if redo {
+            self.history.redo()
+        } else {
+            self.history.undo()
+        };
+        if changed {
+            self.find_path();
+        }
+    }
+
+    fn find_path(&mut self) {
+        let (start, end, heuristic) = (self.start, self.end, self.heuristic);
+        self.explored = Explored::default();
+        self.stats = None;
+        let result = self
+            .grid()
+            .validate_endpoints(start, end, |&cell| cell == 0)
+            .and_then(|()| {
+                let grid = self.grid().to_vec();
+                let graph = GridGraph::new(&grid, |row, col, grid| grid[row][col] == 1);
+                let (result, explored) = match self.algorithm {
+                    Algorithm::AStar => explore(SearchState::new(&graph, start, end, heuristic)),
+                    Algorithm::Dijkstra => explore(SearchState::new(&graph, start, end, Zero)),
+                    Algorithm::Greedy => explore(SearchState::new(
+                        &graph,
+                        start,
+                        end,
+                        Scaled(heuristic, 1000.0),
+                    )),
+                    Algorithm::WeightedAStar => {
+                        explore(SearchState::new(&graph, start, end, Scaled(heuristic, 2.0)))
+                    }
+                    Algorithm::BreadthFirst => {
+                        let mut log = ExploreLog::default();
+                        let result = breadth_first_with_observer(&graph, start, end, &mut log);
+                        (result, log.into())
+                    }
+                    Algorithm::JumpPoint => {
+                        let mut log = ExploreLog::default();
+                        let result = jump_point_search_with_observer(
+                            start,
+                            end,
+                            &grid,
+                            heuristic,
+                            |row, col, grid| grid[row][col] == 1,
+                            &mut log,
+                        );
+                        (result, log.into())
+                    }
+                };
+                self.explored = explored;
+                self.stats = Some(SearchStats {
+                    cost: result.cost,
+                    nodes_expanded: result.nodes_expanded,
+                    nodes_generated: result.nodes_generated,
+                    duration: result.duration,
+                });
+                result.path
+            });
+        (self.path, self.path_error) = match result {
+            Ok(path) => (Some(path), None),
+            Err(e) => (None, Some(e)),
+        };
+    }
+
+    /// Replaces the map, dropping its history, scaled to fit in the canvas.
+    fn replace_map(&mut self, mut grid: Grid<i32>, start: (i32, i32), end: (i32, i32)) {
+        grid.track_dirty(true);
+        self.history = GridHistory::new(grid, HISTORY_BUDGET);
+        (self.start, self.end) = (start, end);
+        self.map_texture = None;
+        self.viewport.fit(self.grid().width, self.grid().height);
+        self.fit_cells = true;
+        self.preset = None;
+        self.find_path();
+    }
+
+    /// Replaces the map with a preset.
+    fn load_preset(&mut self, preset: Preset) {
+        let (start, end) = preset.endpoints();
+        self.replace_map(preset.grid(), start, end);
+        self.preset = Some(preset);
+    }
+
+    /// Scatters walls over the map with `random_density` and `random_seed`,
+    /// keeping the endpoints free. If they must stay joined, the next seeds
+    /// are tried until they are, and `random_seed` is set to the one used.
+    fn randomize(&mut self) {
+        let start = Coord::from(self.start).to_usize();
+        let end = Coord::from(self.end).to_usize();
+        let (density, seed, connected) =
+            (self.random_density, self.random_seed, self.random_connected);
+        let fill = |grid: &mut Grid<i32>, seed: u64| {
+            grid.random_fill(density, seed);
+            for (row, col) in [start, end].into_iter().flatten() {
+                grid.set(row, col, 0);
+            }
+        };
+        self.random_seed = self.history.edit(|grid| {
+            let (Some(start), Some(end), true) = (start, end, connected) else {
+                fill(grid, seed);
+                return seed;
+            };
+            let found = (0..RANDOMIZE_ATTEMPTS)
+                .map(|attempt| seed.wrapping_add(attempt))
+                .find(|&seed| {
+                    fill(grid, seed);
+                    joined(grid, start, end)
+                });
+            found.unwrap_or_else(|| {
+                fill(grid, seed);
+                carve(grid, start, end);
+                seed
+            })
+        });
+        self.preset = None;
+        self.find_path();
+    }
+
+    /// A native file dialog for files with `extension`, opening next to the
+    /// last map file, named after it.
+    fn file_dialog(&self, name: &str, extension: &str) -> rfd::FileDialog {
+        let dialog = rfd::FileDialog::new().add_filter(name, &[extension]);
+        let Some(map_path) = &self.map_path else {
+            return dialog.set_file_name(format!("map.{}", extension));
+        };
+        let dialog = match map_path.parent() {
+            Some(directory) => dialog.set_directory(directory),
+            None => dialog,
+        };
+        match map_path.file_stem() {
+            Some(stem) => dialog.set_file_name(format!("{}.{}", stem.to_string_lossy(), extension)),
+            None => dialog,
+        }
+    }
+
+    /// Writes the map with its endpoints to the file picked in a save dialog.
+    fn save_map(&mut self) {
+        let Some(map_path) = self.file_dialog("Map", "json").save_file() else {
+            return;
+        };
+        let map = MapFile {
+            grid: self.grid().clone(),
+            start: Some(self.start),
+            end: Some(self.end),
+        };
+        self.file_status = Some(
+            map.save(&map_path)
+                .map(|()| format!("Saved {}", map_path.display()))
+                .map_err(|e| e.to_string()),
+        );
+        self.map_path = Some(map_path);
+    }
+
+    /// Draws the map, the explored cells and the path shown to the PNG file
+    /// picked in a save dialog.
+    #[cfg(feature = "image")]
+    fn export_image(&mut self) {
+        let Some(image_path) = self.file_dialog("PNG image", "png").save_file() else {
+            return;
+        };
+        let grid = self.grid().to_vec();
+        let shades = self
+            .explored_colors()
+            .into_iter()
+            .map(|(cell, color)| (cell, image::Rgb([color.r(), color.g(), color.b()])));
+        let mut render = Render::new(&grid)
+            .cell_size(16)
+            .start(self.start)
+            .end(self.end)
+            .shade(shades);
+        if let Some(path) = &self.path {
+            render = render.path(path);
+        }
+        self.file_status = Some(
+            render
+                .save(&image_path)
+                .map(|()| format!("Exported {}", image_path.display()))
+                .map_err(|e| e.to_string()),
+        );
+    }
+
+    /// Replaces the map with the one picked in an open dialog, keeping the
+    /// current endpoints if the file has none.
+    fn load_map(&mut self) {
+        let Some(map_path) = self.file_dialog("Map", "json").pick_file() else {
+            return;
+        };
+        self.file_status = Some(match MapFile::<i32>::load(&map_path) {
+            Ok(map) => {
+                let start = map.start.unwrap_or(self.start);
+                let end = map.end.unwrap_or(self.end);
+                self.replace_map(map.grid, start, end);
+                Ok(format!("Loaded {}", map_path.display()))
+            }
+            Err(e) => Err(e.to_string()),
+        });
+        self.map_path = Some(map_path);
+    }
+
+    fn ui_control(&mut self, ui: &mut egui::Ui) {
+        ui.heading("A* algorithm visualisation");
+        let mut chosen = None;
+        egui::ComboBox::from_label("Preset")
+            .selected_text(self.preset.map_or("custom", Preset::name))
+            .show_ui(ui, |ui| {
+                for preset in Preset::ALL {
+                    if ui
+                        .selectable_label(self.preset == Some(preset), preset.name())
+                        .clicked()
+                    {
+                        chosen = Some(preset);
+                    }
+                }
+            });
+        if let Some(preset) = chosen {
+            self.load_preset(preset);
+        }
+        ui.horizontal(|ui| {
+            if ui.button("Randomize").clicked() {
+                self.randomize();
+            }
+            ui.label("seed");
+            ui.add(egui::DragValue::new(&mut self.random_seed).speed(1.0));
+        });
+        ui.add(egui::Slider::new(&mut self.random_density, 0.0..=0.9).text("density"));
+        ui.checkbox(&mut self.random_connected, "Keep start and end joined");
+        ui.horizontal(|ui| {
+            if ui.button("Save").clicked() {
+                self.save_map();
+            }
+            if ui.button("Load").clicked() {
+                self.load_map();
+            }
+            #[cfg(feature = "image")]
+            if ui.button("Export image").clicked() {
+                self.export_image();
+            }
+        });
+        match &self.file_status {
+            Some(Ok(message)) => {
+                ui.label(message);
+            }
+            Some(Err(message)) => {
+                ui.colored_label(egui::Color32::RED, message);
+            }
+            None => {}
+        }
+        let (algorithm, heuristic) = (self.algorithm, self.heuristic);
+        egui::ComboBox::from_label("Algorithm")
+            .selected_text(self.algorithm.name())
+            .show_ui(ui, |ui| {
+                for algorithm in Algorithm::ALL {
+                    ui.selectable_value(&mut self.algorithm, algorithm, algorithm.name());
+                }
+            });
+        ui.add_enabled_ui(self.algorithm.uses_heuristic(), |ui| {
+            egui::ComboBox::from_label("Heuristic")
+                .selected_text(self.heuristic.name())
+                .show_ui(ui, |ui| {
+                    for heuristic in HeuristicKind::ALL {
+                        ui.selectable_value(&mut self.heuristic, heuristic, heuristic.name());
+                    }
+                });
+        });
+        if (self.algorithm, self.heuristic) != (algorithm, heuristic) {
+            self.find_path();
+        }
+        ui.horizontal(|ui| {
+            ui.label("Start:");
+            ui.colored_label(egui::Color32::GRAY, "row");
+            ui.add(egui::DragValue::new(&mut self.start.1).speed(1.0));
+            ui.colored_label(egui::Color32::GRAY, "col");
+            ui.add(egui::DragValue::new(&mut self.start.0).speed(1.0));
+        });
+        ui.horizontal(|ui| {
+            ui.label("End:");
+            ui.colored_label(egui::Color32::GRAY, "row");
+            ui.add(egui::DragValue::new(&mut self.end.1).speed(1.0));
+            ui.colored_label(egui::Color32::GRAY, "col");
+            ui.add(egui::DragValue::new(&mut self.end.0).speed(1.0));
+        });
+        ui.horizontal(|ui| {
+            if ui.button("Find path").clicked() {
+                self.find_path();
+            }
+            ui.colored_label(egui::Color32::TRANSPARENT, " ");
+            if ui.button("Clear path").clicked() {
+                self.path = None;
+                self.path_error = None;
+                self.stats = None;
+                self.explored = Explored::default();
+            }
+        });
+    }
+
+    /// The figures of the last search, empty when no search ran.
+    fn ui_stats(&self, ui: &mut egui::Ui) {
+        let Some(stats) = &self.stats else {
+            return;
+        };
+        let na = || "-".to_string();
+        egui::CollapsingHeader::new("Statistics")
+            .default_open(true)
+            .show(ui, |ui| {
+                egui::Grid::new("stats").num_columns(2).show(ui, |ui| {
+                    let mut row = |name: &str, value: String| {
+                        ui.label(name);
+                        ui.label(value);
+                        ui.end_row();
+                    };
+                    row(
+                        "Path length",
+                        self.path.as_ref().map_or_else(na, |path| {
+                            format!("{} steps", path.len().saturating_sub(1))
+                        }),
+                    );
+                    row(
+                        "Path cost",
+                        stats.cost.map_or_else(na, |cost| cost.to_string()),
+                    );
+                    row("Nodes expanded", stats.nodes_expanded.to_string());
+                    row("Nodes generated", stats.nodes_generated.to_string());
+                    row(
+                        "Search time",
+                        format!("{:.3} ms", stats.duration.as_secs_f64() * 1000.0),
+                    );
+                });
+            });
+    }
+
+    fn cell_color(&self, row: usize, col: usize) -> egui::Color32 {
+        let cell = (row as i32, col as i32);
+        if self.end == cell {
+            egui::Color32::from_rgb(255, 0, 0)
+        } else if self.start == cell {
+            egui::Color32::from_rgb(0, 255, 0)
+        } else if self.path.as_ref().is_some_and(|path| path.contains(&cell)) {
+            egui::Color32::from_rgb(0, 0, 255)
+        } else if self.show_heatmap {
+            match self
+                .explored
+                .g_scores
+                .iter()
+                .find(|&&(node, _)| node == cell)
+            {
+                Some(&(_, g_score)) => heat_color(g_score, self.explored.max_g_score),
+                None => map_color(self.grid()[(row, col)]),
+            }
+        } else if self.show_explored && self.explored.open.contains(&cell) {
+            OPEN_COLOR
+        } else if self.show_explored && self.explored.closed.contains(&cell) {
+            CLOSED_COLOR
+        } else {
+            map_color(self.grid()[(row, col)])
+        }
+    }
+
+    /// The colors of the explored cells shown over the map, the later ones
+    /// drawn over the earlier ones.
+    fn explored_colors(&self) -> Vec<((i32, i32), egui::Color32)> {
+        if self.show_heatmap {
+            let max_g_score = self.explored.max_g_score;
+            self.explored
+                .g_scores
+                .iter()
+                .map(|&(cell, g_score)| (cell, heat_color(g_score, max_g_score)))
+                .collect()
+        } else if self.show_explored {
+            let closed = self
+                .explored
+                .closed
+                .iter()
+                .map(|&cell| (cell, CLOSED_COLOR));
+            let open = self.explored.open.iter().map(|&cell| (cell, OPEN_COLOR));
+            closed.chain(open).collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Re-renders the static map layer into its texture, only the part of the
+    /// grid that changed since the last frame.
+    fn update_map_texture(&mut self, ctx: &egui::Context) {
+        let dirty = self.history.take_dirty();
+        let Some(texture) = &mut self.map_texture else {
+            let pixels = self.grid().iter().map(|&cell| map_color(cell)).collect();
+            let image = egui::ColorImage {
+                size: [self.grid().width, self.grid().height],
+                pixels,
+            };
+            self.map_texture = Some(ctx.load_texture("map", image, egui::TextureFilter::Nearest));
+            return;
+        };
+        if let Some(dirty) = dirty {
+            let view = self.history.grid().view(dirty);
+            let pixels = view
+                .rows()
+                .flat_map(|row| row.iter().map(|&cell| map_color(cell)))
+                .collect();
+            let image = egui::ColorImage {
+                size: [view.width, view.height],
+                pixels,
+            };
+            let (top, left) = view.origin();
+            texture.set_partial([left, top], image, egui::TextureFilter::Nearest);
+        }
+    }
+
+    /// The cell of the grid under the screen position `pos`, the top left
+    /// corner of the canvas being at `corner`.
+    fn cell_under(&self, pos: egui::Pos2, corner: egui::Pos2) -> Option<(usize, usize)> {
+        let cell = self.viewport.cell_at((pos.x - corner.x, pos.y - corner.y));
+        let (row, col) = Coord::from(cell).to_usize()?;
+        (row < self.grid().height && col < self.grid().width).then_some((row, col))
+    }
+
+    /// Zooms with the mouse wheel around the pointer and pans by dragging with
+    /// the middle button.
+    fn handle_canvas_navigation(
+        &mut self,
+        response: &egui::Response,
+        ui: &mut egui::Ui,
+        corner: egui::Pos2,
+    ) {
+        if response.dragged_by(egui::PointerButton::Middle) {
+            self.fit_cells = false;
+            let delta = response.drag_delta();
+            self.viewport.scroll_by((-delta.x, -delta.y));
+        }
+        let scroll = ui.input().scroll_delta.y;
+        if let Some(pos) = response.hover_pos().filter(|_| scroll != 0.0) {
+            let cell_size = (self.viewport.cell_size * (scroll / 200.0).exp())
+                .clamp(MIN_CELL_SIZE, MAX_CELL_SIZE);
+            self.fit_cells = false;
+            self.viewport
+                .zoom_at((pos.x - corner.x, pos.y - corner.y), cell_size);
+        }
+    }
+
+    fn ui_grid_canvas(&mut self, corner: egui::Pos2, ui: &mut egui::Ui) {
+        let viewport = self.viewport;
+        let screen = |cell: (i32, i32)| {
+            let (x, y) = viewport.cell_origin(cell);
+            corner + egui::Vec2::new(x, y)
+        };
+        let cell_rect = |row: usize, col: usize| {
+            egui::Rect::from_min_size(
+                screen((row as i32, col as i32)),
+                egui::Vec2::splat(viewport.cell_size),
+            )
+        };
+
+        self.update_map_texture(ui.ctx());
+        // Zoomed in or panned, the grid overflows the canvas.
+        let painter = ui.painter_at(ui.min_rect());
+        if let Some(texture) = &self.map_texture {
+            painter.add(egui::Shape::image(
+                texture.id(),
+                egui::Rect::from_min_size(
+                    screen((0, 0)),
+                    egui::Vec2::new(self.grid().width as f32, self.grid().height as f32)
+                        * viewport.cell_size,
+                ),
+                egui::Rect::from_min_max(egui::Pos2::ZERO, egui::Pos2::new(1.0, 1.0)),
+                egui::Color32::WHITE,
+            ));
+        }
+
+        // Only the cells inside the visible part of the canvas are drawn on top of
+        // the map layer, batched into a single mesh.
+        let visible = viewport.visible_in(self.grid().width, self.grid().height);
+        let mut overlay = egui::Mesh::default();
+        let mut mark = |cell: (i32, i32), color: egui::Color32| {
+            if visible.is_some_and(|visible| visible.contains(cell)) {
+                overlay.add_colored_rect(cell_rect(cell.0 as usize, cell.1 as usize), color);
+            }
+        };
+        for (cell, color) in self.explored_colors() {
+            mark(cell, color);
+        }
+        if let Some(path) = &self.path {
+            for &cell in path {
+                mark(cell, egui::Color32::from_rgb(0, 0, 255));
+            }
+        }
+        if let Some((from, to)) = self.shape {
+            // The shape is drawn on a scratch grid spanning it, rather than
+            // on a copy of the whole map every frame.
+            let span = Rect::new(from, to);
+            let origin = (span.top, span.left);
+            let local = |(row, col): (i32, i32)| (row - origin.0, col - origin.1);
+            let mut scratch = Grid::new(span.width(), span.height());
+            draw_shape(
+                &mut scratch,
+                &self.paint_mode,
+                self.shape_filled,
+                (local(from), local(to)),
+                true,
+            );
+            for (row, col) in scratch.cells_where(|&cell| cell) {
+                mark((row as i32 + origin.0, col as i32 + origin.1), SHAPE_COLOR);
+            }
+        }
+        mark(self.start, egui::Color32::from_rgb(0, 255, 0));
+        mark(self.end, egui::Color32::from_rgb(255, 0, 0));
+        if let Some((row, col)) = self
+            .highlited
+            .filter(|&(row, col)| row < self.grid().height && col < self.grid().width)
+        {
+            let cells = match self.paint_mode {
+                PaintTile::ObstaclePlacement => {
+                    brush_cells((row as i32, col as i32), self.brush_size, self.brush_round)
+                }
+                _ => vec![(row as i32, col as i32)],
+            };
+            for (row, col) in cells {
+                let Some((row, col)) = Coord::new(row, col)
+                    .to_usize()
+                    .filter(|&(row, col)| row < self.grid().height && col < self.grid().width)
+                else {
+                    continue;
+                };
+                let tmp = self.cell_color(row, col).to_array();
+                let color = egui::Color32::from_rgb(
+                    (tmp[0] as f32 * 0.5) as u8,
+                    (tmp[1] as f32 * 0.5) as u8,
+                    (tmp[2] as f32 * 0.5) as u8,
+                );
+                mark((row as i32, col as i32), color);
+            }
+        }
+        painter.add(egui::Shape::mesh(overlay));
+    }
+
+    fn handle_canvas_response(
+        &mut self,
+        response: egui::Response,
+        ui: &mut egui::Ui,
+        corner: egui::Pos2,
+    ) {
+        self.handle_canvas_navigation(&response, ui, corner);
+        if response.clicked()
+            || response.dragged_by(egui::PointerButton::Primary)
+            || response.double_clicked()
+        {
+            let mouse_pos = ui.input().pointer.interact_pos();
+            if let Some(pos) = mouse_pos {
+                if let Some((row, col)) = self.cell_under(pos, corner) {
+                    match self.paint_mode {
+                        PaintTile::Start => self.start = (row as i32, col as i32),
+                        PaintTile::End => self.end = (row as i32, col as i32),
+                        PaintTile::ObstaclePlacement => {
+                            // The whole stroke is one step of the history.
+                            let value = match self.stroke_value {
+                                Some(value) => value,
+                                None => {
+                                    self.history.begin();
+                                    let value = if self.grid()[(row, col)] == 0 { 1 } else { 0 };
+                                    *self.stroke_value.insert(value)
+                                }
+                            };
+                            let cells = brush_cells(
+                                (row as i32, col as i32),
+                                self.brush_size,
+                                self.brush_round,
+                            );
+                            self.history.edit(|grid| {
+                                for cell in cells {
+                                    if let Some((row, col)) = Coord::from(cell).to_usize() {
+                                        grid.set(row, col, value);
+                                    }
+                                }
+                            });
+                        }
+                        PaintTile::Line | PaintTile::Rectangle => {
+                            let cell = (row as i32, col as i32);
+                            let from = self.shape.map_or(cell, |(from, _)| from);
+                            self.shape = Some((from, cell));
+                        }
+                        PaintTile::Nothing => {}
+                    }
+                    if self.shape.is_none() {
+                        self.find_path();
+                    }
+                }
+            }
+        }
+
+        if !response.dragged_by(egui::PointerButton::Primary) {
+            if self.stroke_value.take().is_some() {
+                self.history.commit();
+            }
+            if let Some(shape) = self.shape.take() {
+                let (tool, filled) = (&self.paint_mode, self.shape_filled);
+                self.history
+                    .edit(|grid| draw_shape(grid, tool, filled, shape, 1));
+                self.find_path();
+            }
+        }
+
+        if response.hovered() {
+            self.highlited = response
+                .hover_pos()
+                .and_then(|pos| self.cell_under(pos, corner));
+        }
+    }
+}
+
+impl eframe::App for MyApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Ctrl+Z undoes, Ctrl+Y and Ctrl+Shift+Z redo (Cmd on macOS).
+        let (undo, redo) = {
+            let input = ctx.input();
+            let command = input.modifiers.command;
+            let z = command && input.key_pressed(egui::Key::Z);
+            let y = command && input.key_pressed(egui::Key::Y);
+            (z && !input.modifiers.shift, y || z && input.modifiers.shift)
+        };
+        if undo || redo {
+            self.undo(redo);
+        }
+        egui::SidePanel::left("controls").show(ctx, |ui| {
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                self.ui_control(ui);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(RichText::new("Start").color(egui::Color32::GREEN))
+                        .clicked()
+                    {
+                        self.paint_mode = PaintTile::Start;
+                    }
+                    if ui
+                        .button(RichText::new("End").color(egui::Color32::RED))
+                        .clicked()
+                    {
+                        self.paint_mode = PaintTile::End;
+                    }
+                    // make string "Obstacle/Empty" where Obstacle is Black, Empty is White
+                    let mut text = LayoutJob::default();
+                    text.append(
+                        "Obstacle",
+                        0.0,
+                        TextFormat {
+                            font_id: FontId::new(14., egui::FontFamily::Proportional),
+                            color: egui::Color32::BLACK,
+                            ..Default::default()
+                        },
+                    );
+                    text.append(
+                        "/",
+                        0.0,
+                        TextFormat {
+                            font_id: FontId::new(14., egui::FontFamily::Proportional),
+                            color: egui::Color32::GRAY,
+                            ..Default::default()
+                        },
+                    );
+                    text.append(
+                        "Empty",
+                        0.0,
+                        TextFormat {
+                            font_id: FontId::new(14., egui::FontFamily::Proportional),
+                            color: egui::Color32::WHITE,
+                            ..Default::default()
+                        },
+                    );
+
+                    if ui.button(text).clicked() {
+                        self.paint_mode = PaintTile::ObstaclePlacement;
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.add(egui::Slider::new(&mut self.brush_size, 1..=15).text("brush"));
+                    ui.checkbox(&mut self.brush_round, "round");
+                });
+                ui.horizontal(|ui| {
+                    if ui.button("Line").clicked() {
+                        self.paint_mode = PaintTile::Line;
+                    }
+                    if ui.button("Rectangle").clicked() {
+                        self.paint_mode = PaintTile::Rectangle;
+                    }
+                    ui.checkbox(&mut self.shape_filled, "filled");
+                });
+                ui.horizontal(|ui| {
+                    let path_state = if self.path.is_some() {
+                        RichText::new("SUCCESS").underline()
+                    } else {
+                        RichText::new("FAIL").underline()
+                    };
+                    ui.label(path_state);
+                    if ui.button("Clear grid").clicked() {
+                        self.history.edit(|grid| grid.fill(0));
+                        self.find_path()
+                    }
+                });
+                ui.horizontal(|ui| {
+                    let (can_undo, can_redo) = (self.history.can_undo(), self.history.can_redo());
+                    if ui
+                        .add_enabled(can_undo, egui::Button::new("Undo"))
+                        .clicked()
+                    {
+                        self.undo(false);
+                    }
+                    if ui
+                        .add_enabled(can_redo, egui::Button::new("Redo"))
+                        .clicked()
+                    {
+                        self.undo(true);
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.show_explored, "Show explored:");
+                    ui.colored_label(CLOSED_COLOR, "closed");
+                    ui.colored_label(OPEN_COLOR, "open");
+                });
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.show_heatmap, "g-score heatmap:");
+                    ui.colored_label(NEAR_COLOR, "near");
+                    ui.colored_label(FAR_COLOR, "far");
+                });
+                ui.colored_label(
+                    egui::Color32::GRAY,
+                    format!(
+                        "{}x{} map, {}",
+                        self.grid().width,
+                        self.grid().height,
+                        self.grid().memory_usage()
+                    ),
+                );
+                if let Some(e) = self.path_error {
+                    ui.colored_label(egui::Color32::RED, e.to_string());
+                }
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.fit_cells, "Fit");
+                    ui.add_enabled(
+                        !self.fit_cells,
+                        egui::Slider::new(
+                            &mut self.viewport.cell_size,
+                            MIN_CELL_SIZE..=MAX_CELL_SIZE,
+                        )
+                        .text("cell size"),
+                    );
+                });
+                self.ui_stats(ui);
+            });
+        });
+        egui::CentralPanel::default().show(ctx, |ui| {
+            let mut corner = egui::Pos2::ZERO;
+            let canvas = Frame::canvas(ui.style())
+                .show(ui, |ui| {
+                    let (_, rect) = ui.allocate_space(ui.available_size());
+                    let margin = 10.0;
+                    corner = rect.min + egui::Vec2::splat(margin);
+                    self.viewport.size = (rect.width() - margin, rect.height() - margin);
+                    if self.fit_cells {
+                        self.viewport.fit(self.grid().width, self.grid().height);
+                    }
+                    self.ui_grid_canvas(corner, ui);
+                })
+                .response;
+            let response = canvas.interact(egui::Sense::click_and_drag());
+            self.handle_canvas_response(response, ui, corner);
+        });
+    }
+}
+
+/// Opens the window of the visualisation and blocks until it is closed.
+pub fn run() {
+    let options = eframe::NativeOptions {
+        initial_window_size: Some(egui::Vec2::new(900.0, 700.0)),
+        resizable: true,
+        always_on_top: false,
+        ..Default::default()
+    };
+    let mut app = MyApp::default();
+    app.find_path();
+    eframe::run_native(
+        "A* algorithm visualisation",
+        options,
+        Box::new(|_cc| Box::new(app)),
+    );
+}
@@ -0,0 +1,305 @@
+//! Heuristics estimating the remaining cost to the goal.
+//!
+//! Every search takes a `Heuristic`. Closures and functions like
+//! `manhattan_distance` are heuristics as they are, the types of this module
+//! cover the common grid metrics and can be composed with `Max`, `Scaled` and
+//! `Dynamic`, or adapted to wrapping grids with `Toroidal`.
+
+use crate::grid3::Voxel;
+use crate::{diagonal_distance, manhattan_distance};
+
+/// Estimates the cost of the cheapest path from a node to the goal.
+///
+/// A* finds the shortest path as long as the estimate never exceeds the real
+/// cost (the heuristic is admissible).
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::astar;
+/// use pathfinding::heuristic::{Heuristic, Manhattan, Max, Scaled, Zero};
+///
+/// assert_eq!(Manhattan.estimate((0, 0), (2, 3)), 5);
+/// assert_eq!(Scaled(Manhattan, 1.5).estimate((0, 0), (2, 3)), 7);
+/// assert_eq!(Max(Zero, Manhattan).estimate((0, 0), (2, 3)), 5);
+///
+/// let grid = vec![vec![0; 4]; 3];
+/// let path = astar((0, 0), (2, 3), &grid, Max(Zero, Manhattan), |row, col, grid| {
+///     grid[row][col] == 1
+/// });
+///
+/// assert_eq!(path.map(|path| path.cost()), Ok(5));
+/// ```
+pub trait Heuristic<N> {
+    /// Returns the estimated cost from `node` to `goal`.
+    fn estimate(&self, node: N, goal: N) -> u32;
+}
+
+impl<N, F> Heuristic<N> for F
+where
+    F: Fn(N, N) -> u32,
+{
+    fn estimate(&self, node: N, goal: N) -> u32 {
+        self(node, goal)
+    }
+}
+
+/// Always estimates `0`, turning A* into Dijkstra's algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Zero;
+
+impl<N> Heuristic<N> for Zero {
+    fn estimate(&self, _node: N, _goal: N) -> u32 {
+        0
+    }
+}
+
+/// The manhattan distance, for 4-directional movement, see `manhattan_distance`.
+/// On voxels it is the distance for 6-connected movement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Manhattan;
+
+impl Heuristic<(i32, i32)> for Manhattan {
+    fn estimate(&self, node: (i32, i32), goal: (i32, i32)) -> u32 {
+        manhattan_distance(node, goal)
+    }
+}
+
+impl Heuristic<Voxel> for Manhattan {
+    fn estimate(&self, node: Voxel, goal: Voxel) -> u32 {
+        (node.0 - goal.0).unsigned_abs()
+            + (node.1 - goal.1).unsigned_abs()
+            + (node.2 - goal.2).unsigned_abs()
+    }
+}
+
+/// The diagonal (Chebyshev) distance, for 8-directional movement where a
+/// diagonal step costs as much as a straight one, see `diagonal_distance`.
+/// On voxels it is the distance for 26-connected movement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Diagonal;
+
+impl Heuristic<(i32, i32)> for Diagonal {
+    fn estimate(&self, node: (i32, i32), goal: (i32, i32)) -> u32 {
+        diagonal_distance(node, goal)
+    }
+}
+
+impl Heuristic<Voxel> for Diagonal {
+    fn estimate(&self, node: Voxel, goal: Voxel) -> u32 {
+        (node.0 - goal.0)
+            .unsigned_abs()
+            .max((node.1 - goal.1).unsigned_abs())
+            .max((node.2 - goal.2).unsigned_abs())
+    }
+}
+
+/// The octile distance, for 8-directional movement where straight and
+/// diagonal steps have their own costs.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::heuristic::{Heuristic, Octile};
+///
+/// // The usual integer approximation of 1 and the square root of 2.
+/// let octile = Octile { straight: 10, diagonal: 14 };
+///
+/// assert_eq!(octile.estimate((0, 0), (2, 5)), 2 * 14 + 3 * 10);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Octile {
+    /// The cost of an up/down/left/right step.
+    pub straight: u32,
+    /// The cost of a diagonal step.
+    pub diagonal: u32,
+}
+
+impl Heuristic<(i32, i32)> for Octile {
+    fn estimate(&self, node: (i32, i32), goal: (i32, i32)) -> u32 {
+        let dr = (node.0 - goal.0).unsigned_abs();
+        let dc = (node.1 - goal.1).unsigned_abs();
+        let (low, high) = (dr.min(dc), dr.max(dc));
+        let diagonal = self.diagonal.min(2 * self.straight);
+        low.saturating_mul(diagonal)
+            .saturating_add((high - low).saturating_mul(self.straight))
+    }
+}
+
+/// The straight-line distance, rounded down, for any-angle movement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Euclidean;
+
+impl Heuristic<(i32, i32)> for Euclidean {
+    fn estimate(&self, node: (i32, i32), goal: (i32, i32)) -> u32 {
+        let dr = (node.0 - goal.0).unsigned_abs() as u64;
+        let dc = (node.1 - goal.1).unsigned_abs() as u64;
+        (dr * dr + dc * dc).isqrt() as u32
+    }
+}
+
+/// Only admissible for `Connectivity::Six`: with 26-connected movement a
+/// diagonal step costs `1`, and `Euclidean` overestimates, use `Diagonal`.
+impl Heuristic<Voxel> for Euclidean {
+    fn estimate(&self, node: Voxel, goal: Voxel) -> u32 {
+        let dx = (node.0 - goal.0).unsigned_abs() as u64;
+        let dy = (node.1 - goal.1).unsigned_abs() as u64;
+        let dz = (node.2 - goal.2).unsigned_abs() as u64;
+        (dx * dx + dy * dy + dz * dz).isqrt() as u32
+    }
+}
+
+/// The larger estimate of two heuristics. Admissible when both are, and at
+/// least as informed as either.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Max<A, B>(pub A, pub B);
+
+impl<N: Copy, A: Heuristic<N>, B: Heuristic<N>> Heuristic<N> for Max<A, B> {
+    fn estimate(&self, node: N, goal: N) -> u32 {
+        self.0.estimate(node, goal).max(self.1.estimate(node, goal))
+    }
+}
+
+/// A heuristic multiplied by a weight (rounded down). Weights above `1.0`
+/// trade optimality for speed (weighted A*), below `1.0` the heuristic
+/// becomes more conservative.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Scaled<H>(pub H, pub f32);
+
+impl<N, H: Heuristic<N>> Heuristic<N> for Scaled<H> {
+    fn estimate(&self, node: N, goal: N) -> u32 {
+        (self.0.estimate(node, goal) as f64 * self.1 as f64).min(u32::MAX as f64) as u32
+    }
+}
+
+/// Dynamic weighting: a heuristic inflated by up to `1 + epsilon` far from the
+/// goal, less and less as the goal gets closer, down to the plain estimate at
+/// the goal. The search rushes ahead like weighted A* while far away and is
+/// careful near the goal, where weighted A* wastes the most path cost. With an
+/// admissible heuristic the paths cost at most `1 + epsilon` times the optimum.
+///
+/// The weight of a node is `1 + epsilon * min(h, horizon) / horizon`, `h` being
+/// the plain estimate, so the start is inflated the most when the horizon is
+/// its estimate, as set by `Dynamic::new`.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::astar_graph_with_stats;
+/// use pathfinding::graph::GridGraph;
+/// use pathfinding::heuristic::{Dynamic, Manhattan, Scaled};
+/// use pathfinding::testing::{random_grid, Rng};
+///
+/// // The nodes expanded and the path costs summed over a few random maps.
+/// let (mut plain, mut dynamic, mut weighted) = ((0, 0), (0, 0), (0, 0));
+/// let mut rng = Rng::new(3);
+/// for _ in 0..20 {
+///     let mut grid = random_grid(&mut rng, 40, 40, 0.3);
+///     let (start, goal) = ((0, 0), (39, 39));
+///     grid[0][0] = 0;
+///     grid[39][39] = 0;
+///     let graph = GridGraph::new(&grid, |row, col, grid| grid[row][col] == 1);
+///     let runs = [
+///         (&mut plain, astar_graph_with_stats(&graph, start, goal, Manhattan)),
+///         (&mut dynamic, astar_graph_with_stats(&graph, start, goal, Dynamic::new(Manhattan, 1.0, start, goal))),
+///         (&mut weighted, astar_graph_with_stats(&graph, start, goal, Scaled(Manhattan, 2.0))),
+///     ];
+///     if runs[0].1.cost.is_none() {
+///         continue;
+///     }
+///     for (total, result) in runs {
+///         total.0 += result.nodes_expanded;
+///         total.1 += result.cost.unwrap();
+///     }
+/// }
+///
+/// assert!(dynamic.0 < plain.0 * 2 / 3);
+/// assert!(dynamic.1 < weighted.1);
+/// assert!(dynamic.1 <= plain.1 * 2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Dynamic<H> {
+    /// The heuristic to inflate.
+    pub heuristic: H,
+    /// How much the heuristic is inflated at most, `0.0` is plain A*.
+    pub epsilon: f32,
+    /// The estimate from which on the full inflation applies.
+    pub horizon: u32,
+}
+
+impl<H> Dynamic<H> {
+    /// Inflates `heuristic` by up to `1 + epsilon` for a search from `start` to
+    /// `goal`, fully at the start.
+    pub fn new<N>(heuristic: H, epsilon: f32, start: N, goal: N) -> Self
+    where
+        H: Heuristic<N>,
+    {
+        let horizon = heuristic.estimate(start, goal);
+        Dynamic {
+            heuristic,
+            epsilon,
+            horizon,
+        }
+    }
+}
+
+impl<N, H: Heuristic<N>> Heuristic<N> for Dynamic<H> {
+    fn estimate(&self, node: N, goal: N) -> u32 {
+        let estimate = self.heuristic.estimate(node, goal) as f64;
+        let remaining = match self.horizon {
+            0 => 0.0,
+            horizon => estimate.min(horizon as f64) / horizon as f64,
+        };
+        let weight = 1.0 + self.epsilon as f64 * remaining;
+        (estimate * weight).min(u32::MAX as f64) as u32
+    }
+}
+
+/// A grid heuristic for grids wrapping around their edges (see
+/// `MovementModel::with_wrapping`): the smallest estimate to the goal or to
+/// one of its copies across the edges. Admissible when `heuristic` is on a
+/// grid that doesn't wrap.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::builder::AstarBuilder;
+/// use pathfinding::heuristic::{Manhattan, Toroidal};
+/// use pathfinding::movement::MovementModel;
+///
+/// let grid = vec![
+///     vec![0, 0, 0, 1, 0, 0],
+///     vec![0, 0, 0, 1, 0, 0],
+/// ];
+///
+/// let result = AstarBuilder::new()
+///     .movement(MovementModel::four_way().with_wrapping(true))
+///     .heuristic(Toroidal { heuristic: Manhattan, height: 2, width: 6 })
+///     .run((0, 1), (1, 5), &grid);
+///
+/// // Left across the edge, the wall is in the way the other way round.
+/// assert_eq!(result.cost, Some(3));
+/// assert!(result.path.unwrap().steps().any(|step| step == ((1, 0), (1, 5)) || step == ((0, 0), (0, 5))));
+/// assert_eq!(AstarBuilder::new().run((0, 1), (1, 5), &grid).cost, None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Toroidal<H> {
+    /// The heuristic on a grid that doesn't wrap.
+    pub heuristic: H,
+    /// The number of rows of the grid.
+    pub height: usize,
+    /// The number of columns of the grid.
+    pub width: usize,
+}
+
+impl<H: Heuristic<(i32, i32)>> Heuristic<(i32, i32)> for Toroidal<H> {
+    fn estimate(&self, node: (i32, i32), goal: (i32, i32)) -> u32 {
+        let (height, width) = (self.height as i32, self.width as i32);
+        [-height, 0, height]
+            .into_iter()
+            .flat_map(|dr| [-width, 0, width].map(|dc| (goal.0 + dr, goal.1 + dc)))
+            .map(|copy| self.heuristic.estimate(node, copy))
+            .min()
+            .unwrap_or(0)
+    }
+}
@@ -0,0 +1,214 @@
+//! Undo and redo of the edits of a grid, recorded as `Grid::diff` patches so
+//! a step only stores the cells it changed.
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use crate::grid::{CellChange, Grid, Rect};
+
+/// A grid with the history of its edits.
+///
+/// The history keeps at most `max_changes` changed cells over all its steps
+/// (each one `size_of::<CellChange<T>>()` bytes), forgetting the oldest steps
+/// past that. A single edit changing more cells can't be undone.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::grid::Grid;
+/// use pathfinding::history::GridHistory;
+///
+/// let mut history = GridHistory::new(Grid::new(3, 2), 100);
+/// history.edit(|grid| grid.set(0, 1, 1));
+/// history.edit(|grid| grid.fill(1));
+/// assert_eq!(history.grid().to_vec(), vec![vec![1, 1, 1], vec![1, 1, 1]]);
+///
+/// assert!(history.undo());
+/// assert_eq!(history.grid().to_vec(), vec![vec![0, 1, 0], vec![0, 0, 0]]);
+/// assert!(history.undo());
+/// assert!(!history.undo());
+/// assert_eq!(history.grid(), &Grid::new(3, 2));
+///
+/// assert!(history.redo());
+/// assert_eq!(history.grid().get(0, 1), Some(&1));
+///
+/// // A new edit forgets the steps undone before it.
+/// history.edit(|grid| grid.set(1, 2, 1));
+/// assert!(!history.redo());
+///
+/// // Filling the grid changes more cells than the budget allows.
+/// let mut small = GridHistory::new(Grid::new(3, 2), 4);
+/// small.edit(|grid| grid.set(0, 0, 1));
+/// small.edit(|grid| grid.fill(1));
+/// assert!(!small.can_undo());
+/// ```
+#[derive(Debug, Clone)]
+pub struct GridHistory<T> {
+    grid: Grid<T>,
+    undo: VecDeque<Vec<CellChange<T>>>,
+    redo: Vec<Vec<CellChange<T>>>,
+    /// The number of cells changed by the steps of `undo` and `redo`.
+    changes: usize,
+    max_changes: usize,
+    /// The grid as it was when the step being recorded began, see `begin`.
+    step_start: Option<Grid<T>>,
+}
+
+impl<T: Clone + PartialEq> GridHistory<T> {
+    /// Starts an empty history of `grid`, keeping at most `max_changes`
+    /// changed cells.
+    pub fn new(grid: Grid<T>, max_changes: usize) -> Self {
+        GridHistory {
+            grid,
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            changes: 0,
+            max_changes,
+            step_start: None,
+        }
+    }
+
+    /// The grid as it is now.
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    /// The cells changed by the edits, undos and redos since the last call,
+    /// when the grid tracks its changes, see `Grid::take_dirty`.
+    pub fn take_dirty(&mut self) -> Option<Rect> {
+        self.grid.take_dirty()
+    }
+
+    /// Unwraps the grid, dropping its history.
+    pub fn into_inner(self) -> Grid<T> {
+        self.grid
+    }
+
+    /// Edits the grid with `f` as one step of the history, which `undo`
+    /// reverts as a whole. The steps undone before are forgotten, and an edit
+    /// leaving the grid as it was isn't recorded.
+    ///
+    /// The changes are found by comparing the grid before and after the edit,
+    /// which takes a copy of the grid. Between `begin` and `commit` the edit
+    /// is part of the step being recorded instead, and the grid isn't copied.
+    ///
+    /// ### Returns
+    ///
+    /// What `f` returns.
+    pub fn edit<R, F: FnOnce(&mut Grid<T>) -> R>(&mut self, f: F) -> R {
+        if self.step_start.is_some() {
+            return f(&mut self.grid);
+        }
+        self.begin();
+        let result = f(&mut self.grid);
+        self.commit();
+        result
+    }
+
+    /// Starts recording a step made of all the edits until `commit`, e.g. the
+    /// frames of a brush stroke, copying the grid once. Does nothing if a step
+    /// is already being recorded.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::Grid;
+    /// use pathfinding::history::GridHistory;
+    ///
+    /// let mut history = GridHistory::new(Grid::new(3, 1), 100);
+    /// history.begin();
+    /// for col in 0..3 {
+    ///     history.edit(|grid| grid.set(0, col, 1));
+    /// }
+    /// history.commit();
+    /// assert_eq!(history.grid().to_vec(), vec![vec![1, 1, 1]]);
+    ///
+    /// assert!(history.undo());
+    /// assert_eq!(history.grid(), &Grid::new(3, 1));
+    /// assert!(!history.can_undo());
+    /// ```
+    pub fn begin(&mut self) {
+        if self.step_start.is_none() {
+            self.step_start = Some(self.grid.clone());
+        }
+    }
+
+    /// Records the changes made since `begin` as one step, which `undo`
+    /// reverts as a whole. Does nothing if no step is being recorded.
+    pub fn commit(&mut self) {
+        let Some(before) = self.step_start.take() else {
+            return;
+        };
+        if (before.width, before.height) != (self.grid.width, self.grid.height) {
+            // Patches can't resize a grid, so the edit can't be reverted.
+            self.clear();
+            return;
+        }
+        let patch = before.diff(&self.grid);
+        if !patch.is_empty() {
+            self.changes -= self.redo.drain(..).map(|step| step.len()).sum::<usize>();
+            self.push_undo(patch);
+        }
+    }
+
+    /// Reverts the last step.
+    ///
+    /// ### Returns
+    ///
+    /// `false` if there was nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        self.commit();
+        let Some(patch) = self.undo.pop_back() else {
+            return false;
+        };
+        self.grid.revert_patch(&patch);
+        self.redo.push(patch);
+        true
+    }
+
+    /// Applies again the last step undone.
+    ///
+    /// ### Returns
+    ///
+    /// `false` if there was nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        self.commit();
+        let Some(patch) = self.redo.pop() else {
+            return false;
+        };
+        self.grid.apply_patch(&patch);
+        self.undo.push_back(patch);
+        true
+    }
+
+    /// Whether there is a step to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Whether there is a step to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Forgets every step, the one being recorded included, keeping the grid
+    /// as it is.
+    pub fn clear(&mut self) {
+        self.step_start = None;
+        self.undo.clear();
+        self.redo.clear();
+        self.changes = 0;
+    }
+
+    /// Records a step, dropping the oldest ones beyond the budget.
+    fn push_undo(&mut self, patch: Vec<CellChange<T>>) {
+        self.changes += patch.len();
+        self.undo.push_back(patch);
+        while self.changes > self.max_changes {
+            match self.undo.pop_front() {
+                Some(oldest) => self.changes -= oldest.len(),
+                None => break,
+            }
+        }
+    }
+}
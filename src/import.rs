@@ -0,0 +1,332 @@
+//! Import of per-cell costs from CSV files and grayscale heightmaps, of
+//! obstacle grids from images, and CSV import and export of grids.
+//!
+//! The imported cost grids can be searched with `graph::WeightedGridGraph`, or
+//! with `astar_weighted` once wrapped in a `costgrid::CostGrid`, so real
+//! elevation or traffic data can drive the weighted search.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::graph::IMPASSABLE;
+use crate::grid::Grid;
+
+/// How raw imported values are turned into cell costs.
+///
+/// The resulting costs are rounded and clamped to `1..IMPASSABLE`, so that a
+/// free cell never costs less than a unit step.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scaling {
+    /// The values are used as they are.
+    Identity,
+    /// Every value becomes `value * factor + offset`.
+    Linear { factor: f64, offset: f64 },
+    /// The smallest value is mapped to `min` and the largest to `max`, linearly in between.
+    Normalize { min: u32, max: u32 },
+}
+
+impl Scaling {
+    /// Applies the scaling to every passable value of the grid. `None` values are impassable.
+    fn apply(&self, values: Vec<Vec<Option<f64>>>) -> Vec<Vec<u32>> {
+        let passable = || values.iter().flatten().flatten().copied();
+        let low = passable().fold(f64::INFINITY, f64::min);
+        let high = passable().fold(f64::NEG_INFINITY, f64::max);
+        let scale = |value: f64| match *self {
+            Scaling::Identity => value,
+            Scaling::Linear { factor, offset } => value * factor + offset,
+            Scaling::Normalize { min, max } if high > low => {
+                min as f64 + (value - low) / (high - low) * (max as f64 - min as f64)
+            }
+            Scaling::Normalize { min, .. } => min as f64,
+        };
+        values
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|value| match value {
+                        Some(value) => {
+                            scale(*value).round().clamp(1.0, (IMPASSABLE - 1) as f64) as u32
+                        }
+                        None => IMPASSABLE,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Error produced when costs can't be imported.
+#[derive(Debug)]
+pub enum ImportError {
+    /// The file couldn't be read.
+    Io(io::Error),
+    /// A CSV field isn't a valid value (`line` and `column` are 1-based).
+    InvalidValue {
+        line: usize,
+        column: usize,
+        value: String,
+    },
+    /// A row doesn't have as many fields as the first one.
+    RaggedRow {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// There is nothing to import.
+    Empty,
+    /// The image couldn't be decoded.
+    #[cfg(feature = "image")]
+    Image(image::ImageError),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Io(e) => write!(f, "{}", e),
+            ImportError::InvalidValue {
+                line,
+                column,
+                value,
+            } => write!(
+                f,
+                "line {}, column {}: invalid value {:?}",
+                line, column, value
+            ),
+            ImportError::RaggedRow {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {}: expected {} fields, found {}",
+                line, expected, found
+            ),
+            ImportError::Empty => write!(f, "no costs to import"),
+            #[cfg(feature = "image")]
+            ImportError::Image(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+impl From<io::Error> for ImportError {
+    fn from(e: io::Error) -> Self {
+        ImportError::Io(e)
+    }
+}
+
+/// Parses a cost grid from CSV text: one row of the grid per line, comma separated.
+///
+/// Empty fields and fields holding `x` or a negative number are impassable.
+///
+/// ### Arguments
+///
+/// * `text` - The CSV text.
+/// * `scaling` - How the values are turned into costs.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::graph::IMPASSABLE;
+/// use pathfinding::import::{costs_from_csv, Scaling};
+///
+/// let costs = costs_from_csv("1,2,3\n4,x,6\n", Scaling::Linear { factor: 10.0, offset: 0.0 }).unwrap();
+///
+/// assert_eq!(costs, vec![vec![10, 20, 30], vec![40, IMPASSABLE, 60]]);
+/// ```
+pub fn costs_from_csv(text: &str, scaling: Scaling) -> Result<Vec<Vec<u32>>, ImportError> {
+    let values = parse_csv(text, |field| match field {
+        "" | "x" | "X" => Some(None),
+        field => match field.parse::<f64>() {
+            Ok(value) if value < 0.0 => Some(None),
+            Ok(value) if value.is_finite() => Some(Some(value)),
+            _ => None,
+        },
+    })?;
+    Ok(scaling.apply(values))
+}
+
+/// Splits CSV text into rows of values, `parse` turning the trimmed fields
+/// into values, or `None` for invalid ones. Blank lines are skipped.
+fn parse_csv<V, P>(text: &str, parse: P) -> Result<Vec<Vec<V>>, ImportError>
+where
+    P: Fn(&str) -> Option<V>,
+{
+    let mut values: Vec<Vec<V>> = vec![];
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let row = line
+            .split(',')
+            .enumerate()
+            .map(|(column, field)| {
+                parse(field.trim()).ok_or_else(|| ImportError::InvalidValue {
+                    line: index + 1,
+                    column: column + 1,
+                    value: field.trim().to_string(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if let Some(expected) = values.first().map(Vec::len) {
+            if expected != row.len() {
+                return Err(ImportError::RaggedRow {
+                    line: index + 1,
+                    expected,
+                    found: row.len(),
+                });
+            }
+        }
+        values.push(row);
+    }
+    if values.is_empty() {
+        return Err(ImportError::Empty);
+    }
+    Ok(values)
+}
+
+/// Reads a cost grid from a CSV file, see `costs_from_csv`.
+pub fn costs_from_csv_file(path: &Path, scaling: Scaling) -> Result<Vec<Vec<u32>>, ImportError> {
+    costs_from_csv(&fs::read_to_string(path)?, scaling)
+}
+
+/// Builds a cost grid from row-major grayscale samples, e.g. the pixels of a heightmap.
+///
+/// ### Arguments
+///
+/// * `width` - The number of samples per row.
+/// * `samples` - The samples, row after row.
+/// * `scaling` - How the samples are turned into costs.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::import::{costs_from_samples, Scaling};
+///
+/// let costs = costs_from_samples(2, &[0, 255, 128, 64], Scaling::Normalize { min: 1, max: 11 }).unwrap();
+///
+/// assert_eq!(costs, vec![vec![1, 11], vec![6, 4]]);
+/// ```
+pub fn costs_from_samples<S>(
+    width: usize,
+    samples: &[S],
+    scaling: Scaling,
+) -> Result<Vec<Vec<u32>>, ImportError>
+where
+    S: Copy + Into<f64>,
+{
+    if width == 0 || samples.is_empty() {
+        return Err(ImportError::Empty);
+    }
+    if !samples.len().is_multiple_of(width) {
+        return Err(ImportError::RaggedRow {
+            line: samples.len() / width + 1,
+            expected: width,
+            found: samples.len() % width,
+        });
+    }
+    let values = samples
+        .chunks(width)
+        .map(|row| row.iter().map(|&sample| Some(sample.into())).collect())
+        .collect();
+    Ok(scaling.apply(values))
+}
+
+/// Loads a cost grid from a grayscale heightmap image, one cell per pixel.
+/// Color images are converted to 16 bit luminance first.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::import::{costs_from_heightmap, Scaling};
+///
+/// let path = std::env::temp_dir().join("pathfinding-heightmap-doc.png");
+/// image::GrayImage::from_raw(2, 1, vec![0, 255]).unwrap().save(&path).unwrap();
+///
+/// let costs = costs_from_heightmap(&path, Scaling::Normalize { min: 1, max: 100 }).unwrap();
+///
+/// assert_eq!(costs, vec![vec![1, 100]]);
+/// ```
+#[cfg(feature = "image")]
+pub fn costs_from_heightmap(path: &Path, scaling: Scaling) -> Result<Vec<Vec<u32>>, ImportError> {
+    let image = image::open(path).map_err(ImportError::Image)?.into_luma16();
+    costs_from_samples(image.width() as usize, image.as_raw(), scaling)
+}
+
+impl<T: FromStr + Clone> Grid<T> {
+    /// Parses a grid from CSV text: one row per line, the cells comma
+    /// separated, as written by `to_csv`. Blank lines are skipped.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::Grid;
+    ///
+    /// let grid: Grid<i32> = Grid::from_csv("0,1,0\n0,0,1\n").unwrap();
+    /// assert_eq!(grid.to_vec(), vec![vec![0, 1, 0], vec![0, 0, 1]]);
+    /// assert_eq!(Grid::from_csv(&grid.to_csv()).unwrap(), grid);
+    ///
+    /// assert!(Grid::<u8>::from_csv("0,1\n0,300\n").is_err());
+    /// ```
+    pub fn from_csv(text: &str) -> Result<Self, ImportError> {
+        Ok(Grid::from_vec(parse_csv(text, |field| field.parse().ok())?))
+    }
+}
+
+impl<T: fmt::Display> Grid<T> {
+    /// Writes the grid as CSV text, one line per row and the cells comma
+    /// separated, for spreadsheets and other tools.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+        for row in self.rows() {
+            for (col, cell) in row.iter().enumerate() {
+                if col > 0 {
+                    csv.push(',');
+                }
+                csv.push_str(&cell.to_string());
+            }
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+#[cfg(feature = "image")]
+impl Grid<i32> {
+    /// Loads a grid from an image, one cell per pixel, e.g. a floor plan or a
+    /// screenshot: pixels darker than `threshold` (in 8 bit luminance) become
+    /// obstacles (`1`), the others free cells (`0`).
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::grid::Grid;
+    ///
+    /// let path = std::env::temp_dir().join("pathfinding-floor-plan-doc.png");
+    /// image::GrayImage::from_raw(3, 1, vec![0, 200, 100]).unwrap().save(&path).unwrap();
+    ///
+    /// let grid = Grid::from_image(&path, 128).unwrap();
+    ///
+    /// assert_eq!(grid.to_vec(), vec![vec![1, 0, 1]]);
+    /// ```
+    pub fn from_image(path: impl AsRef<Path>, threshold: u8) -> Result<Self, ImportError> {
+        let image = image::open(path).map_err(ImportError::Image)?.into_luma8();
+        if image.width() == 0 || image.height() == 0 {
+            return Err(ImportError::Empty);
+        }
+        let cells = image
+            .as_raw()
+            .chunks(image.width() as usize)
+            .map(|row| {
+                row.iter()
+                    .map(|&luma| i32::from(luma < threshold))
+                    .collect()
+            })
+            .collect();
+        Ok(Grid::from_vec(cells))
+    }
+}
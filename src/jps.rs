@@ -0,0 +1,296 @@
+//! Jump point search on four-way grids with unit costs.
+
+use alloc::collections::BinaryHeap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+#[cfg(not(feature = "std"))]
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use crate::check_endpoints;
+use crate::collections::{HashMap, HashSet};
+use crate::heuristic::Heuristic;
+use crate::path::Path;
+use crate::reconstruct_path;
+use crate::search::{Observer, SearchError, SearchResult};
+
+const UP: (i32, i32) = (-1, 0);
+const DOWN: (i32, i32) = (1, 0);
+const LEFT: (i32, i32) = (0, -1);
+const RIGHT: (i32, i32) = (0, 1);
+
+/// Jump point search - A* on a grid with up/down/left/right steps of cost `1`
+/// that only stops at the cells where a shortest path may have to turn, and
+/// skips over the rest in straight jumps. It finds paths as short as `astar`
+/// with four-way movement while putting far fewer cells in the open set.
+///
+/// Of all the shortest paths, the one moving vertically as early as possible
+/// is searched for: a horizontal run only turns next to the corner of an
+/// obstacle, where going vertically one cell earlier is blocked, and every
+/// cell of a vertical run from which a horizontal jump finds such a corner (or
+/// the goal) is a jump point.
+///
+/// ### Arguments
+///
+/// * `start` - The start position.
+/// * `end` - The end position.
+/// * `grid` - The grid (consisting of vector of vectors).
+/// * `heuristic` - The heuristic, it must not overestimate the number of steps
+///   for the path to be the shortest.
+/// * `is_cell_solid` - The predicate closure to check if a node is solid or not.
+///
+/// ### Returns
+///
+/// The `Path` of every cell from start to end, or the `SearchError` telling
+/// why there is none, as for `astar`.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::astar;
+/// use pathfinding::jps::jump_point_search;
+/// use pathfinding::manhattan_distance;
+///
+/// let grid = vec![
+///     vec![0, 0, 0, 0],
+///     vec![0, 1, 1, 0],
+///     vec![0, 0, 1, 0],
+/// ];
+/// let is_solid = |row: usize, col: usize, grid: &Vec<Vec<i32>>| grid[row][col] == 1;
+///
+/// let path = jump_point_search((2, 1), (2, 3), &grid, manhattan_distance, is_solid);
+///
+/// assert_eq!(path.unwrap(), vec![(2, 1), (2, 0), (1, 0), (0, 0), (0, 1), (0, 2), (0, 3), (1, 3), (2, 3)]);
+/// assert_eq!(
+///     jump_point_search((2, 1), (2, 3), &grid, manhattan_distance, is_solid).map(|path| path.cost()),
+///     astar((2, 1), (2, 3), &grid, manhattan_distance, is_solid).map(|path| path.cost()),
+/// );
+/// ```
+pub fn jump_point_search<H, F>(
+    start: (i32, i32),
+    end: (i32, i32),
+    grid: &Vec<Vec<i32>>,
+    heuristic: H,
+    is_cell_solid: F,
+) -> Result<Path<(i32, i32)>, SearchError>
+where
+    H: Heuristic<(i32, i32)>,
+    F: Fn(usize, usize, &Vec<Vec<i32>>) -> bool,
+{
+    jump_point_search_with_stats(start, end, grid, heuristic, is_cell_solid).path
+}
+
+/// Same as `jump_point_search`, but returns a `SearchResult` carrying the path
+/// cost and statistics about the search next to the path.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::astar_with_stats;
+/// use pathfinding::jps::jump_point_search_with_stats;
+/// use pathfinding::manhattan_distance;
+///
+/// let grid = vec![
+///     vec![0, 0, 0, 0, 0, 0],
+///     vec![0, 1, 1, 1, 1, 0],
+///     vec![0, 0, 0, 0, 1, 0],
+///     vec![0, 0, 0, 0, 0, 0],
+/// ];
+/// let is_solid = |row: usize, col: usize, grid: &Vec<Vec<i32>>| grid[row][col] == 1;
+///
+/// let jps = jump_point_search_with_stats((2, 0), (2, 5), &grid, manhattan_distance, is_solid);
+/// let astar = astar_with_stats((2, 0), (2, 5), &grid, manhattan_distance, is_solid);
+///
+/// assert_eq!(jps.cost, Some(7));
+/// assert_eq!(jps.cost, astar.cost);
+/// assert_eq!(jps.path.unwrap().len(), 8);
+/// assert!(jps.nodes_generated < astar.nodes_generated);
+/// ```
+pub fn jump_point_search_with_stats<H, F>(
+    start: (i32, i32),
+    end: (i32, i32),
+    grid: &Vec<Vec<i32>>,
+    heuristic: H,
+    is_cell_solid: F,
+) -> SearchResult<(i32, i32)>
+where
+    H: Heuristic<(i32, i32)>,
+    F: Fn(usize, usize, &Vec<Vec<i32>>) -> bool,
+{
+    jump_point_search_with_observer(start, end, grid, heuristic, is_cell_solid, ())
+}
+
+/// Same as `jump_point_search_with_stats`, but notifies `observer` of every expanded jump
+/// point and of every jump to a cheaper path, see `search::Observer`.
+pub fn jump_point_search_with_observer<H, F, O>(
+    start: (i32, i32),
+    end: (i32, i32),
+    grid: &Vec<Vec<i32>>,
+    heuristic: H,
+    is_cell_solid: F,
+    mut observer: O,
+) -> SearchResult<(i32, i32)>
+where
+    H: Heuristic<(i32, i32)>,
+    F: Fn(usize, usize, &Vec<Vec<i32>>) -> bool,
+    O: Observer<(i32, i32)>,
+{
+    if let Err(error) = check_endpoints(start, end, grid, &is_cell_solid) {
+        return SearchResult::failed(error);
+    }
+    #[cfg(feature = "std")]
+    let started = Instant::now();
+    let jumps = Jumps {
+        grid,
+        is_cell_solid,
+        end,
+    };
+    let mut open_set_heap =
+        BinaryHeap::from([Reverse((heuristic.estimate(start, end), u32::MAX, start))]);
+    let mut closed_set = HashSet::new();
+    let mut came_from = HashMap::new();
+    let mut g_score = HashMap::new();
+    g_score.insert(start, 0);
+    let mut nodes_generated = 1;
+    let mut found = None;
+
+    while let Some(Reverse((f_score, _, current))) = open_set_heap.pop() {
+        if !closed_set.insert(current) {
+            continue;
+        }
+        let g = g_score[&current];
+        observer.on_expand(current, g, f_score);
+        if current == end {
+            found = Some(g);
+            break;
+        }
+
+        let directions = match came_from.get(&current) {
+            None => vec![UP, LEFT, DOWN, RIGHT],
+            Some(&parent) => jumps.directions(parent, current),
+        };
+        for direction in directions {
+            let Some(jump_point) = jumps.jump(current, direction) else {
+                continue;
+            };
+            let tentative_g_score = g + distance(current, jump_point);
+            if g_score
+                .get(&jump_point)
+                .is_some_and(|&known| tentative_g_score >= known)
+            {
+                continue;
+            }
+            observer.on_relax(current, jump_point);
+            came_from.insert(jump_point, current);
+            g_score.insert(jump_point, tentative_g_score);
+            let f_score = tentative_g_score.saturating_add(heuristic.estimate(jump_point, end));
+            // Deeper nodes first among equal f-scores, they are closer to the goal.
+            open_set_heap.push(Reverse((f_score, u32::MAX - tentative_g_score, jump_point)));
+            nodes_generated += 1;
+        }
+    }
+
+    SearchResult {
+        path: found
+            .map(|_| fill_in(&reconstruct_path(&came_from, end)))
+            .ok_or(SearchError::NoPath),
+        cost: found,
+        nodes_expanded: closed_set.len(),
+        nodes_generated,
+        #[cfg(feature = "std")]
+        duration: started.elapsed(),
+        #[cfg(not(feature = "std"))]
+        duration: Duration::ZERO,
+    }
+}
+
+/// The straight jumps over a grid towards `end`.
+struct Jumps<'a, F> {
+    grid: &'a Vec<Vec<i32>>,
+    is_cell_solid: F,
+    end: (i32, i32),
+}
+
+impl<F> Jumps<'_, F>
+where
+    F: Fn(usize, usize, &Vec<Vec<i32>>) -> bool,
+{
+    /// Whether `(row, col)` is inside the grid and not solid.
+    fn is_free(&self, (row, col): (i32, i32)) -> bool {
+        let (Ok(row), Ok(col)) = (usize::try_from(row), usize::try_from(col)) else {
+            return false;
+        };
+        self.grid.get(row).is_some_and(|cells| col < cells.len())
+            && !(self.is_cell_solid)(row, col, self.grid)
+    }
+
+    /// The directions worth jumping in from `node`, reached from `parent`: on
+    /// from a vertical jump and to both sides, on from a horizontal one and
+    /// vertically where an obstacle behind `node` blocked turning earlier.
+    fn directions(&self, parent: (i32, i32), node: (i32, i32)) -> Vec<(i32, i32)> {
+        let direction = ((node.0 - parent.0).signum(), (node.1 - parent.1).signum());
+        if direction.1 == 0 {
+            return vec![direction, LEFT, RIGHT];
+        }
+        let mut directions = vec![direction];
+        for turn in [UP, DOWN] {
+            if self.is_forced(node, direction, turn) {
+                directions.push(turn);
+            }
+        }
+        directions
+    }
+
+    /// Whether a horizontal run going in `direction` must turn `turn` at `node`.
+    fn is_forced(&self, node: (i32, i32), direction: (i32, i32), turn: (i32, i32)) -> bool {
+        self.is_free((node.0 + turn.0, node.1))
+            && !self.is_free((node.0 + turn.0, node.1 - direction.1))
+    }
+
+    /// Walks from `from` in `direction` until a jump point, `None` if an
+    /// obstacle or the edge of the grid comes first.
+    fn jump(&self, from: (i32, i32), direction: (i32, i32)) -> Option<(i32, i32)> {
+        let mut node = from;
+        loop {
+            node = (node.0 + direction.0, node.1 + direction.1);
+            if !self.is_free(node) {
+                return None;
+            }
+            if node == self.end {
+                return Some(node);
+            }
+            let turns = if direction.1 == 0 {
+                self.jump(node, LEFT).is_some() || self.jump(node, RIGHT).is_some()
+            } else {
+                self.is_forced(node, direction, UP) || self.is_forced(node, direction, DOWN)
+            };
+            if turns {
+                return Some(node);
+            }
+        }
+    }
+}
+
+/// The number of steps of a straight jump.
+fn distance(a: (i32, i32), b: (i32, i32)) -> u32 {
+    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+}
+
+/// The path through every cell between the jump points.
+fn fill_in(jump_points: &[(i32, i32)]) -> Path<(i32, i32)> {
+    let mut cells = vec![jump_points[0]];
+    for pair in jump_points.windows(2) {
+        let direction = (
+            (pair[1].0 - pair[0].0).signum(),
+            (pair[1].1 - pair[0].1).signum(),
+        );
+        let mut cell = pair[0];
+        while cell != pair[1] {
+            cell = (cell.0 + direction.0, cell.1 + direction.1);
+            cells.push(cell);
+        }
+    }
+    Path::from_unit_steps(cells)
+}
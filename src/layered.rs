@@ -0,0 +1,228 @@
+//! Grids made of several aligned layers: whether the cells can be entered,
+//! what entering them costs and the zones they belong to.
+
+use alloc::vec::Vec;
+
+use crate::coord::Coord;
+use crate::graph::Graph;
+use crate::grid::Grid;
+use crate::movement::MovementModel;
+
+/// A `width` x `height` map with three layers of the same size:
+///
+/// - the passability, `true` for the cells that can be entered,
+/// - the cost of entering every cell, `1` by default,
+/// - the zone flags, a bit set per cell (`0` by default) of the zones it is
+///   part of, e.g. `1` for water and `2` for enemy territory, that searches
+///   can be made to avoid.
+///
+/// The layers are only reachable through the accessors, so they can't get out
+/// of step with each other. See `LayeredGraph` to search on them.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::grid::Grid;
+/// use pathfinding::layered::LayeredGrid;
+///
+/// let mut map = LayeredGrid::from(&Grid::from_vec(vec![vec![0, 1, 0], vec![0, 0, 0]]));
+/// map.set_cost(1, 1, 5);
+/// map.set_zones(1, 2, 0b10);
+///
+/// assert_eq!(map.passable(0, 1), Some(false));
+/// assert_eq!(map.cost(1, 1), Some(5));
+/// assert_eq!(map.zones(1, 2), Some(0b10));
+/// assert_eq!(map.cost(2, 0), None);
+/// assert_eq!(map.costs().to_vec(), vec![vec![1, 1, 1], vec![1, 5, 1]]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LayeredGrid {
+    passable: Grid<bool>,
+    costs: Grid<u32>,
+    zones: Grid<u32>,
+}
+
+impl LayeredGrid {
+    /// Creates a map where every cell can be entered for `1` and is in no zone.
+    pub fn new(width: usize, height: usize) -> Self {
+        let mut passable = Grid::new(width, height);
+        passable.fill(true);
+        let mut costs = Grid::new(width, height);
+        costs.fill(1);
+        LayeredGrid {
+            passable,
+            costs,
+            zones: Grid::new(width, height),
+        }
+    }
+
+    /// The number of columns.
+    pub fn width(&self) -> usize {
+        self.passable.width
+    }
+
+    /// The number of rows.
+    pub fn height(&self) -> usize {
+        self.passable.height
+    }
+
+    /// Whether the cell at `row`, `col` can be entered, `None` if it is
+    /// outside of the grid.
+    pub fn passable(&self, row: usize, col: usize) -> Option<bool> {
+        self.passable.get(row, col).copied()
+    }
+
+    /// The cost of entering the cell at `row`, `col`.
+    pub fn cost(&self, row: usize, col: usize) -> Option<u32> {
+        self.costs.get(row, col).copied()
+    }
+
+    /// The zone flags of the cell at `row`, `col`.
+    pub fn zones(&self, row: usize, col: usize) -> Option<u32> {
+        self.zones.get(row, col).copied()
+    }
+
+    /// Replaces the passability of the cell at `row`, `col` and returns the old
+    /// one, `None` if it is outside of the grid.
+    pub fn set_passable(&mut self, row: usize, col: usize, passable: bool) -> Option<bool> {
+        self.passable
+            .get_mut(row, col)
+            .map(|cell| core::mem::replace(cell, passable))
+    }
+
+    /// Replaces the cost of the cell at `row`, `col` and returns the old one.
+    ///
+    /// ### Panics
+    ///
+    /// If `cost` is `0`, free steps would make the heuristics overestimate,
+    /// like `CostGrid::set`.
+    pub fn set_cost(&mut self, row: usize, col: usize, cost: u32) -> Option<u32> {
+        assert!(cost > 0, "costs must be at least 1");
+        self.costs
+            .get_mut(row, col)
+            .map(|cell| core::mem::replace(cell, cost))
+    }
+
+    /// Replaces the zone flags of the cell at `row`, `col` and returns the old
+    /// ones.
+    pub fn set_zones(&mut self, row: usize, col: usize, zones: u32) -> Option<u32> {
+        self.zones
+            .get_mut(row, col)
+            .map(|cell| core::mem::replace(cell, zones))
+    }
+
+    /// The passability layer.
+    pub fn passability(&self) -> &Grid<bool> {
+        &self.passable
+    }
+
+    /// The cost layer.
+    pub fn costs(&self) -> &Grid<u32> {
+        &self.costs
+    }
+
+    /// The zone flags layer.
+    pub fn zone_flags(&self) -> &Grid<u32> {
+        &self.zones
+    }
+
+    /// The `(row, col)` of the cells in every zone of `zones`, row after row.
+    pub fn cells_in(&self, zones: u32) -> Vec<(usize, usize)> {
+        self.zones
+            .cells_where(|&flags| flags & zones == zones)
+            .collect()
+    }
+}
+
+/// Walls for the non-zero cells of a `Grid<i32>`, such as the grids of
+/// `astar`, with the default costs and no zones.
+impl From<&Grid<i32>> for LayeredGrid {
+    fn from(grid: &Grid<i32>) -> Self {
+        let mut layered = LayeredGrid::new(grid.width, grid.height);
+        for ((row, col), &cell) in grid.enumerate_cells() {
+            layered.set_passable(row, col, cell == 0);
+        }
+        layered
+    }
+}
+
+/// Adapter exposing a `LayeredGrid` as a `Graph`.
+///
+/// A step into a passable cell outside of the avoided zones costs the cost of
+/// the move (see `MovementModel`, `1` for up/down/left/right by default) times
+/// the cost of the cell.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::layered::{LayeredGraph, LayeredGrid};
+/// use pathfinding::{astar_graph, manhattan_distance};
+///
+/// const WATER: u32 = 1;
+///
+/// let mut map = LayeredGrid::new(3, 2);
+/// map.set_zones(0, 1, WATER);
+/// map.set_cost(1, 1, 4);
+///
+/// // Swimming is the shortest way.
+/// let path = astar_graph(&LayeredGraph::new(&map), (0, 0), (0, 2), manhattan_distance).unwrap();
+/// assert_eq!(path.cost(), 2);
+///
+/// // Staying dry goes through the expensive cell.
+/// let dry = LayeredGraph::new(&map).avoiding(WATER);
+/// let path = astar_graph(&dry, (0, 0), (0, 2), manhattan_distance).unwrap();
+/// assert_eq!(path, vec![(0, 0), (1, 0), (1, 1), (1, 2), (0, 2)]);
+/// assert_eq!(path.cost(), 1 + 4 + 1 + 1);
+/// ```
+pub struct LayeredGraph<'a> {
+    grid: &'a LayeredGrid,
+    movement: MovementModel,
+    avoid: u32,
+}
+
+impl<'a> LayeredGraph<'a> {
+    /// Creates the adapter from a map, moving up/down/left/right and avoiding
+    /// no zone.
+    pub fn new(grid: &'a LayeredGrid) -> Self {
+        LayeredGraph {
+            grid,
+            movement: MovementModel::four_way(),
+            avoid: 0,
+        }
+    }
+
+    /// Replaces the movement model, e.g. to allow diagonal steps.
+    pub fn with_movement(mut self, movement: MovementModel) -> Self {
+        self.movement = movement;
+        self
+    }
+
+    /// Forbids entering the cells having any of the flags of `zones`.
+    pub fn avoiding(mut self, zones: u32) -> Self {
+        self.avoid = zones;
+        self
+    }
+
+    /// The cost of entering `cell`, `None` if it can't be entered.
+    fn entry_cost(&self, cell: (i32, i32)) -> Option<u32> {
+        let (row, col) = Coord::from(cell).to_usize()?;
+        if !self.grid.passable(row, col)? || self.grid.zones(row, col)? & self.avoid != 0 {
+            return None;
+        }
+        self.grid.cost(row, col)
+    }
+}
+
+impl Graph for LayeredGraph<'_> {
+    type Node = (i32, i32);
+
+    fn successors(&self, (row, col): (i32, i32)) -> Vec<((i32, i32), u32)> {
+        self.movement
+            .neighbors_in(row, col, self.grid.height(), self.grid.width(), |r, c| {
+                self.entry_cost((r, c)).is_some()
+            })
+            .into_iter()
+            .filter_map(|(cell, cost)| Some((cell, cost.saturating_mul(self.entry_cost(cell)?))))
+            .collect()
+    }
+}
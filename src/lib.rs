@@ -0,0 +1,71 @@
+//! A* pathfinding on 2D grids and arbitrary graphs.
+//!
+//! The search functions live in `algo` and are re-exported at the crate root,
+//! so `pathfinding::astar` works as well as `pathfinding::algo::astar`. The
+//! egui visualisation is behind the `gui` feature (enabled by default), turn
+//! off the default features to use the algorithms headlessly.
+//!
+//! Without the `std` feature the crate is `no_std` and only needs `alloc`: the
+//! searches, graphs, grids and heuristics are available, the modules doing I/O,
+//! reading the clock, locking or taking square roots (`anytime`, `cache`, `diff`,
+//! `import`, `movingai`, `gui`, `navmesh`) are not.
+//!
+//! The other features are off by default: `image` imports heightmaps and
+//! renders grids to PNG files, `serde` serializes paths, grids and search
+//! results, `json` saves maps to files, `tracing` emits a span for every search
+//! run to completion with progress events every 1024 expansions, and `ffi`
+//! exports the grid search to C (see `include/pathfinding.h`).
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod algo;
+#[cfg(feature = "std")]
+pub mod anytime;
+pub mod bfs;
+pub mod bitgrid;
+pub mod builder;
+#[cfg(feature = "std")]
+pub mod cache;
+pub mod cell;
+pub mod chunked;
+pub mod collections;
+pub mod coord;
+pub mod costgrid;
+#[cfg(feature = "std")]
+pub mod diff;
+pub mod direction;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod graph;
+pub mod grid;
+pub mod grid3;
+#[cfg(feature = "gui")]
+pub mod gui;
+pub mod heuristic;
+pub mod history;
+#[cfg(feature = "std")]
+pub mod import;
+pub mod jps;
+pub mod layered;
+pub mod movement;
+#[cfg(feature = "std")]
+pub mod movingai;
+#[cfg(feature = "std")]
+pub mod navmesh;
+pub mod path;
+pub mod prelude;
+#[cfg(feature = "image")]
+pub mod render;
+pub mod rle;
+#[cfg(feature = "json")]
+pub mod save;
+pub mod scenarios;
+pub mod search;
+pub mod terrain;
+pub mod testing;
+pub mod waypoint;
+pub mod world;
+
+pub use algo::*;
@@ -0,0 +1,286 @@
+//! Movement models: which cells of a grid can be reached from a cell in one step.
+
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+/// A move of a movement model: the `(row, col)` offset and the cost of the step.
+pub type Move = ((i32, i32), u32);
+
+/// Up, left, down, right.
+const FOUR_WAY: &[Move] = &[((-1, 0), 1), ((0, -1), 1), ((1, 0), 1), ((0, 1), 1)];
+
+/// `FOUR_WAY`, then the diagonals.
+const EIGHT_WAY: &[Move] = &[
+    ((-1, 0), 1),
+    ((0, -1), 1),
+    ((1, 0), 1),
+    ((0, 1), 1),
+    ((-1, -1), 1),
+    ((-1, 1), 1),
+    ((1, -1), 1),
+    ((1, 1), 1),
+];
+
+/// The jumps of a chess knight.
+const KNIGHT: &[Move] = &[
+    ((-2, -1), 1),
+    ((-2, 1), 1),
+    ((-1, -2), 1),
+    ((-1, 2), 1),
+    ((1, -2), 1),
+    ((1, 2), 1),
+    ((2, -1), 1),
+    ((2, 1), 1),
+];
+
+/// Whether a diagonal step may pass the corners of solid cells. The two cells
+/// next to a diagonal step are the ones sharing a side with both its start and
+/// its end; outside of the grid counts as solid.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::movement::{CornerCutting, MovementModel};
+///
+/// let grid = vec![
+///     vec![0, 1],
+///     vec![1, 0],
+/// ];
+/// let is_solid = |row: usize, col: usize, grid: &Vec<Vec<i32>>| grid[row][col] == 1;
+/// let squeezes = |policy| {
+///     MovementModel::eight_way()
+///         .with_corner_cutting(policy)
+///         .neighbors(0, 0, &grid, is_solid)
+///         .contains(&((1, 1), 1))
+/// };
+///
+/// assert!(squeezes(CornerCutting::Allowed));
+/// assert!(!squeezes(CornerCutting::IfOneSideOpen));
+/// assert!(!squeezes(CornerCutting::Never));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CornerCutting {
+    /// Diagonal steps ignore the cells next to them, even squeezing between two
+    /// diagonally adjacent obstacles.
+    Allowed,
+    /// At least one of the two cells next to the step must be free, so a step
+    /// may clip one corner but not pass between two obstacles.
+    #[default]
+    IfOneSideOpen,
+    /// Both cells next to the step must be free, nothing is clipped.
+    Never,
+}
+
+/// The neighborhood of a grid cell, i.e. the moves a search may take from it.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::movement::MovementModel;
+///
+/// let grid = vec![
+///     vec![0, 0, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ];
+/// let is_solid = |row: usize, col: usize, grid: &Vec<Vec<i32>>| grid[row][col] == 1;
+///
+/// let neighbors = |model: MovementModel| {
+///     model.neighbors(1, 1, &grid, is_solid).into_iter().map(|(cell, _)| cell).collect::<Vec<_>>()
+/// };
+///
+/// assert_eq!(neighbors(MovementModel::four_way()), vec![(0, 1), (1, 0), (2, 1)]);
+/// assert_eq!(neighbors(MovementModel::eight_way()).len(), 7);
+/// assert_eq!(neighbors(MovementModel::knight()), vec![]);
+///
+/// // A king that pays more for diagonal steps.
+/// let king = MovementModel::custom(vec![
+///     ((-1, 0), 10), ((0, -1), 10), ((1, 0), 10), ((0, 1), 10),
+///     ((-1, -1), 14), ((-1, 1), 14), ((1, -1), 14), ((1, 1), 14),
+/// ]);
+/// assert_eq!(king.neighbors(0, 0, &grid, is_solid), vec![((1, 0), 10), ((0, 1), 10), ((1, 1), 14)]);
+///
+/// // On a wrapping grid the corners are neighbors of the opposite edges.
+/// let torus = MovementModel::four_way().with_wrapping(true);
+/// assert_eq!(neighbors(torus), vec![(0, 1), (1, 0), (2, 1)]);
+/// assert_eq!(
+///     MovementModel::four_way().with_wrapping(true).neighbors(0, 0, &grid, is_solid),
+///     vec![((2, 0), 1), ((0, 2), 1), ((1, 0), 1), ((0, 1), 1)]
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MovementModel {
+    moves: Cow<'static, [Move]>,
+    corner_cutting: CornerCutting,
+    #[cfg_attr(feature = "serde", serde(default))]
+    wrapping: bool,
+}
+
+impl MovementModel {
+    /// Up/down/left/right steps costing `1`, the movement of `astar`.
+    pub fn four_way() -> Self {
+        MovementModel {
+            moves: Cow::Borrowed(FOUR_WAY),
+            corner_cutting: CornerCutting::default(),
+            wrapping: false,
+        }
+    }
+
+    /// The four-way steps plus the diagonals, all costing `1` (so
+    /// `diagonal_distance` is the matching heuristic). Diagonal steps can't
+    /// pass between two obstacles unless allowed, see `with_corner_cutting`.
+    pub fn eight_way() -> Self {
+        MovementModel {
+            moves: Cow::Borrowed(EIGHT_WAY),
+            corner_cutting: CornerCutting::default(),
+            wrapping: false,
+        }
+    }
+
+    /// The L-shaped jumps of a chess knight, costing `1`. Jumps go over solid
+    /// cells, only the landing cell has to be free.
+    pub fn knight() -> Self {
+        MovementModel {
+            moves: Cow::Borrowed(KNIGHT),
+            corner_cutting: CornerCutting::default(),
+            wrapping: false,
+        }
+    }
+
+    /// Any set of moves with their costs, tried in the given order.
+    pub fn custom(moves: Vec<Move>) -> Self {
+        MovementModel {
+            moves: Cow::Owned(moves),
+            corner_cutting: CornerCutting::default(),
+            wrapping: false,
+        }
+    }
+
+    /// Sets the corner-cutting policy of the diagonal steps, `IfOneSideOpen` by default.
+    pub fn with_corner_cutting(mut self, corner_cutting: CornerCutting) -> Self {
+        self.corner_cutting = corner_cutting;
+        self
+    }
+
+    /// Makes the grid wrap around (a torus): moves leaving it on one edge
+    /// enter it again on the opposite one, as on Pac-Man or planet-surface
+    /// maps. Wrapping grids must be rectangular. Off by default.
+    ///
+    /// Plain distances overestimate the paths crossing the edges, wrap the
+    /// heuristic as well with `heuristic::Toroidal`.
+    pub fn with_wrapping(mut self, wrapping: bool) -> Self {
+        self.wrapping = wrapping;
+        self
+    }
+
+    /// The moves of the model.
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// The corner-cutting policy of the diagonal steps.
+    pub fn corner_cutting(&self) -> CornerCutting {
+        self.corner_cutting
+    }
+
+    /// Whether the grid wraps around its edges.
+    pub fn wrapping(&self) -> bool {
+        self.wrapping
+    }
+
+    /// The cells reachable from `row`, `col` in one move, with the cost of the move.
+    ///
+    /// ### Arguments
+    ///
+    /// * `row` - The row of the cell.
+    /// * `col` - The column of the cell.
+    /// * `grid` - The grid (consisting of vector of vectors).
+    /// * `is_solid` - The predicate telling which cells can't be entered.
+    pub fn neighbors<F>(
+        &self,
+        row: i32,
+        col: i32,
+        grid: &Vec<Vec<i32>>,
+        is_solid: F,
+    ) -> Vec<((i32, i32), u32)>
+    where
+        F: Fn(usize, usize, &Vec<Vec<i32>>) -> bool,
+    {
+        let width = grid.first().map_or(0, Vec::len);
+        self.neighbors_in(row, col, grid.len(), width, |r, c| {
+            r >= 0
+                && c >= 0
+                && grid
+                    .get(r as usize)
+                    .is_some_and(|cells| (c as usize) < cells.len())
+                && !is_solid(r as usize, c as usize, grid)
+        })
+    }
+
+    /// Like `neighbors`, for any kind of grid: `is_free(row, col)` tells whether
+    /// a cell exists and can be entered. The grid doesn't wrap around, as its
+    /// size is unknown, see `neighbors_in`.
+    pub fn neighbors_where<F>(&self, row: i32, col: i32, is_free: F) -> Vec<((i32, i32), u32)>
+    where
+        F: Fn(i32, i32) -> bool,
+    {
+        self.steps(row, col, |r, c| is_free(r, c).then_some((r, c)))
+    }
+
+    /// Like `neighbors_where`, on a grid of `height` rows of `width` cells that
+    /// wraps around its edges if the model does. `is_free` is asked about the
+    /// cells as they are after wrapping.
+    pub fn neighbors_in<F>(
+        &self,
+        row: i32,
+        col: i32,
+        height: usize,
+        width: usize,
+        is_free: F,
+    ) -> Vec<((i32, i32), u32)>
+    where
+        F: Fn(i32, i32) -> bool,
+    {
+        let (height, width) = (height as i32, width as i32);
+        if !self.wrapping || height == 0 || width == 0 {
+            return self.neighbors_where(row, col, is_free);
+        }
+        self.steps(row, col, |r, c| {
+            let cell = (r.rem_euclid(height), c.rem_euclid(width));
+            is_free(cell.0, cell.1).then_some(cell)
+        })
+    }
+
+    /// The moves from `row`, `col` whose cells are free, `free_cell(row, col)`
+    /// returning where a free cell lies after wrapping.
+    fn steps<F>(&self, row: i32, col: i32, free_cell: F) -> Vec<((i32, i32), u32)>
+    where
+        F: Fn(i32, i32) -> Option<(i32, i32)>,
+    {
+        let is_free = |r, c| free_cell(r, c).is_some();
+        self.moves
+            .iter()
+            .filter(|&&((dr, dc), _)| {
+                if dr.abs() != 1 || dc.abs() != 1 {
+                    return true;
+                }
+                match self.corner_cutting {
+                    CornerCutting::Allowed => true,
+                    CornerCutting::IfOneSideOpen => {
+                        is_free(row + dr, col) || is_free(row, col + dc)
+                    }
+                    CornerCutting::Never => is_free(row + dr, col) && is_free(row, col + dc),
+                }
+            })
+            .filter_map(|&((dr, dc), cost)| Some((free_cell(row + dr, col + dc)?, cost)))
+            .collect()
+    }
+}
+
+impl Default for MovementModel {
+    fn default() -> Self {
+        MovementModel::four_way()
+    }
+}
@@ -0,0 +1,222 @@
+//! Loading of the MovingAI Labs grid benchmarks: maps (`.map`) and the
+//! scenarios (`.scen`) of start and goal cells run on them.
+//!
+//! A map file starts with its header, then has one line per row:
+//!
+//! ```text
+//! type octile
+//! height 2
+//! width 4
+//! map
+//! ..@.
+//! .T..
+//! ```
+//!
+//! `.`, `G` and `S` (swamp) are free cells, stored as `0`. `@` and `O` (out of
+//! bounds), `T` (trees) and `W` (water) are obstacles, stored as `1`.
+//!
+//! The optimal lengths of the scenarios count diagonal steps as `√2` and don't
+//! allow cutting corners: search with `MovementModel::eight_way` and
+//! `CornerCutting::Never` to reproduce them.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// One query of a scenario file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    /// The group of queries of about the same length this one belongs to.
+    pub bucket: u32,
+    /// The map file, relative to the benchmark directory.
+    pub map: String,
+    /// The width of the map.
+    pub width: usize,
+    /// The height of the map.
+    pub height: usize,
+    /// The start as `(row, col)`, like the nodes of the grid searches.
+    pub start: (i32, i32),
+    /// The goal as `(row, col)`.
+    pub goal: (i32, i32),
+    /// The length of the shortest path.
+    pub optimal_length: f64,
+}
+
+/// Error produced when a benchmark file can't be loaded.
+#[derive(Debug)]
+pub enum MovingAiError {
+    /// The file couldn't be read.
+    Io(io::Error),
+    /// The file is malformed (`line` is 1-based).
+    Parse { line: usize, message: String },
+}
+
+impl fmt::Display for MovingAiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MovingAiError::Io(e) => write!(f, "{}", e),
+            MovingAiError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for MovingAiError {}
+
+impl From<io::Error> for MovingAiError {
+    fn from(e: io::Error) -> Self {
+        MovingAiError::Io(e)
+    }
+}
+
+fn error(line: usize, message: String) -> MovingAiError {
+    MovingAiError::Parse { line, message }
+}
+
+/// Parses a map from the text of a `.map` file (see the module docs).
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::movingai::parse_map;
+///
+/// let grid = parse_map("type octile\nheight 2\nwidth 4\nmap\n..@.\n.T..\n").unwrap();
+///
+/// assert_eq!(grid, vec![vec![0, 0, 1, 0], vec![0, 1, 0, 0]]);
+/// ```
+pub fn parse_map(text: &str) -> Result<Vec<Vec<i32>>, MovingAiError> {
+    let mut lines = text.lines().enumerate();
+    let (mut width, mut height) = (None, None);
+    loop {
+        let Some((index, line)) = lines.next() else {
+            return Err(error(
+                text.lines().count(),
+                "map has no `map` line".to_string(),
+            ));
+        };
+        let mut fields = line.split_whitespace();
+        match (fields.next(), fields.next()) {
+            (Some("map"), None) => break,
+            (Some("type"), Some(_)) | (None, None) => {}
+            (Some(key @ ("height" | "width")), Some(value)) => {
+                let value = value
+                    .parse::<usize>()
+                    .map_err(|e| error(index + 1, format!("invalid {}: {}", key, e)))?;
+                if key == "height" {
+                    height = Some(value);
+                } else {
+                    width = Some(value);
+                }
+            }
+            _ => {
+                return Err(error(
+                    index + 1,
+                    format!("unexpected header line {:?}", line),
+                ))
+            }
+        }
+    }
+    let (Some(width), Some(height)) = (width, height) else {
+        return Err(error(1, "map header lacks its width or height".to_string()));
+    };
+
+    let mut grid = Vec::with_capacity(height);
+    for (index, line) in lines {
+        let line = line.trim_end();
+        if line.is_empty() {
+            continue;
+        }
+        let row = line
+            .chars()
+            .map(|c| match c {
+                '.' | 'G' | 'S' => Ok(0),
+                '@' | 'O' | 'T' | 'W' => Ok(1),
+                other => Err(error(
+                    index + 1,
+                    format!("unexpected map character {:?}", other),
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if row.len() != width {
+            return Err(error(
+                index + 1,
+                format!("row has {} cells, expected {}", row.len(), width),
+            ));
+        }
+        grid.push(row);
+    }
+    if grid.len() != height {
+        return Err(error(
+            text.lines().count(),
+            format!("map has {} rows, expected {}", grid.len(), height),
+        ));
+    }
+    Ok(grid)
+}
+
+/// Parses the queries of a `.scen` file: a `version` line, then one query per
+/// line as tab separated fields (bucket, map, width, height, start x and y,
+/// goal x and y, optimal length). `x` is the column and `y` the row.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::movingai::parse_scen;
+///
+/// let queries = parse_scen("version 1\n0\tmaps/tiny.map\t4\t2\t0\t0\t3\t1\t3.41421356\n").unwrap();
+///
+/// assert_eq!(queries.len(), 1);
+/// assert_eq!(queries[0].map, "maps/tiny.map");
+/// assert_eq!((queries[0].start, queries[0].goal), ((0, 0), (1, 3)));
+/// ```
+pub fn parse_scen(text: &str) -> Result<Vec<Query>, MovingAiError> {
+    let mut queries = vec![];
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with("version") {
+            continue;
+        }
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [bucket, map, width, height, start_x, start_y, goal_x, goal_y, optimal_length] =
+            fields[..]
+        else {
+            return Err(error(
+                index + 1,
+                format!("expected 9 fields, got {}", fields.len()),
+            ));
+        };
+        let number = |field: &str| {
+            field
+                .trim()
+                .parse::<usize>()
+                .map_err(|e| error(index + 1, format!("invalid number {:?}: {}", field, e)))
+        };
+        let cell =
+            |x: &str, y: &str| Ok::<_, MovingAiError>((number(y)? as i32, number(x)? as i32));
+        queries.push(Query {
+            bucket: number(bucket)? as u32,
+            map: map.to_string(),
+            width: number(width)?,
+            height: number(height)?,
+            start: cell(start_x, start_y)?,
+            goal: cell(goal_x, goal_y)?,
+            optimal_length: optimal_length.trim().parse().map_err(|e| {
+                error(
+                    index + 1,
+                    format!("invalid length {:?}: {}", optimal_length, e),
+                )
+            })?,
+        });
+    }
+    Ok(queries)
+}
+
+/// Loads the map of the `.map` file at `path`, see `parse_map`.
+pub fn load_map(path: impl AsRef<Path>) -> Result<Vec<Vec<i32>>, MovingAiError> {
+    parse_map(&fs::read_to_string(path)?)
+}
+
+/// Loads the queries of the `.scen` file at `path`, see `parse_scen`.
+pub fn load_scen(path: impl AsRef<Path>) -> Result<Vec<Query>, MovingAiError> {
+    parse_scen(&fs::read_to_string(path)?)
+}
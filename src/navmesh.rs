@@ -0,0 +1,258 @@
+//! Navigation meshes: searching continuous space, with the walkable area
+//! covered by triangles instead of grid cells.
+//!
+//! The search runs over the triangles, from the one holding the start to the
+//! one holding the goal, then the funnel algorithm pulls the corridor of
+//! triangles taut into the shortest line through it.
+//!
+//! ### Example
+//!
+//! ```
+//! use pathfinding::navmesh::NavMesh;
+//! use pathfinding::search::SearchError;
+//!
+//! // An L-shaped corridor as three rectangles.
+//! let vertices = vec![
+//!     (0.0, 0.0), (8.0, 0.0), (8.0, 2.0), (0.0, 2.0),
+//!     (10.0, 0.0), (10.0, 2.0), (10.0, 10.0), (8.0, 10.0),
+//! ];
+//! let mesh = NavMesh::from_polygons(vertices, &[vec![0, 1, 2, 3], vec![1, 4, 5, 2], vec![2, 5, 6, 7]]);
+//!
+//! // Straight to the inner corner, then straight to the goal.
+//! let path = mesh.find_path((1.0, 1.0), (9.0, 9.0)).unwrap();
+//! assert_eq!(path, vec![(1.0, 1.0), (8.0, 2.0), (9.0, 9.0)]);
+//!
+//! assert_eq!(mesh.find_path((1.0, 1.0), (9.0, 1.0)).unwrap(), vec![(1.0, 1.0), (9.0, 1.0)]);
+//! assert_eq!(mesh.find_path((1.0, 1.0), (1.0, 9.0)), Err(SearchError::GoalOutOfBounds));
+//! ```
+
+use crate::collections::HashMap;
+use crate::graph::Graph;
+use crate::path::Path;
+use crate::search::SearchError;
+
+/// A point of the plane.
+pub type Point = (f32, f32);
+
+pub use crate::graph::COST_SCALE;
+
+/// A mesh of triangles covering the walkable area. Triangles are neighbors
+/// when they share an edge, i.e. both of its vertices; triangles touching
+/// another one in the middle of its edge aren't connected.
+///
+/// As a `Graph` the nodes are the indices of the triangles, and a step costs
+/// the distance between their centroids (see `COST_SCALE`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavMesh {
+    vertices: Vec<Point>,
+    triangles: Vec<[usize; 3]>,
+    neighbors: Vec<[Option<usize>; 3]>,
+}
+
+/// Twice the signed area of the triangle `a`, `b`, `c`, positive when it
+/// turns counterclockwise.
+fn cross(a: Point, b: Point, c: Point) -> f32 {
+    (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+}
+
+fn distance(a: Point, b: Point) -> f32 {
+    ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+}
+
+impl NavMesh {
+    /// Creates a mesh from its vertices and triangles, given as indices of
+    /// three vertices in any winding order.
+    ///
+    /// ### Panics
+    ///
+    /// If a triangle refers to a missing vertex.
+    pub fn new(vertices: Vec<Point>, triangles: Vec<[usize; 3]>) -> Self {
+        // Counterclockwise, so the inside is on the left of every edge.
+        let triangles: Vec<[usize; 3]> = triangles
+            .into_iter()
+            .map(|[a, b, c]| {
+                if cross(vertices[a], vertices[b], vertices[c]) < 0.0 {
+                    [a, c, b]
+                } else {
+                    [a, b, c]
+                }
+            })
+            .collect();
+        let mut edges: HashMap<(usize, usize), Vec<(usize, usize)>> = HashMap::new();
+        for (triangle, corners) in triangles.iter().enumerate() {
+            for side in 0..3 {
+                let (a, b) = (corners[side], corners[(side + 1) % 3]);
+                edges
+                    .entry((a.min(b), a.max(b)))
+                    .or_default()
+                    .push((triangle, side));
+            }
+        }
+        let mut neighbors = vec![[None; 3]; triangles.len()];
+        for sharing in edges.values() {
+            if let [(a, side_a), (b, side_b)] = sharing[..] {
+                neighbors[a][side_a] = Some(b);
+                neighbors[b][side_b] = Some(a);
+            }
+        }
+        NavMesh {
+            vertices,
+            triangles,
+            neighbors,
+        }
+    }
+
+    /// Creates a mesh from convex polygons, each given as the indices of its
+    /// vertices in order around it, by splitting them into triangles.
+    pub fn from_polygons(vertices: Vec<Point>, polygons: &[Vec<usize>]) -> Self {
+        let triangles = polygons
+            .iter()
+            .flat_map(|polygon| {
+                (2..polygon.len()).map(move |i| [polygon[0], polygon[i - 1], polygon[i]])
+            })
+            .collect();
+        NavMesh::new(vertices, triangles)
+    }
+
+    /// The number of triangles.
+    pub fn len(&self) -> usize {
+        self.triangles.len()
+    }
+
+    /// Whether the mesh has no triangles.
+    pub fn is_empty(&self) -> bool {
+        self.triangles.is_empty()
+    }
+
+    /// The corners of `triangle`, counterclockwise.
+    pub fn triangle(&self, triangle: usize) -> [Point; 3] {
+        self.triangles[triangle].map(|vertex| self.vertices[vertex])
+    }
+
+    /// The center of `triangle`.
+    pub fn centroid(&self, triangle: usize) -> Point {
+        let [a, b, c] = self.triangle(triangle);
+        ((a.0 + b.0 + c.0) / 3.0, (a.1 + b.1 + c.1) / 3.0)
+    }
+
+    /// The triangle holding `point`, `None` if it is outside of the mesh.
+    /// Points on an edge belong to either triangle.
+    pub fn locate(&self, point: Point) -> Option<usize> {
+        (0..self.len()).find(|&triangle| {
+            let [a, b, c] = self.triangle(triangle);
+            cross(a, b, point) >= 0.0 && cross(b, c, point) >= 0.0 && cross(c, a, point) >= 0.0
+        })
+    }
+
+    /// The straight-line distance between the centroids of two triangles in
+    /// `COST_SCALE` units, rounded down: the admissible heuristic of the mesh.
+    pub fn centroid_distance(&self, a: usize, b: usize) -> u32 {
+        (distance(self.centroid(a), self.centroid(b)) * COST_SCALE as f32) as u32
+    }
+
+    /// Searches the shortest path from `start` to `end`.
+    ///
+    /// ### Returns
+    ///
+    /// The corners of the path, from `start` to `end`, or
+    /// `SearchError::StartOutOfBounds` / `GoalOutOfBounds` if a point is
+    /// outside of the mesh and `SearchError::NoPath` if the mesh doesn't
+    /// connect them.
+    pub fn find_path(&self, start: Point, end: Point) -> Result<Vec<Point>, SearchError> {
+        let from = self.locate(start).ok_or(SearchError::StartOutOfBounds)?;
+        let to = self.locate(end).ok_or(SearchError::GoalOutOfBounds)?;
+        let corridor = crate::astar_graph(self, from, to, |a, b| self.centroid_distance(a, b))?;
+        Ok(self.funnel(start, end, &corridor))
+    }
+
+    /// The shortest line from `start` to `end` through a corridor of
+    /// neighboring triangles (the simple stupid funnel algorithm).
+    ///
+    /// ### Arguments
+    ///
+    /// * `start` - A point in the first triangle.
+    /// * `end` - A point in the last triangle.
+    /// * `corridor` - The triangles, each one a neighbor of the previous one,
+    ///   e.g. the `Path` found by searching the mesh as a graph.
+    ///
+    /// ### Returns
+    ///
+    /// The corners of the line, `start` and `end` included.
+    ///
+    /// ### Panics
+    ///
+    /// If two consecutive triangles of the corridor aren't neighbors.
+    pub fn funnel(&self, start: Point, end: Point, corridor: &Path<usize>) -> Vec<Point> {
+        // The edges crossed, as seen walking the corridor.
+        let mut portals = vec![(start, start)];
+        for (from, to) in corridor.steps() {
+            let side = (0..3)
+                .find(|&side| self.neighbors[from][side] == Some(to))
+                .expect("the triangles of the corridor are neighbors");
+            let corners = self.triangles[from];
+            let right = self.vertices[corners[side]];
+            let left = self.vertices[corners[(side + 1) % 3]];
+            portals.push((left, right));
+        }
+        portals.push((end, end));
+
+        let mut points = vec![start];
+        let (mut apex, mut left, mut right) = (start, start, start);
+        let (mut left_index, mut right_index) = (0, 0);
+        let mut index = 1;
+        while index < portals.len() {
+            let (next_left, next_right) = portals[index];
+            if cross(apex, right, next_right) >= 0.0 {
+                if apex == right || cross(apex, left, next_right) < 0.0 {
+                    right = next_right;
+                    right_index = index;
+                } else {
+                    // The right side crossed over the left one, which becomes a corner.
+                    let corner = left_index;
+                    apex = left;
+                    points.push(apex);
+                    (left, right) = (apex, apex);
+                    (left_index, right_index) = (corner, corner);
+                    index = corner + 1;
+                    continue;
+                }
+            }
+            if cross(apex, left, next_left) <= 0.0 {
+                if apex == left || cross(apex, right, next_left) > 0.0 {
+                    left = next_left;
+                    left_index = index;
+                } else {
+                    let corner = right_index;
+                    apex = right;
+                    points.push(apex);
+                    (left, right) = (apex, apex);
+                    (left_index, right_index) = (corner, corner);
+                    index = corner + 1;
+                    continue;
+                }
+            }
+            index += 1;
+        }
+        if points.last() != Some(&end) {
+            points.push(end);
+        }
+        points.dedup();
+        points
+    }
+}
+
+impl Graph for NavMesh {
+    type Node = usize;
+
+    fn successors(&self, triangle: usize) -> Vec<(usize, u32)> {
+        let centroid = self.centroid(triangle);
+        self.neighbors[triangle]
+            .iter()
+            .flatten()
+            .map(|&neighbor| {
+                let cost = distance(centroid, self.centroid(neighbor)) * COST_SCALE as f32;
+                (neighbor, cost.ceil() as u32)
+            })
+            .collect()
+    }
+}
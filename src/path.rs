@@ -0,0 +1,348 @@
+//! The path returned by the searches.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Deref;
+
+use crate::cell::Cell;
+use crate::direction::{self, Direction};
+use crate::movement::MovementModel;
+
+/// A path found by a search: the nodes from the start to the end, together with
+/// the cost of reaching each of them.
+///
+/// `Path` dereferences to a slice of its nodes, so `first`, `last`, `iter` and
+/// the other slice methods can be used directly.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::path::Path;
+///
+/// let path = Path::new(vec![(0, 0), (0, 1), (0, 2), (1, 2)], vec![0, 1, 2, 3]);
+///
+/// assert_eq!(path.cost(), 3);
+/// assert_eq!(path.len(), 4);
+/// assert!(path.contains(&(0, 1)));
+/// assert_eq!(path.steps().next(), Some(((0, 0), (0, 1))));
+/// assert_eq!(path.simplify(), vec![(0, 0), (0, 2), (1, 2)]);
+///
+/// let mut path = path;
+/// path.truncate_at_cost(1);
+/// assert_eq!(path, vec![(0, 0), (0, 1)]);
+/// ```
+///
+/// With the `serde` feature paths, `SearchResult`s and the related types can
+/// be serialized, e.g. into save files:
+///
+/// ```
+/// # #[cfg(feature = "serde")]
+/// # {
+/// use pathfinding::path::Path;
+///
+/// let path = Path::from_unit_steps(vec![(0, 0), (0, 1)]);
+/// let json = serde_json::to_string(&path).unwrap();
+///
+/// assert_eq!(json, r#"{"nodes":[[0,0],[0,1]],"costs":[0,1]}"#);
+/// assert_eq!(serde_json::from_str::<Path<(i32, i32)>>(&json).unwrap(), path);
+/// assert!(serde_json::from_str::<Path<(i32, i32)>>(r#"{"nodes":[[0,0]],"costs":[]}"#).is_err());
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "PathParts<N>"))]
+pub struct Path<N> {
+    nodes: Vec<N>,
+    costs: Vec<u32>,
+}
+
+impl<N> Path<N> {
+    /// Creates a path from its nodes and the cost of reaching each node from
+    /// the first one (so `costs[0]` is usually `0`).
+    ///
+    /// ### Panics
+    ///
+    /// If `nodes` and `costs` have different lengths.
+    pub fn new(nodes: Vec<N>, costs: Vec<u32>) -> Self {
+        assert_eq!(nodes.len(), costs.len(), "one cost per node is needed");
+        Path { nodes, costs }
+    }
+
+    /// Creates a path where every step costs `1`.
+    pub fn from_unit_steps(nodes: Vec<N>) -> Self {
+        let costs = (0..nodes.len() as u32).collect();
+        Path { nodes, costs }
+    }
+
+    /// The total cost of the path, `0` for an empty path.
+    pub fn cost(&self) -> u32 {
+        self.costs.last().copied().unwrap_or(0)
+    }
+
+    /// The cost of reaching the node at `index` from the start.
+    pub fn cost_at(&self, index: usize) -> Option<u32> {
+        self.costs.get(index).copied()
+    }
+
+    /// The number of nodes on the path, the start and end included.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Whether the path has no nodes at all.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// The nodes of the path.
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    /// Turns the path back into a plain vector of nodes.
+    pub fn into_vec(self) -> Vec<N> {
+        self.nodes
+    }
+
+    /// Shortens the path to the nodes reachable within `max_cost`, e.g. how far
+    /// an agent gets with the movement points it has left this turn.
+    pub fn truncate_at_cost(&mut self, max_cost: u32) {
+        let keep = self.costs.partition_point(|&cost| cost <= max_cost);
+        self.nodes.truncate(keep);
+        self.costs.truncate(keep);
+    }
+
+    /// Converts every node with `f`, keeping the costs, e.g. from cells to
+    /// the flat indices of a `grid::GridIndex` and back.
+    pub fn map<M, F: FnMut(N) -> M>(self, f: F) -> Path<M> {
+        Path {
+            nodes: self.nodes.into_iter().map(f).collect(),
+            costs: self.costs,
+        }
+    }
+}
+
+impl<N: Copy + PartialEq> Path<N> {
+    /// The moves of the path as `(from, to)` pairs.
+    pub fn steps(&self) -> impl Iterator<Item = (N, N)> + '_ {
+        self.nodes.windows(2).map(|step| (step[0], step[1]))
+    }
+
+    /// Whether `node` is on the path.
+    pub fn contains(&self, node: &N) -> bool {
+        self.nodes.contains(node)
+    }
+}
+
+impl Path<(i32, i32)> {
+    /// The directions of the steps of the path, see `direction::to_directions`.
+    pub fn directions(&self) -> Option<Vec<Direction>> {
+        direction::to_directions(&self.nodes)
+    }
+
+    /// Keeps only the start, the end and the cells where the path turns, so the
+    /// straight runs in between collapse into single waypoints.
+    pub fn simplify(&self) -> Path<(i32, i32)> {
+        let mut nodes = vec![];
+        let mut costs = vec![];
+        for (index, &node) in self.nodes.iter().enumerate() {
+            let turns = match (index.checked_sub(1), self.nodes.get(index + 1)) {
+                (Some(previous), Some(&next)) => {
+                    let previous = self.nodes[previous];
+                    (node.0 - previous.0, node.1 - previous.1) != (next.0 - node.0, next.1 - node.1)
+                }
+                _ => true,
+            };
+            if turns {
+                nodes.push(node);
+                costs.push(self.costs[index]);
+            }
+        }
+        Path { nodes, costs }
+    }
+}
+
+impl<N> Deref for Path<N> {
+    type Target = [N];
+
+    fn deref(&self) -> &[N] {
+        &self.nodes
+    }
+}
+
+impl<N> IntoIterator for Path<N> {
+    type Item = N;
+    type IntoIter = vec::IntoIter<N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.nodes.into_iter()
+    }
+}
+
+impl<'a, N> IntoIterator for &'a Path<N> {
+    type Item = &'a N;
+    type IntoIter = core::slice::Iter<'a, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.nodes.iter()
+    }
+}
+
+impl<N: PartialEq> PartialEq<Vec<N>> for Path<N> {
+    fn eq(&self, other: &Vec<N>) -> bool {
+        self.nodes == *other
+    }
+}
+
+impl<N> From<Path<N>> for Vec<N> {
+    fn from(path: Path<N>) -> Self {
+        path.nodes
+    }
+}
+
+/// What a `Path` is deserialized from, checked before it becomes a `Path`.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct PathParts<N> {
+    nodes: Vec<N>,
+    costs: Vec<u32>,
+}
+
+#[cfg(feature = "serde")]
+impl<N> TryFrom<PathParts<N>> for Path<N> {
+    type Error = &'static str;
+
+    fn try_from(parts: PathParts<N>) -> Result<Self, Self::Error> {
+        if parts.nodes.len() != parts.costs.len() {
+            return Err("a path needs one cost per node");
+        }
+        Ok(Path {
+            nodes: parts.nodes,
+            costs: parts.costs,
+        })
+    }
+}
+
+/// Why `validate_path` rejected a path. `index` is the position of the
+/// offending node in the path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InvalidPath {
+    /// The path has no nodes.
+    Empty,
+    /// The path doesn't begin at the start.
+    WrongStart,
+    /// The path doesn't finish at the end.
+    WrongEnd,
+    /// A node is outside of the grid.
+    OutOfBounds { index: usize },
+    /// A node is on a cell that can't be entered.
+    Blocked { index: usize },
+    /// A node can't be reached from the previous one with a single move.
+    Disconnected { index: usize },
+}
+
+impl fmt::Display for InvalidPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidPath::Empty => write!(f, "path is empty"),
+            InvalidPath::WrongStart => write!(f, "path doesn't begin at the start"),
+            InvalidPath::WrongEnd => write!(f, "path doesn't finish at the end"),
+            InvalidPath::OutOfBounds { index } => {
+                write!(f, "node {} is outside of the grid", index)
+            }
+            InvalidPath::Blocked { index } => write!(f, "node {} is on a blocked cell", index),
+            InvalidPath::Disconnected { index } => {
+                write!(
+                    f,
+                    "node {} isn't one move away from the previous one",
+                    index
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for InvalidPath {}
+
+/// Checks that `path` goes from `start` to `end` through passable cells of
+/// `grid`, one move of `movement` at a time (corner cutting included).
+///
+/// ### Arguments
+///
+/// * `path` - The nodes of the path.
+/// * `grid` - The grid of `Cell`s, for `i32` grids `0` is free.
+/// * `start` - The expected first node.
+/// * `end` - The expected last node.
+/// * `movement` - The moves the path may take.
+///
+/// ### Returns
+///
+/// The cost of the path as `CellGraph` counts it (move cost times the cost of
+/// the entered cell), or why the path is invalid. Compare the cost with
+/// `testing::reference_cost` to check that the path is also the shortest.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::movement::MovementModel;
+/// use pathfinding::path::{validate_path, InvalidPath};
+///
+/// let grid = vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+/// ];
+/// let four_way = MovementModel::four_way();
+///
+/// let path = [(0, 0), (1, 0), (1, 1), (1, 2), (0, 2)];
+/// assert_eq!(validate_path(&path, &grid, (0, 0), (0, 2), &four_way), Ok(4));
+///
+/// let path = [(0, 0), (0, 1), (0, 2)];
+/// assert_eq!(validate_path(&path, &grid, (0, 0), (0, 2), &four_way), Err(InvalidPath::Blocked { index: 1 }));
+///
+/// let path = [(0, 0), (1, 1), (0, 2)];
+/// assert_eq!(validate_path(&path, &grid, (0, 0), (0, 2), &four_way), Err(InvalidPath::Disconnected { index: 1 }));
+/// ```
+pub fn validate_path<C: Cell>(
+    path: &[(i32, i32)],
+    grid: &[Vec<C>],
+    start: (i32, i32),
+    end: (i32, i32),
+    movement: &MovementModel,
+) -> Result<u32, InvalidPath> {
+    let cell_at = |(row, col): (i32, i32)| {
+        grid.get(usize::try_from(row).ok()?)?
+            .get(usize::try_from(col).ok()?)
+    };
+    match (path.first(), path.last()) {
+        (None, _) | (_, None) => return Err(InvalidPath::Empty),
+        (Some(&first), _) if first != start => return Err(InvalidPath::WrongStart),
+        (_, Some(&last)) if last != end => return Err(InvalidPath::WrongEnd),
+        _ => {}
+    }
+    let mut cost: u32 = 0;
+    for (index, &node) in path.iter().enumerate() {
+        match cell_at(node) {
+            None => return Err(InvalidPath::OutOfBounds { index }),
+            Some(cell) if !cell.passable() => return Err(InvalidPath::Blocked { index }),
+            Some(_) => {}
+        }
+        if index == 0 {
+            continue;
+        }
+        let previous = path[index - 1];
+        let width = grid.first().map_or(0, Vec::len);
+        let step = movement
+            .neighbors_in(previous.0, previous.1, grid.len(), width, |r, c| {
+                cell_at((r, c)).is_some_and(Cell::passable)
+            })
+            .into_iter()
+            .filter(|&(neighbor, _)| neighbor == node)
+            .map(|(_, step)| step)
+            .min()
+            .ok_or(InvalidPath::Disconnected { index })?;
+        let entered = cell_at(node).map_or(1, Cell::cost);
+        cost = cost.saturating_add(step.saturating_mul(entered));
+    }
+    Ok(cost)
+}
@@ -0,0 +1,42 @@
+//! The commonly used items of the crate, to get started with one import.
+//!
+//! ### Example
+//!
+//! ```
+//! use pathfinding::prelude::*;
+//!
+//! let grid = vec![vec![0; 4]; 4];
+//! let path = astar((0, 0), (3, 3), &grid, Manhattan, |row, col, grid| grid[row][col] == 1);
+//!
+//! assert_eq!(path.map(|path| path.cost()), Ok(6));
+//! ```
+
+pub use crate::bfs::{breadth_first, breadth_first_with_stats};
+pub use crate::bitgrid::BitGrid;
+pub use crate::builder::AstarBuilder;
+pub use crate::cell::Cell;
+pub use crate::coord::Coord;
+pub use crate::costgrid::CostGrid;
+pub use crate::direction::Direction;
+pub use crate::graph::{CellGraph, Graph, Grid3Graph, GridGraph, WeightedGridGraph, IMPASSABLE};
+pub use crate::grid::{ascii_walls, Grid, GridIndex, GridView, GridViewport, Rect, Symmetry};
+pub use crate::grid3::{Connectivity, Grid3, Voxel};
+pub use crate::heuristic::{
+    Diagonal, Dynamic, Euclidean, Heuristic, Manhattan, Max, Octile, Scaled, Zero,
+};
+pub use crate::jps::{jump_point_search, jump_point_search_with_stats};
+pub use crate::layered::{LayeredGraph, LayeredGrid};
+pub use crate::movement::{CornerCutting, MovementModel};
+pub use crate::path::{validate_path, InvalidPath, Path};
+pub use crate::search::{
+    AbortReason, Observer, SearchError, SearchLimits, SearchResult, SearchState, Searcher, Step,
+    TieBreak,
+};
+pub use crate::terrain::{CostTable, Terrain};
+pub use crate::world::{WorldGraph, WorldGrid};
+pub use crate::{
+    astar, astar_3d, astar_best_effort, astar_cells, astar_clearance, astar_graph,
+    astar_graph_best_effort, astar_graph_with_limits, astar_graph_with_observer,
+    astar_graph_with_stats, astar_weighted, astar_with_cost, astar_with_limits, astar_with_stats,
+    astar_world, diagonal_distance, manhattan_distance,
+};
@@ -0,0 +1,176 @@
+//! Headless rendering of grids and paths to images, e.g. for documentation
+//! and bug reports. The colors are the ones of the GUI: obstacles (cells
+//! holding `1`) black, other cells white, the start green and the end red.
+//!
+//! ### Example
+//!
+//! ```
+//! use pathfinding::render::Render;
+//! use pathfinding::{astar, manhattan_distance};
+//!
+//! let grid = vec![vec![0, 1, 0], vec![0, 0, 0]];
+//! let path = astar((0, 0), (0, 2), &grid, manhattan_distance, |row, col, grid| grid[row][col] == 1).unwrap();
+//!
+//! let image = Render::new(&grid).cell_size(4).start((0, 0)).end((0, 2)).path(&path).to_image();
+//!
+//! assert_eq!(image.dimensions(), (12, 8));
+//! assert_eq!(image.get_pixel(5, 1).0, [0, 0, 0]);
+//! assert_eq!(image.get_pixel(1, 1).0, [0, 255, 0]);
+//! // The middle of the path cells, their borders stay white.
+//! assert_eq!(image.get_pixel(5, 5).0, [0, 0, 255]);
+//! assert_eq!(image.get_pixel(4, 4).0, [255, 255, 255]);
+//!
+//! Render::new(&grid).path(&path).save(std::env::temp_dir().join("pathfinding-render-doc.png")).unwrap();
+//! ```
+
+use std::path::Path;
+
+use image::{Rgb, RgbImage};
+
+const FREE: Rgb<u8> = Rgb([255, 255, 255]);
+const OBSTACLE: Rgb<u8> = Rgb([0, 0, 0]);
+const START: Rgb<u8> = Rgb([0, 255, 0]);
+const END: Rgb<u8> = Rgb([255, 0, 0]);
+/// The colors of the paths, in the order they are added, starting over after
+/// the last one.
+const PATHS: [Rgb<u8>; 4] = [
+    Rgb([0, 0, 255]),
+    Rgb([255, 140, 0]),
+    Rgb([160, 32, 240]),
+    Rgb([0, 170, 170]),
+];
+
+/// A drawing of a grid with the endpoints and the paths of searches on it.
+///
+/// Every cell is a square of `cell_size` pixels. The paths are drawn as
+/// smaller squares in the middle of their cells, each one inside of the
+/// previous one, so overlapping paths stay visible.
+pub struct Render<'a> {
+    grid: &'a [Vec<i32>],
+    cell_size: u32,
+    start: Option<(i32, i32)>,
+    end: Option<(i32, i32)>,
+    shades: Vec<((i32, i32), Rgb<u8>)>,
+    paths: Vec<&'a [(i32, i32)]>,
+}
+
+impl<'a> Render<'a> {
+    /// A drawing of `grid` alone, with cells of 8 pixels.
+    pub fn new(grid: &'a [Vec<i32>]) -> Self {
+        Render {
+            grid,
+            cell_size: 8,
+            start: None,
+            end: None,
+            shades: vec![],
+            paths: vec![],
+        }
+    }
+
+    /// Sets the size of the cells, in pixels.
+    pub fn cell_size(mut self, cell_size: u32) -> Self {
+        self.cell_size = cell_size;
+        self
+    }
+
+    /// Marks the start cell.
+    pub fn start(mut self, start: (i32, i32)) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    /// Marks the end cell.
+    pub fn end(mut self, end: (i32, i32)) -> Self {
+        self.end = Some(end);
+        self
+    }
+
+    /// Colors cells over the grid, under the endpoints and the paths, e.g.
+    /// the cells explored by a search.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use image::Rgb;
+    /// use pathfinding::render::Render;
+    ///
+    /// let grid = vec![vec![0, 0, 0]];
+    /// let shaded = [((0, 0), Rgb([1, 2, 3])), ((0, 1), Rgb([4, 5, 6]))];
+    /// let image = Render::new(&grid).cell_size(1).start((0, 0)).shade(shaded).to_image();
+    ///
+    /// assert_eq!(image.get_pixel(0, 0).0, [0, 255, 0]);
+    /// assert_eq!(image.get_pixel(1, 0).0, [4, 5, 6]);
+    /// assert_eq!(image.get_pixel(2, 0).0, [255, 255, 255]);
+    /// ```
+    pub fn shade(mut self, cells: impl IntoIterator<Item = ((i32, i32), Rgb<u8>)>) -> Self {
+        self.shades.extend(cells);
+        self
+    }
+
+    /// Adds a path to draw, over the ones added before.
+    pub fn path(mut self, path: &'a [(i32, i32)]) -> Self {
+        self.paths.push(path);
+        self
+    }
+
+    /// Fills the square of `size` pixels in the middle of `cell`, if the cell
+    /// is in the grid.
+    fn fill(&self, image: &mut RgbImage, (row, col): (i32, i32), size: u32, color: Rgb<u8>) {
+        let (Ok(row), Ok(col)) = (u32::try_from(row), u32::try_from(col)) else {
+            return;
+        };
+        let inset = (self.cell_size - size) / 2;
+        let (x, y) = (col * self.cell_size + inset, row * self.cell_size + inset);
+        if x + size > image.width() || y + size > image.height() {
+            return;
+        }
+        for dy in 0..size {
+            for dx in 0..size {
+                image.put_pixel(x + dx, y + dy, color);
+            }
+        }
+    }
+
+    /// Draws the grid into an image.
+    pub fn to_image(&self) -> RgbImage {
+        let width = self.grid.first().map_or(0, Vec::len) as u32;
+        let height = self.grid.len() as u32;
+        let mut image = RgbImage::from_pixel(width * self.cell_size, height * self.cell_size, FREE);
+        for (row, cells) in self.grid.iter().enumerate() {
+            for (col, &cell) in cells.iter().enumerate() {
+                if cell == 1 {
+                    self.fill(
+                        &mut image,
+                        (row as i32, col as i32),
+                        self.cell_size,
+                        OBSTACLE,
+                    );
+                }
+            }
+        }
+        for &(cell, color) in &self.shades {
+            self.fill(&mut image, cell, self.cell_size, color);
+        }
+        for (cell, color) in [(self.start, START), (self.end, END)] {
+            if let Some(cell) = cell {
+                self.fill(&mut image, cell, self.cell_size, color);
+            }
+        }
+        let count = self.paths.len() as u32;
+        for (index, path) in self.paths.iter().enumerate() {
+            let size = self.cell_size * (count - index as u32) / (count + 1);
+            for &cell in path.iter() {
+                if Some(cell) != self.start && Some(cell) != self.end {
+                    self.fill(&mut image, cell, size, PATHS[index % PATHS.len()]);
+                }
+            }
+        }
+        image
+    }
+
+    /// Draws the grid into a PNG file at `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), image::ImageError> {
+        self.to_image()
+            .save_with_format(path, image::ImageFormat::Png)
+    }
+}
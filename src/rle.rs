@@ -0,0 +1,211 @@
+//! Run-length encoded grids, for huge maps made of a few long stretches of
+//! the same cell, e.g. open fields with sparse obstacles.
+//!
+//! With the `serde` feature an `RleGrid` serializes as its size and its runs,
+//! each one the value of its cells and the index (row after row) where it
+//! ends, so the files stay as small as the grid in memory:
+//!
+//! ```
+//! # #[cfg(feature = "serde")]
+//! # {
+//! use pathfinding::grid::Grid;
+//! use pathfinding::rle::RleGrid;
+//!
+//! let grid = Grid::from_vec(vec![vec![0, 0, 0, 0], vec![0, 1, 0, 0]]);
+//! let json = serde_json::to_string(&RleGrid::from(&grid)).unwrap();
+//!
+//! assert_eq!(json, r#"{"width":4,"height":2,"runs":[[0,5],[1,6],[0,8]]}"#);
+//! assert_eq!(serde_json::from_str::<RleGrid<i32>>(&json).unwrap().to_grid(), grid);
+//! assert!(serde_json::from_str::<RleGrid<i32>>(r#"{"width":4,"height":2,"runs":[[0,5]]}"#).is_err());
+//! # }
+//! ```
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::grid::{Grid, MemoryUsage};
+
+/// A `width` x `height` grid stored as runs of equal cells, row after row.
+/// Reading a cell takes a binary search through the runs, setting one splits
+/// or merges runs as needed.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::grid::Grid;
+/// use pathfinding::rle::RleGrid;
+///
+/// let mut grid = Grid::new(1000, 1000);
+/// grid.set(500, 500, 1);
+/// let mut rle = RleGrid::from(&grid);
+///
+/// assert_eq!(rle.run_count(), 3);
+/// assert_eq!(rle.get(500, 500), Some(&1));
+/// assert_eq!(rle.get(0, 999), Some(&0));
+/// assert_eq!(rle.get(1000, 0), None);
+///
+/// rle.set(500, 500, 0);
+/// assert_eq!(rle.run_count(), 1);
+/// rle.set(0, 0, 1);
+/// assert_eq!(rle.run_count(), 2);
+/// assert_eq!(rle.to_grid()[(0, 0)], 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "RleParts<T>",
+        bound(deserialize = "T: serde::Deserialize<'de> + PartialEq")
+    )
+)]
+pub struct RleGrid<T> {
+    /// The number of columns.
+    pub width: usize,
+    /// The number of rows.
+    pub height: usize,
+    /// The value of every run and the index of the cell after it, increasing
+    /// up to `width * height`.
+    runs: Vec<(T, usize)>,
+}
+
+/// What an `RleGrid` is deserialized from, checked before it becomes one.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RleParts<T> {
+    width: usize,
+    height: usize,
+    runs: Vec<(T, usize)>,
+}
+
+#[cfg(feature = "serde")]
+impl<T: PartialEq> TryFrom<RleParts<T>> for RleGrid<T> {
+    type Error = &'static str;
+
+    fn try_from(parts: RleParts<T>) -> Result<Self, Self::Error> {
+        if parts.width.checked_mul(parts.height) != Some(parts.runs.last().map_or(0, |run| run.1)) {
+            return Err("the runs of a grid must end at width * height");
+        }
+        let mut start = 0;
+        for (index, (value, end)) in parts.runs.iter().enumerate() {
+            if *end <= start || index > 0 && *value == parts.runs[index - 1].0 {
+                return Err(
+                    "the runs of a grid must be non-empty and differ from the previous one",
+                );
+            }
+            start = *end;
+        }
+        Ok(RleGrid {
+            width: parts.width,
+            height: parts.height,
+            runs: parts.runs,
+        })
+    }
+}
+
+impl<T: Clone + PartialEq> RleGrid<T> {
+    /// Creates a grid with every cell holding `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        let cells = width * height;
+        RleGrid {
+            width,
+            height,
+            runs: if cells > 0 {
+                vec![(value, cells)]
+            } else {
+                vec![]
+            },
+        }
+    }
+
+    /// The run holding the cell `index`.
+    fn run(&self, index: usize) -> usize {
+        self.runs.partition_point(|&(_, end)| end <= index)
+    }
+
+    /// The cell at `row`, `col`, `None` if it is outside of the grid.
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        if row < self.height && col < self.width {
+            Some(&self.runs[self.run(row * self.width + col)].0)
+        } else {
+            None
+        }
+    }
+
+    /// Replaces the cell at `row`, `col` and returns the old value, `None` if
+    /// it is outside of the grid.
+    pub fn set(&mut self, row: usize, col: usize, value: T) -> Option<T> {
+        if row >= self.height || col >= self.width {
+            return None;
+        }
+        let index = row * self.width + col;
+        let run = self.run(index);
+        let (old, end) = self.runs[run].clone();
+        if old == value {
+            return Some(old);
+        }
+        let start = if run == 0 { 0 } else { self.runs[run - 1].1 };
+        // The run is cut around the cell, which then joins its neighbors if
+        // they hold the same value.
+        let mut pieces = Vec::with_capacity(3);
+        if start < index {
+            pieces.push((old.clone(), index));
+        }
+        let cell = run + pieces.len();
+        pieces.push((value, index + 1));
+        if index + 1 < end {
+            pieces.push((old.clone(), end));
+        }
+        self.runs.splice(run..=run, pieces);
+        if cell + 1 < self.runs.len() && self.runs[cell + 1].0 == self.runs[cell].0 {
+            self.runs[cell].1 = self.runs.remove(cell + 1).1;
+        }
+        if cell > 0 && self.runs[cell - 1].0 == self.runs[cell].0 {
+            self.runs[cell - 1].1 = self.runs.remove(cell).1;
+        }
+        Some(old)
+    }
+
+    /// The number of runs, the size of the grid in memory.
+    pub fn run_count(&self) -> usize {
+        self.runs.len()
+    }
+
+    /// How much memory the grid takes, see `Grid::memory_usage`.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage {
+            cells: self.width * self.height,
+            bytes: core::mem::size_of::<Self>()
+                + self.runs.capacity() * core::mem::size_of::<(T, usize)>(),
+            chunks: 0,
+        }
+    }
+
+    /// Expands the grid into a `Grid` of its cells.
+    pub fn to_grid(&self) -> Grid<T> {
+        let mut run = 0;
+        Grid::from_fn(self.width, self.height, |row, col| {
+            if row * self.width + col == self.runs[run].1 {
+                run += 1;
+            }
+            self.runs[run].0.clone()
+        })
+    }
+}
+
+impl<T: Clone + PartialEq> From<&Grid<T>> for RleGrid<T> {
+    fn from(grid: &Grid<T>) -> Self {
+        let mut runs: Vec<(T, usize)> = Vec::new();
+        for (index, cell) in grid.iter().enumerate() {
+            match runs.last_mut() {
+                Some(run) if run.0 == *cell => run.1 = index + 1,
+                _ => runs.push((cell.clone(), index + 1)),
+            }
+        }
+        RleGrid {
+            width: grid.width,
+            height: grid.height,
+            runs,
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! Saving maps to JSON files and loading them back.
+//!
+//! A map file holds a format version, the size and the cells of the grid (row
+//! after row) and optionally the start and end of a search:
+//!
+//! ```text
+//! {"version":1,"width":3,"height":1,"cells":[0,1,0],"start":[0,0],"end":null}
+//! ```
+//!
+//! ### Example
+//!
+//! ```
+//! use pathfinding::grid::Grid;
+//! use pathfinding::save::MapFile;
+//!
+//! let path = std::env::temp_dir().join("pathfinding-save-example.json");
+//! let map = MapFile {
+//!     grid: Grid::from_vec(vec![vec![0, 1, 0], vec![0, 0, 0]]),
+//!     start: Some((0, 0)),
+//!     end: Some((0, 2)),
+//! };
+//! map.save(&path).unwrap();
+//!
+//! assert_eq!(MapFile::<i32>::load(&path).unwrap(), map);
+//! assert_eq!(Grid::<i32>::load(&path).unwrap(), map.grid);
+//! # std::fs::remove_file(&path).unwrap();
+//! ```
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::grid::Grid;
+
+/// The version of the format written by `save`, the only one `load` reads.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// A grid with the endpoints of a search on it, as saved in a map file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MapFile<T> {
+    pub grid: Grid<T>,
+    pub start: Option<(i32, i32)>,
+    pub end: Option<(i32, i32)>,
+}
+
+/// Error produced when a map file can't be saved or loaded.
+#[derive(Debug)]
+pub enum MapFileError {
+    /// The file couldn't be read or written.
+    Io(io::Error),
+    /// The file isn't a valid map file.
+    Format(serde_json::Error),
+    /// The file was written in another version of the format.
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for MapFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MapFileError::Io(e) => write!(f, "{}", e),
+            MapFileError::Format(e) => write!(f, "invalid map file: {}", e),
+            MapFileError::UnsupportedVersion(version) => write!(
+                f,
+                "map file version {} is not supported, expected {}",
+                version, FORMAT_VERSION
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MapFileError {}
+
+impl From<io::Error> for MapFileError {
+    fn from(e: io::Error) -> Self {
+        MapFileError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for MapFileError {
+    fn from(e: serde_json::Error) -> Self {
+        MapFileError::Format(e)
+    }
+}
+
+/// What is written to a map file, borrowing the grid.
+#[derive(Serialize)]
+struct Saved<'a, T> {
+    version: u32,
+    #[serde(flatten)]
+    grid: &'a Grid<T>,
+    start: Option<(i32, i32)>,
+    end: Option<(i32, i32)>,
+}
+
+/// What is read from a map file, once its version is known to be supported.
+#[derive(Deserialize)]
+struct Loaded<T> {
+    #[serde(flatten)]
+    grid: Grid<T>,
+    start: Option<(i32, i32)>,
+    end: Option<(i32, i32)>,
+}
+
+#[derive(Deserialize)]
+struct Version {
+    version: u32,
+}
+
+fn write<T: Serialize>(path: &Path, saved: &Saved<'_, T>) -> Result<(), MapFileError> {
+    fs::write(path, serde_json::to_string(saved)?)?;
+    Ok(())
+}
+
+fn read<T: DeserializeOwned>(path: &Path) -> Result<Loaded<T>, MapFileError> {
+    let text = fs::read_to_string(path)?;
+    let Version { version } = serde_json::from_str(&text)?;
+    if version != FORMAT_VERSION {
+        return Err(MapFileError::UnsupportedVersion(version));
+    }
+    Ok(serde_json::from_str(&text)?)
+}
+
+impl<T: Serialize> MapFile<T> {
+    /// Writes the map to the file at `path`, replacing it if it exists.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), MapFileError> {
+        let saved = Saved {
+            version: FORMAT_VERSION,
+            grid: &self.grid,
+            start: self.start,
+            end: self.end,
+        };
+        write(path.as_ref(), &saved)
+    }
+}
+
+impl<T: DeserializeOwned> MapFile<T> {
+    /// Reads the map file at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, MapFileError> {
+        let Loaded { grid, start, end } = read(path.as_ref())?;
+        Ok(MapFile { grid, start, end })
+    }
+}
+
+impl<T: Serialize> Grid<T> {
+    /// Writes the grid to a map file at `path`, without endpoints. See
+    /// `MapFile` to save them too.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), MapFileError> {
+        let saved = Saved {
+            version: FORMAT_VERSION,
+            grid: self,
+            start: None,
+            end: None,
+        };
+        write(path.as_ref(), &saved)
+    }
+}
+
+impl<T: DeserializeOwned> Grid<T> {
+    /// Reads the grid of the map file at `path`, ignoring its endpoints.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, MapFileError> {
+        read(path.as_ref()).map(|loaded| loaded.grid)
+    }
+}
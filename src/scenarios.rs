@@ -0,0 +1,168 @@
+//! A few curated maps to try the searches on, by name: the benches run them
+//! and the visualisation offers them as presets.
+//!
+//! Every map is 21 columns by 13 rows in the `astar` convention (`0` free,
+//! `1` wall), with a start (`S`) and an end (`E`) marker.
+//!
+//! ### Example
+//!
+//! ```
+//! use pathfinding::scenarios::Preset;
+//! use pathfinding::{astar, manhattan_distance};
+//!
+//! for preset in Preset::ALL {
+//!     assert_eq!(Preset::from_name(preset.name()), Some(preset));
+//!     let (start, end) = preset.endpoints();
+//!     let grid = preset.grid().to_vec();
+//!     let path = astar(start, end, &grid, manhattan_distance, |row, col, grid| grid[row][col] == 1);
+//!     assert!(path.is_ok(), "no path on {}", preset.name());
+//! }
+//! ```
+
+use crate::grid::{ascii_walls, AsciiMap, Grid};
+
+/// A bundled map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Preset {
+    /// A perfect maze: a single path between any two cells.
+    Maze,
+    /// Six rooms joined by narrow doors.
+    Rooms,
+    /// A wide field with a few scattered rocks.
+    OpenField,
+    /// A corridor winding to the center; the straight line is the worst guess.
+    Spiral,
+    /// Two halves joined by a single gap.
+    Chokepoint,
+}
+
+const MAZE: &str = "
+    S........#.....#.....
+    ########.#.#.###.#.#.
+    .....#...#.#.#...#.#.
+    .#.###.###.###.###.##
+    .#.....#...#...#.#...
+    .#########.#.###.###.
+    ...#.......#.....#...
+    ##.#####.#####.###.#.
+    .#.#...#.......#...#.
+    .#.#.#.#####.###.###.
+    ...#.#.#...#...#...#.
+    .###.#.#.#.#######.#.
+    .....#...#.........#E
+";
+
+const ROOMS: &str = "
+    S......#......#......
+    .......#......#......
+    .......#.............
+    ..............#......
+    .......#......#......
+    .......#......#......
+    ###.#######.#####.###
+    .......#......#......
+    .......#......#......
+    ..............#......
+    .......#.............
+    .......#......#......
+    .......#......#.....E
+";
+
+const OPEN_FIELD: &str = "
+    S....................
+    .....................
+    ...##.........#......
+    ...##................
+    ..........###........
+    .......#.............
+    .....................
+    ..#..........##......
+    .............##...#..
+    .....#...............
+    .........#...........
+    .....................
+    ....................E
+";
+
+const SPIRAL: &str = "
+    S....................
+    ####################.
+    ...................#.
+    .#################.#.
+    .#...............#.#.
+    .#.#############.#.#.
+    .#.#..........E#.#.#.
+    .#.#.###########.#.#.
+    .#.#.............#.#.
+    .#.###############.#.
+    .#.................#.
+    .###################.
+    .....................
+";
+
+const CHOKEPOINT: &str = "
+    S.........#..........
+    ..........#..........
+    ....#.....#......#...
+    ..........#..........
+    ..........#....#.....
+    ..#.......#..........
+    .....................
+    ..........#......#...
+    ......#...#..........
+    ..........#..........
+    ...#......#.....#....
+    ..........#..........
+    ..........#.........E
+";
+
+impl Preset {
+    /// Every preset, in the order they are listed in the visualisation.
+    pub const ALL: [Preset; 5] = [
+        Preset::Maze,
+        Preset::Rooms,
+        Preset::OpenField,
+        Preset::Spiral,
+        Preset::Chokepoint,
+    ];
+
+    /// The name of the preset, e.g. `"open-field"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Preset::Maze => "maze",
+            Preset::Rooms => "rooms",
+            Preset::OpenField => "open-field",
+            Preset::Spiral => "spiral",
+            Preset::Chokepoint => "chokepoint",
+        }
+    }
+
+    /// The preset called `name`, `None` if there is none.
+    pub fn from_name(name: &str) -> Option<Preset> {
+        Preset::ALL.into_iter().find(|preset| preset.name() == name)
+    }
+
+    /// The map, with its `S` and `E` markers.
+    pub fn map(self) -> AsciiMap<i32> {
+        let text = match self {
+            Preset::Maze => MAZE,
+            Preset::Rooms => ROOMS,
+            Preset::OpenField => OPEN_FIELD,
+            Preset::Spiral => SPIRAL,
+            Preset::Chokepoint => CHOKEPOINT,
+        };
+        Grid::from_ascii(text, ascii_walls).expect("the bundled maps are valid")
+    }
+
+    /// The grid of the map.
+    pub fn grid(self) -> Grid<i32> {
+        self.map().grid
+    }
+
+    /// The start and the end of the map.
+    pub fn endpoints(self) -> ((i32, i32), (i32, i32)) {
+        let map = self.map();
+        let marker = |marker| map.marker(marker).expect("the bundled maps are marked");
+        (marker('S'), marker('E'))
+    }
+}
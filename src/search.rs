@@ -0,0 +1,823 @@
+use alloc::collections::BinaryHeap;
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::Hash;
+use core::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use crate::collections::{HashMap, HashSet};
+
+use crate::graph::Graph;
+use crate::heuristic::Heuristic;
+use crate::path::Path;
+use crate::reconstruct_path;
+
+/// The outcome of a search together with statistics about how it went.
+///
+/// Returned by `astar_with_stats` and `astar_graph_with_stats`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SearchResult<N> {
+    /// The nodes from start to end, or why there is no such path.
+    pub path: Result<Path<N>, SearchError>,
+    /// The sum of the step costs along the path, `None` if there is no path.
+    pub cost: Option<u32>,
+    /// The number of nodes taken out of the open set and expanded.
+    pub nodes_expanded: usize,
+    /// The number of nodes pushed to the open set (the start included).
+    pub nodes_generated: usize,
+    /// The wall-clock time the search took, always zero without the `std`
+    /// feature as there is no clock to read.
+    pub duration: Duration,
+}
+
+impl<N> SearchResult<N> {
+    /// The result of a search that couldn't even start.
+    pub(crate) fn failed(error: SearchError) -> Self {
+        SearchResult {
+            path: Err(error),
+            cost: None,
+            nodes_expanded: 0,
+            nodes_generated: 0,
+            duration: Duration::ZERO,
+        }
+    }
+}
+
+/// Why a search didn't produce a path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SearchError {
+    /// The start position is outside of the grid.
+    StartOutOfBounds,
+    /// The goal position is outside of the grid.
+    GoalOutOfBounds,
+    /// The start position is on a solid cell.
+    StartBlocked,
+    /// The goal position is on a solid cell.
+    GoalBlocked,
+    /// The whole reachable area was explored without finding the goal.
+    NoPath,
+    /// Everything within `SearchLimits::radius` was explored without finding
+    /// the goal, it may still be reachable by a longer path.
+    NoPathWithinRadius,
+    /// The search was stopped before it could finish, see `SearchLimits`.
+    Aborted(AbortReason),
+}
+
+/// Why a search was stopped early.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AbortReason {
+    /// `SearchLimits::max_expanded` nodes were expanded.
+    NodeLimit,
+    /// The `SearchLimits::cancel` flag was raised.
+    Cancelled,
+    /// The `SearchLimits::deadline` passed.
+    Deadline,
+}
+
+/// Bounds on how much work a search may do.
+///
+/// ### Example
+///
+/// ```
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::sync::Arc;
+///
+/// use pathfinding::astar_with_limits;
+/// use pathfinding::manhattan_distance;
+/// use pathfinding::search::{AbortReason, SearchError, SearchLimits};
+///
+/// let grid = vec![vec![0; 50]; 50];
+/// let is_solid = |row: usize, col: usize, grid: &Vec<Vec<i32>>| grid[row][col] == 1;
+///
+/// let limits = SearchLimits { max_expanded: Some(10), ..Default::default() };
+/// let result = astar_with_limits((0, 0), (49, 49), &grid, manhattan_distance, is_solid, limits);
+/// assert_eq!(result.path, Err(SearchError::Aborted(AbortReason::NodeLimit)));
+/// assert_eq!(result.nodes_expanded, 10);
+///
+/// // The flag would usually be raised from another thread, e.g. the UI.
+/// let cancel = Arc::new(AtomicBool::new(false));
+/// cancel.store(true, Ordering::Relaxed);
+/// let limits = SearchLimits { cancel: Some(cancel), ..Default::default() };
+/// let result = astar_with_limits((0, 0), (49, 49), &grid, manhattan_distance, is_solid, limits);
+/// assert_eq!(result.path, Err(SearchError::Aborted(AbortReason::Cancelled)));
+///
+/// // Is the corner within 5 moves? Only the cells up to 5 moves away are explored.
+/// let limits = SearchLimits { radius: Some(5), ..Default::default() };
+/// let result = astar_with_limits((0, 0), (49, 49), &grid, manhattan_distance, is_solid, limits.clone());
+/// assert_eq!(result.path, Err(SearchError::NoPathWithinRadius));
+/// assert_eq!(result.nodes_expanded, 21);
+/// let result = astar_with_limits((0, 0), (2, 3), &grid, manhattan_distance, is_solid, limits);
+/// assert_eq!(result.cost, Some(5));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SearchLimits {
+    /// The maximum number of nodes to expand, unlimited if `None`.
+    pub max_expanded: Option<usize>,
+    /// A flag checked before every expansion, the search stops once it is `true`.
+    pub cancel: Option<Arc<AtomicBool>>,
+    /// The highest path cost (g-score) to explore, unlimited if `None`. Nodes
+    /// farther away aren't opened, and if the goal is one of them the search
+    /// fails with `SearchError::NoPathWithinRadius`. Unlike the other limits it
+    /// can't be raised to resume a search, as the pruned nodes are forgotten.
+    pub radius: Option<u32>,
+    /// The point in time after which the search stops. It is only checked every
+    /// few expansions, so the search may run a little past it.
+    #[cfg(feature = "std")]
+    pub deadline: Option<Instant>,
+}
+
+/// How many expansions happen between two progress events, with the `tracing` feature.
+#[cfg(feature = "tracing")]
+const TRACE_INTERVAL: usize = 1024;
+
+/// How many expansions happen between two looks at the clock.
+#[cfg(feature = "std")]
+const DEADLINE_CHECK_INTERVAL: usize = 16;
+
+impl SearchLimits {
+    fn check(&self, nodes_expanded: usize) -> Result<(), AbortReason> {
+        if self
+            .cancel
+            .as_ref()
+            .is_some_and(|cancel| cancel.load(AtomicOrdering::Relaxed))
+        {
+            return Err(AbortReason::Cancelled);
+        }
+        if self
+            .max_expanded
+            .is_some_and(|max_expanded| nodes_expanded >= max_expanded)
+        {
+            return Err(AbortReason::NodeLimit);
+        }
+        #[cfg(feature = "std")]
+        if nodes_expanded.is_multiple_of(DEADLINE_CHECK_INTERVAL)
+            && self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return Err(AbortReason::Deadline);
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SearchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            SearchError::StartOutOfBounds => "start is outside of the grid",
+            SearchError::GoalOutOfBounds => "goal is outside of the grid",
+            SearchError::StartBlocked => "start is on a solid cell",
+            SearchError::GoalBlocked => "goal is on a solid cell",
+            SearchError::NoPath => "goal is unreachable from start",
+            SearchError::NoPathWithinRadius => "goal is not within the search radius",
+            SearchError::Aborted(AbortReason::NodeLimit) => "search hit its node limit",
+            SearchError::Aborted(AbortReason::Cancelled) => "search was cancelled",
+            SearchError::Aborted(AbortReason::Deadline) => "search ran out of time",
+        };
+        f.write_str(message)
+    }
+}
+
+impl core::error::Error for SearchError {}
+
+/// What a single `SearchState::step` did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Step<N> {
+    /// `node` was closed and its successors in `opened` were added to the open
+    /// set (or got a cheaper path).
+    Expanded { node: N, opened: Vec<N> },
+    /// The goal was reached with the given path cost, see `SearchState::path`.
+    Found { cost: u32 },
+    /// The open set is empty, the goal is unreachable (or out of the
+    /// `SearchLimits::radius`).
+    Exhausted,
+    /// The search hit one of its `SearchLimits`.
+    Aborted(AbortReason),
+}
+
+/// Hooks called by the search as it runs, for logging, instrumentation or
+/// animation. Both methods do nothing by default and `()` is the observer used
+/// when none is given, so an unused observer compiles to nothing.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::graph::GridGraph;
+/// use pathfinding::manhattan_distance;
+/// use pathfinding::search::{Observer, SearchState};
+///
+/// #[derive(Default)]
+/// struct Log {
+///     expanded: Vec<((i32, i32), u32, u32)>,
+///     relaxed: usize,
+/// }
+///
+/// impl Observer<(i32, i32)> for Log {
+///     fn on_expand(&mut self, node: (i32, i32), g: u32, f: u32) {
+///         self.expanded.push((node, g, f));
+///     }
+///
+///     fn on_relax(&mut self, _from: (i32, i32), _to: (i32, i32)) {
+///         self.relaxed += 1;
+///     }
+/// }
+///
+/// let grid = vec![vec![0; 3]];
+/// let graph = GridGraph::new(&grid, |row, col, grid| grid[row][col] == 1);
+/// let mut log = Log::default();
+///
+/// SearchState::new(&graph, (0, 0), (0, 2), manhattan_distance)
+///     .with_observer(&mut log)
+///     .finish();
+///
+/// assert_eq!(log.expanded, vec![((0, 0), 0, 2), ((0, 1), 1, 2), ((0, 2), 2, 2)]);
+/// assert_eq!(log.relaxed, 2);
+/// ```
+pub trait Observer<N> {
+    /// Called when `node` is taken out of the open set, with its g- and f-score.
+    fn on_expand(&mut self, node: N, g: u32, f: u32) {
+        let _ = (node, g, f);
+    }
+
+    /// Called when a cheaper path to `to` through `from` is found.
+    fn on_relax(&mut self, from: N, to: N) {
+        let _ = (from, to);
+    }
+}
+
+impl<N> Observer<N> for () {}
+
+impl<N, O: Observer<N> + ?Sized> Observer<N> for &mut O {
+    fn on_expand(&mut self, node: N, g: u32, f: u32) {
+        (**self).on_expand(node, g, f);
+    }
+
+    fn on_relax(&mut self, from: N, to: N) {
+        (**self).on_relax(from, to);
+    }
+}
+
+/// Which node the search expands first among open nodes with the same f-score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TieBreak {
+    /// The node with the highest g-score, i.e. the one farthest from the start.
+    /// On open maps this dives straight to the goal instead of widening a
+    /// front of equally good nodes.
+    #[default]
+    HighG,
+    /// The node with the lowest g-score, i.e. the one closest to the start.
+    LowG,
+}
+
+impl TieBreak {
+    fn key(self, g_score: u32) -> u32 {
+        match self {
+            TieBreak::HighG => g_score,
+            TieBreak::LowG => u32::MAX - g_score,
+        }
+    }
+}
+
+/// An entry of the open set. Ordered so that the `BinaryHeap` (a max-heap) pops
+/// the lowest f-score first, then the highest `tie` (see `TieBreak::key`), then
+/// the node pushed first. No two entries compare equal, so the order of
+/// expansion never depends on how the heap arranges ties internally.
+struct OpenNode<N> {
+    f_score: u32,
+    tie: u32,
+    sequence: usize,
+    g_score: u32,
+    node: N,
+}
+
+impl<N> PartialEq for OpenNode<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N> Eq for OpenNode<N> {}
+
+impl<N> PartialOrd for OpenNode<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N> Ord for OpenNode<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .f_score
+            .cmp(&self.f_score)
+            .then(self.tie.cmp(&other.tie))
+            .then(other.sequence.cmp(&self.sequence))
+    }
+}
+
+/// A resumable A* search. Every call to `step` expands a single node, so the
+/// search can be spread over several frames and animated while it runs.
+///
+/// The search is deterministic: among open nodes with the same f-score and
+/// `TieBreak` key the one opened first is expanded first, so the same graph
+/// (with successors in the same order) always gives the same path, on every
+/// run and platform. Only `open_nodes` and `closed_nodes` iterate hash
+/// collections, nothing else depends on their order.
+///
+/// ```
+/// use pathfinding::astar;
+/// use pathfinding::manhattan_distance;
+///
+/// // Many shortest paths cross an open grid, the same one is always returned.
+/// let grid = vec![vec![0; 3]; 3];
+/// let path = astar((0, 0), (2, 2), &grid, manhattan_distance, |row, col, grid| grid[row][col] == 1);
+///
+/// assert_eq!(path.unwrap(), vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)]);
+/// ```
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::graph::GridGraph;
+/// use pathfinding::manhattan_distance;
+/// use pathfinding::search::{SearchState, Step};
+///
+/// let grid = vec![vec![0; 3]; 3];
+/// let graph = GridGraph::new(&grid, |row, col, grid| grid[row][col] == 1);
+/// let mut state = SearchState::new(&graph, (0, 0), (0, 2), manhattan_distance);
+///
+/// assert_eq!(
+///     state.step(),
+///     Step::Expanded { node: (0, 0), opened: vec![(1, 0), (0, 1)] }
+/// );
+/// while !state.is_finished() {
+///     state.step();
+/// }
+///
+/// assert_eq!(state.step(), Step::Found { cost: 2 });
+/// assert_eq!(state.path().unwrap(), vec![(0, 0), (0, 1), (0, 2)]);
+/// ```
+pub struct SearchState<G: Graph, H, O = ()> {
+    graph: G,
+    observer: O,
+    heuristic: H,
+    end: G::Node,
+    open_set_heap: BinaryHeap<OpenNode<G::Node>>,
+    closed_set: HashSet<G::Node>,
+    came_from: HashMap<G::Node, G::Node>,
+    g_score: HashMap<G::Node, u32>,
+    nodes_generated: usize,
+    found: Option<u32>,
+    limits: SearchLimits,
+    aborted: Option<AbortReason>,
+    closest: Option<(u32, u32, G::Node)>,
+    tie_break: TieBreak,
+    pruned: bool,
+}
+
+impl<G, H> SearchState<G, H>
+where
+    G: Graph,
+    H: Heuristic<G::Node>,
+{
+    /// Starts a search from `start` to `end`, nothing is expanded yet.
+    ///
+    /// ### Arguments
+    ///
+    /// * `graph` - The graph to search, owned or borrowed (`&G` is a graph too).
+    /// * `start` - The start node.
+    /// * `end` - The end node.
+    /// * `heuristic` - The heuristic, called as `heuristic.estimate(node, end)`.
+    pub fn new(graph: G, start: G::Node, end: G::Node, heuristic: H) -> Self {
+        Self::with_buffers(graph, start, end, heuristic, Buffers::default())
+    }
+
+    /// Starts a search reusing the collections of an earlier one, see `Searcher`.
+    fn with_buffers(
+        graph: G,
+        start: G::Node,
+        end: G::Node,
+        heuristic: H,
+        mut buffers: Buffers<G::Node>,
+    ) -> Self {
+        buffers.clear();
+        buffers.g_score.insert(start, 0);
+        buffers.open_set_heap.push(OpenNode {
+            f_score: heuristic.estimate(start, end),
+            tie: TieBreak::default().key(0),
+            sequence: 0,
+            g_score: 0,
+            node: start,
+        });
+
+        SearchState {
+            graph,
+            observer: (),
+            heuristic,
+            end,
+            open_set_heap: buffers.open_set_heap,
+            closed_set: buffers.closed_set,
+            came_from: buffers.came_from,
+            g_score: buffers.g_score,
+            nodes_generated: 1,
+            found: None,
+            limits: SearchLimits::default(),
+            aborted: None,
+            closest: None,
+            tie_break: TieBreak::default(),
+            pruned: false,
+        }
+    }
+}
+
+impl<G, H, O> SearchState<G, H, O>
+where
+    G: Graph,
+    H: Heuristic<G::Node>,
+    O: Observer<G::Node>,
+{
+    /// Replaces the observer notified of every expansion and relaxation.
+    pub fn with_observer<P: Observer<G::Node>>(self, observer: P) -> SearchState<G, H, P> {
+        SearchState {
+            graph: self.graph,
+            observer,
+            heuristic: self.heuristic,
+            end: self.end,
+            open_set_heap: self.open_set_heap,
+            closed_set: self.closed_set,
+            came_from: self.came_from,
+            g_score: self.g_score,
+            nodes_generated: self.nodes_generated,
+            found: self.found,
+            limits: self.limits,
+            aborted: self.aborted,
+            closest: self.closest,
+            tie_break: self.tie_break,
+            pruned: self.pruned,
+        }
+    }
+
+    /// Replaces the limits of the search. An aborted search can be resumed
+    /// by giving it new limits, e.g. a bigger node budget.
+    pub fn with_limits(mut self, limits: SearchLimits) -> Self {
+        self.limits = limits;
+        self.aborted = None;
+        self
+    }
+
+    /// Replaces the tie-breaking rule. It only applies to nodes opened from
+    /// now on, so call it before the first `step`.
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Expands the most promising open node.
+    ///
+    /// Once the search is finished, `step` keeps returning the final
+    /// `Step::Found`, `Step::Exhausted` or `Step::Aborted`.
+    pub fn step(&mut self) -> Step<G::Node> {
+        self.advance(true)
+    }
+
+    fn advance(&mut self, record: bool) -> Step<G::Node> {
+        if let Some(cost) = self.found {
+            return Step::Found { cost };
+        }
+        if let Err(reason) = self.limits.check(self.closed_set.len()) {
+            self.aborted = Some(reason);
+            return Step::Aborted(reason);
+        }
+        while let Some(OpenNode {
+            f_score: current_f_score,
+            g_score: current_g_score,
+            node: current,
+            ..
+        }) = self.open_set_heap.pop()
+        {
+            // A node may be pushed several times as shorter paths to it are found,
+            // only its first (cheapest) pop counts.
+            if !self.closed_set.insert(current) {
+                continue;
+            }
+            let estimate = (
+                current_f_score.saturating_sub(current_g_score),
+                current_g_score,
+            );
+            if self.closest.is_none_or(|(h, g, _)| estimate < (h, g)) {
+                self.closest = Some((estimate.0, estimate.1, current));
+            }
+            #[cfg(feature = "tracing")]
+            if self.closed_set.len().is_multiple_of(TRACE_INTERVAL) {
+                tracing::debug!(
+                    expanded = self.closed_set.len(),
+                    generated = self.nodes_generated,
+                    open = self.open_set_heap.len(),
+                    g = current_g_score,
+                    f = current_f_score,
+                    closest_h = self.closest.map(|(h, _, _)| h),
+                    "search progress"
+                );
+            }
+            if current == self.end {
+                self.observer
+                    .on_expand(current, current_g_score, current_f_score);
+                self.found = Some(current_g_score);
+                return Step::Found {
+                    cost: current_g_score,
+                };
+            }
+
+            self.observer
+                .on_expand(current, current_g_score, current_f_score);
+            let mut opened = vec![];
+            for (neighbor, cost) in self.graph.successors(current) {
+                if self.closed_set.contains(&neighbor) {
+                    continue;
+                }
+
+                let tentative_g_score = current_g_score.saturating_add(cost);
+                if self
+                    .limits
+                    .radius
+                    .is_some_and(|radius| tentative_g_score > radius)
+                {
+                    self.pruned = true;
+                    continue;
+                }
+                if self
+                    .g_score
+                    .get(&neighbor)
+                    .is_some_and(|&known| tentative_g_score >= known)
+                {
+                    continue;
+                }
+
+                self.observer.on_relax(current, neighbor);
+                self.came_from.insert(neighbor, current);
+                self.g_score.insert(neighbor, tentative_g_score);
+                self.open_set_heap.push(OpenNode {
+                    f_score: tentative_g_score
+                        .saturating_add(self.heuristic.estimate(neighbor, self.end)),
+                    tie: self.tie_break.key(tentative_g_score),
+                    sequence: self.nodes_generated,
+                    g_score: tentative_g_score,
+                    node: neighbor,
+                });
+                self.nodes_generated += 1;
+                if record {
+                    opened.push(neighbor);
+                }
+            }
+            return Step::Expanded {
+                node: current,
+                opened,
+            };
+        }
+        Step::Exhausted
+    }
+
+    /// Steps until the search is finished and returns the final step, keeping
+    /// the state around for inspection.
+    pub fn run(&mut self) -> Step<G::Node> {
+        loop {
+            match self.advance(false) {
+                Step::Expanded { .. } => continue,
+                last => return last,
+            }
+        }
+    }
+
+    /// Runs the search to its end and returns the result. `duration` only
+    /// covers the time spent in this call.
+    pub fn finish(mut self) -> SearchResult<G::Node> {
+        self.conclude(false)
+    }
+
+    /// Like `finish`, but when the goal turns out to be unreachable the path to
+    /// `closest_node` is returned instead of `SearchError::NoPath`, so an agent
+    /// can at least get as close as possible. Check the last node of the path
+    /// to know whether the goal was reached.
+    pub fn finish_best_effort(mut self) -> SearchResult<G::Node> {
+        self.conclude(true)
+    }
+
+    /// Like `finish`, but keeps the state, so what the search explored on the
+    /// way (`open_nodes`, `closed_nodes`, `g_score`) can be inspected next to
+    /// the result.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use pathfinding::graph::GridGraph;
+    /// use pathfinding::manhattan_distance;
+    /// use pathfinding::search::SearchState;
+    ///
+    /// let grid = vec![vec![0; 4]; 3];
+    /// let graph = GridGraph::new(&grid, |row, col, grid| grid[row][col] == 1);
+    /// let mut state = SearchState::new(&graph, (0, 0), (2, 3), manhattan_distance);
+    ///
+    /// let result = state.result();
+    /// assert_eq!(result.cost, Some(5));
+    /// assert_eq!(state.closed_nodes().count(), result.nodes_expanded);
+    /// assert!(state.open_nodes().all(|node| state.g_score(node).is_some()));
+    /// ```
+    pub fn result(&mut self) -> SearchResult<G::Node> {
+        self.conclude(false)
+    }
+
+    fn conclude(&mut self, best_effort: bool) -> SearchResult<G::Node> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("search", best_effort).entered();
+        #[cfg(feature = "std")]
+        let started = Instant::now();
+        let (path, cost) = match self.run() {
+            Step::Found { cost } => (Ok(self.build_path(self.end)), Some(cost)),
+            Step::Aborted(reason) => (Err(SearchError::Aborted(reason)), None),
+            _ => match self.closest_node().filter(|_| best_effort) {
+                Some(closest) => (Ok(self.build_path(closest)), self.g_score(closest)),
+                None if self.pruned => (Err(SearchError::NoPathWithinRadius), None),
+                None => (Err(SearchError::NoPath), None),
+            },
+        };
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            expanded = self.closed_set.len(),
+            generated = self.nodes_generated,
+            cost,
+            error = path.as_ref().err().map(tracing::field::display),
+            "search finished"
+        );
+        SearchResult {
+            path,
+            cost,
+            nodes_expanded: self.closed_set.len(),
+            nodes_generated: self.nodes_generated,
+            #[cfg(feature = "std")]
+            duration: started.elapsed(),
+            #[cfg(not(feature = "std"))]
+            duration: Duration::ZERO,
+        }
+    }
+
+    /// Gives back the collections of the search for reuse.
+    fn into_buffers(self) -> Buffers<G::Node> {
+        Buffers {
+            open_set_heap: self.open_set_heap,
+            closed_set: self.closed_set,
+            came_from: self.came_from,
+            g_score: self.g_score,
+        }
+    }
+
+    /// The number of nodes expanded so far.
+    pub fn nodes_expanded(&self) -> usize {
+        self.closed_set.len()
+    }
+
+    /// Whether the goal was found, the open set ran empty or a limit was hit.
+    pub fn is_finished(&self) -> bool {
+        self.found.is_some() || self.aborted.is_some() || self.open_set_heap.is_empty()
+    }
+
+    /// The path to the goal, once it has been found.
+    pub fn path(&self) -> Option<Path<G::Node>> {
+        self.found.map(|_| self.build_path(self.end))
+    }
+
+    /// The best path known so far from the start to `node`, if `node` has been reached.
+    pub fn path_to(&self, node: G::Node) -> Option<Path<G::Node>> {
+        self.g_score
+            .contains_key(&node)
+            .then(|| self.build_path(node))
+    }
+
+    fn build_path(&self, node: G::Node) -> Path<G::Node> {
+        let nodes = reconstruct_path(&self.came_from, node);
+        let costs = nodes.iter().map(|node| self.g_score[node]).collect();
+        Path::new(nodes, costs)
+    }
+
+    /// The cost of the best path known so far from the start to `node`.
+    pub fn g_score(&self, node: G::Node) -> Option<u32> {
+        self.g_score.get(&node).copied()
+    }
+
+    /// The nodes that were reached but not expanded yet, in no particular order.
+    pub fn open_nodes(&self) -> impl Iterator<Item = G::Node> + '_ {
+        self.g_score
+            .keys()
+            .filter(|&node| !self.closed_set.contains(node))
+            .copied()
+    }
+
+    /// The expanded node with the lowest heuristic estimate to the goal (the
+    /// cheaper one on ties), i.e. the closest the search got to the goal.
+    pub fn closest_node(&self) -> Option<G::Node> {
+        self.closest.map(|(_, _, node)| node)
+    }
+
+    /// The nodes that were already expanded, in no particular order.
+    pub fn closed_nodes(&self) -> impl Iterator<Item = G::Node> + '_ {
+        self.closed_set.iter().copied()
+    }
+}
+
+/// The collections a search fills, kept by `Searcher` between queries.
+struct Buffers<N> {
+    open_set_heap: BinaryHeap<OpenNode<N>>,
+    closed_set: HashSet<N>,
+    came_from: HashMap<N, N>,
+    g_score: HashMap<N, u32>,
+}
+
+impl<N> Default for Buffers<N> {
+    fn default() -> Self {
+        Buffers {
+            open_set_heap: BinaryHeap::new(),
+            closed_set: HashSet::new(),
+            came_from: HashMap::new(),
+            g_score: HashMap::new(),
+        }
+    }
+}
+
+impl<N> Buffers<N> {
+    /// Empties the collections, keeping their capacity.
+    fn clear(&mut self) {
+        self.open_set_heap.clear();
+        self.closed_set.clear();
+        self.came_from.clear();
+        self.g_score.clear();
+    }
+}
+
+/// A* that keeps its open set and hash maps between queries, so searching
+/// every frame doesn't allocate them over and over. After the first few
+/// queries the collections have grown to fit and no longer reallocate.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::graph::GridGraph;
+/// use pathfinding::manhattan_distance;
+/// use pathfinding::search::Searcher;
+///
+/// let grid = vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+/// ];
+/// let graph = GridGraph::new(&grid, |row, col, grid| grid[row][col] == 1);
+/// let mut searcher = Searcher::new(manhattan_distance);
+///
+/// for _frame in 0..3 {
+///     let result = searcher.find((0, 0), (0, 2), &graph);
+///     assert_eq!(result.path.unwrap(), vec![(0, 0), (1, 0), (1, 1), (1, 2), (0, 2)]);
+/// }
+/// ```
+pub struct Searcher<N, H> {
+    heuristic: H,
+    buffers: Buffers<N>,
+    limits: SearchLimits,
+}
+
+impl<N, H> Searcher<N, H>
+where
+    N: Copy + Eq + Hash,
+    H: Heuristic<N>,
+{
+    /// Creates a searcher using `heuristic` for all its queries.
+    pub fn new(heuristic: H) -> Self {
+        Searcher {
+            heuristic,
+            buffers: Buffers::default(),
+            limits: SearchLimits::default(),
+        }
+    }
+
+    /// Sets the limits applied to every query.
+    pub fn with_limits(mut self, limits: SearchLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Searches a path from `start` to `end` in `graph`, like `astar_graph_with_stats`.
+    pub fn find<G: Graph<Node = N>>(&mut self, start: N, end: N, graph: &G) -> SearchResult<N> {
+        let buffers = core::mem::take(&mut self.buffers);
+        let heuristic = |node: N, goal: N| self.heuristic.estimate(node, goal);
+        let mut state = SearchState::with_buffers(graph, start, end, heuristic, buffers)
+            .with_limits(self.limits.clone());
+        let result = state.conclude(false);
+        self.buffers = state.into_buffers();
+        result
+    }
+}
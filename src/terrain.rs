@@ -0,0 +1,134 @@
+//! Maps described by what is on the ground rather than by cost values: a
+//! `Grid<Terrain>`, and a `CostTable` saying what crossing every kind of
+//! terrain costs, turned into a `CostGrid` for `astar_weighted`.
+//!
+//! ### Example
+//!
+//! ```
+//! use pathfinding::graph::IMPASSABLE;
+//! use pathfinding::grid::Grid;
+//! use pathfinding::terrain::{CostTable, Terrain};
+//! use pathfinding::{astar_weighted, manhattan_distance};
+//!
+//! let world = Grid::from_ascii("=^^=\n=~~=\n.%%.", Terrain::from_char).unwrap().grid;
+//!
+//! // Walking, the forest is quicker than the long way around.
+//! let walker = CostTable::default();
+//! let path = astar_weighted((0, 0), (0, 3), &walker.costs(&world), manhattan_distance).unwrap();
+//! assert_eq!(path, vec![(0, 0), (0, 1), (0, 2), (0, 3)]);
+//!
+//! // A boat only sails on water, and lands on the roads.
+//! let boat = CostTable::uniform(IMPASSABLE)
+//!     .with(Terrain::Water, 1)
+//!     .with(Terrain::Road, 1);
+//! let path = astar_weighted((0, 0), (0, 3), &boat.costs(&world), manhattan_distance).unwrap();
+//! assert_eq!(path, vec![(0, 0), (1, 0), (1, 1), (1, 2), (1, 3), (0, 3)]);
+//! ```
+
+use crate::costgrid::CostGrid;
+use crate::graph::IMPASSABLE;
+use crate::grid::Grid;
+
+/// What a cell of the world is made of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Terrain {
+    /// `=` on ASCII maps.
+    Road,
+    /// `.` on ASCII maps.
+    #[default]
+    Grass,
+    /// `^` on ASCII maps.
+    Forest,
+    /// `%` on ASCII maps.
+    Swamp,
+    /// `~` on ASCII maps.
+    Water,
+    /// `#` on ASCII maps.
+    Wall,
+}
+
+impl Terrain {
+    /// Every kind of terrain.
+    pub const ALL: [Terrain; 6] = [
+        Terrain::Road,
+        Terrain::Grass,
+        Terrain::Forest,
+        Terrain::Swamp,
+        Terrain::Water,
+        Terrain::Wall,
+    ];
+
+    /// The terrain drawn as `character` on ASCII maps, `None` if there is
+    /// none. It is a mapping for `Grid::from_ascii`.
+    pub fn from_char(character: char) -> Option<Terrain> {
+        Terrain::ALL
+            .into_iter()
+            .find(|terrain| terrain.to_char() == character)
+    }
+
+    /// The character drawing the terrain on ASCII maps.
+    pub fn to_char(self) -> char {
+        match self {
+            Terrain::Road => '=',
+            Terrain::Grass => '.',
+            Terrain::Forest => '^',
+            Terrain::Swamp => '%',
+            Terrain::Water => '~',
+            Terrain::Wall => '#',
+        }
+    }
+}
+
+/// The cost of entering every kind of terrain, `IMPASSABLE` for the ones
+/// that can't be entered. Different agents (walkers, boats, ...) use
+/// different tables on the same world.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CostTable {
+    /// The costs, in the order of `Terrain::ALL`.
+    costs: [u32; 6],
+}
+
+/// The costs of a walker: roads `1`, grass `2`, forests `4` and swamps `8`,
+/// water and walls can't be entered.
+impl Default for CostTable {
+    fn default() -> Self {
+        CostTable {
+            costs: [1, 2, 4, 8, IMPASSABLE, IMPASSABLE],
+        }
+    }
+}
+
+impl CostTable {
+    /// A table where every terrain costs `cost`.
+    ///
+    /// ### Panics
+    ///
+    /// If `cost` is `0`.
+    pub fn uniform(cost: u32) -> Self {
+        assert!(cost > 0, "costs must be at least 1");
+        CostTable { costs: [cost; 6] }
+    }
+
+    /// Sets the cost of entering `terrain`.
+    ///
+    /// ### Panics
+    ///
+    /// If `cost` is `0`, free steps would make the heuristics overestimate.
+    pub fn with(mut self, terrain: Terrain, cost: u32) -> Self {
+        assert!(cost > 0, "costs must be at least 1");
+        self.costs[terrain as usize] = cost;
+        self
+    }
+
+    /// The cost of entering `terrain`.
+    pub fn cost(&self, terrain: Terrain) -> u32 {
+        self.costs[terrain as usize]
+    }
+
+    /// The costs of the cells of `world`, for `astar_weighted`.
+    pub fn costs(&self, world: &Grid<Terrain>) -> CostGrid {
+        CostGrid::new(world.map(|&terrain| self.cost(terrain)))
+            .expect("the costs of a table are at least 1")
+    }
+}
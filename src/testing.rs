@@ -0,0 +1,166 @@
+//! Helpers for testing code built on the searches: seeded grid generators and
+//! a reference search to compare path costs against.
+//!
+//! Grids use the `astar` convention: `0` is free and `1` is a wall.
+//!
+//! ### Example
+//!
+//! Checking `astar` against the reference on a few random maps:
+//!
+//! ```
+//! use pathfinding::astar;
+//! use pathfinding::manhattan_distance;
+//! use pathfinding::movement::MovementModel;
+//! use pathfinding::path::validate_path;
+//! use pathfinding::testing::{random_grid, reference_cost, Rng};
+//!
+//! let mut rng = Rng::new(7);
+//! for _ in 0..20 {
+//!     let grid = random_grid(&mut rng, 12, 8, 0.3);
+//!     let (start, end) = (rng.free_cell(&grid).unwrap(), rng.free_cell(&grid).unwrap());
+//!     let four_way = MovementModel::four_way();
+//!
+//!     let found = astar(start, end, &grid, manhattan_distance, |row, col, grid| grid[row][col] != 0);
+//!     match (found, reference_cost(&grid, start, end, &four_way)) {
+//!         (Ok(path), Some(best)) => {
+//!             assert_eq!(validate_path(&path, &grid, start, end, &four_way), Ok(best));
+//!         }
+//!         (Err(_), None) => {}
+//!         (found, best) => panic!("astar found {:?}, the reference {:?}", found, best),
+//!     }
+//! }
+//! ```
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::cell::Cell;
+use crate::graph::CellGraph;
+use crate::heuristic::Zero;
+use crate::movement::MovementModel;
+
+/// A small seeded pseudo-random number generator (SplitMix64), so generated
+/// maps are the same on every run and platform.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Creates a generator from a seed.
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    /// The next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..bound`, `bound` must not be `0`.
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// `true` with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    /// A random free cell of `grid`, `None` if there is none.
+    pub fn free_cell(&mut self, grid: &[Vec<i32>]) -> Option<(i32, i32)> {
+        let free: Vec<(i32, i32)> = grid
+            .iter()
+            .enumerate()
+            .flat_map(|(row, cells)| {
+                cells
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &cell)| cell == 0)
+                    .map(move |(col, _)| (row as i32, col as i32))
+            })
+            .collect();
+        (!free.is_empty()).then(|| free[self.below(free.len())])
+    }
+}
+
+/// A grid without walls.
+pub fn open_grid(width: usize, height: usize) -> Vec<Vec<i32>> {
+    vec![vec![0; width]; height]
+}
+
+/// A grid where every cell is a wall with probability `density`.
+pub fn random_grid(rng: &mut Rng, width: usize, height: usize, density: f64) -> Vec<Vec<i32>> {
+    (0..height)
+        .map(|_| (0..width).map(|_| rng.chance(density) as i32).collect())
+        .collect()
+}
+
+/// A perfect maze (exactly one path between any two free cells), carved by a
+/// randomized depth-first search. The rooms are the cells with an even row and
+/// column, the walls between them are opened, so with odd sizes the maze
+/// reaches all four borders.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::movement::MovementModel;
+/// use pathfinding::testing::{maze, reference_cost, Rng};
+///
+/// let grid = maze(&mut Rng::new(1), 9, 7);
+///
+/// assert!(reference_cost(&grid, (0, 0), (6, 8), &MovementModel::four_way()).is_some());
+/// ```
+pub fn maze(rng: &mut Rng, width: usize, height: usize) -> Vec<Vec<i32>> {
+    let mut grid = vec![vec![1; width]; height];
+    if width == 0 || height == 0 {
+        return grid;
+    }
+    grid[0][0] = 0;
+    let mut stack = vec![(0usize, 0usize)];
+    while let Some(&(row, col)) = stack.last() {
+        let mut options = Vec::new();
+        for (dr, dc) in [(-2i32, 0i32), (2, 0), (0, -2), (0, 2)] {
+            let (r, c) = (row as i32 + dr, col as i32 + dc);
+            if r >= 0
+                && c >= 0
+                && (r as usize) < height
+                && (c as usize) < width
+                && grid[r as usize][c as usize] == 1
+            {
+                options.push((r as usize, c as usize));
+            }
+        }
+        if options.is_empty() {
+            stack.pop();
+            continue;
+        }
+        let (r, c) = options[rng.below(options.len())];
+        grid[(row + r) / 2][(col + c) / 2] = 0;
+        grid[r][c] = 0;
+        stack.push((r, c));
+    }
+    grid
+}
+
+/// The cost of the shortest path from `start` to `end`, found by an exhaustive
+/// search (Dijkstra's algorithm on a `CellGraph`), `None` if there is no path.
+pub fn reference_cost<C: Cell>(
+    grid: &Vec<Vec<C>>,
+    start: (i32, i32),
+    end: (i32, i32),
+    movement: &MovementModel,
+) -> Option<u32> {
+    let graph = CellGraph::new(grid).with_movement(movement.clone());
+    let passable = |node| graph.get(node).is_some_and(Cell::passable);
+    if !passable(start) || !passable(end) {
+        return None;
+    }
+    crate::astar_graph(&graph, start, end, Zero)
+        .ok()
+        .map(|path| path.cost())
+}
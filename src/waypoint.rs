@@ -0,0 +1,275 @@
+//! Sparse waypoint graphs extracted from grids, for large open maps.
+//!
+//! Shortest paths around obstacles only turn at their corners, so a graph of
+//! the corners, linked when they see each other, holds the same paths as the
+//! grid with far fewer nodes. The paths found are any-angle: straight lines
+//! between waypoints rather than steps between neighboring cells.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::graph::{self, Graph};
+use crate::path::Path;
+use crate::search::SearchError;
+
+pub use crate::graph::COST_SCALE;
+
+/// The square of the straight-line distance between two cells, in
+/// `COST_SCALE` units.
+fn distance_squared(a: (i32, i32), b: (i32, i32)) -> u64 {
+    let dr = (a.0 - b.0).unsigned_abs() as u64 * COST_SCALE as u64;
+    let dc = (a.1 - b.1).unsigned_abs() as u64 * COST_SCALE as u64;
+    dr * dr + dc * dc
+}
+
+/// The straight-line distance between two cells, rounded down: the
+/// heuristic of the searches.
+fn distance(a: (i32, i32), b: (i32, i32)) -> u32 {
+    distance_squared(a, b).isqrt() as u32
+}
+
+/// The cost of the link between two cells, their distance rounded up so that
+/// a path never costs less than the heuristic says.
+fn link_cost(a: (i32, i32), b: (i32, i32)) -> u32 {
+    let squared = distance_squared(a, b);
+    let root = squared.isqrt();
+    (root + u64::from(root * root < squared)) as u32
+}
+
+/// Whether the straight line between the centers of cells `a` and `b` only
+/// crosses free cells. A line passing exactly through the corner between two
+/// cells needs both of them free, so it never clips an obstacle (like
+/// diagonal steps with `CornerCutting::Never`).
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::waypoint::line_of_sight;
+///
+/// let grid = vec![
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+/// ];
+/// let is_free = |row: i32, col: i32| grid[row as usize][col as usize] == 0;
+///
+/// assert!(line_of_sight((0, 0), (0, 2), is_free));
+/// assert!(!line_of_sight((0, 0), (2, 2), is_free));
+/// assert!(!line_of_sight((1, 0), (1, 2), is_free));
+/// ```
+pub fn line_of_sight<F>(a: (i32, i32), b: (i32, i32), is_free: F) -> bool
+where
+    F: Fn(i32, i32) -> bool,
+{
+    let (rows, cols) = ((b.0 - a.0).abs(), (b.1 - a.1).abs());
+    let (step_row, step_col) = ((b.0 - a.0).signum(), (b.1 - a.1).signum());
+    let (mut row, mut col) = a;
+    let (mut crossed_rows, mut crossed_cols) = (0, 0);
+    if !is_free(row, col) {
+        return false;
+    }
+    while crossed_rows < rows || crossed_cols < cols {
+        // Which cell border the line reaches first, scaled to integers.
+        let next = (1 + 2 * crossed_cols) * rows - (1 + 2 * crossed_rows) * cols;
+        if next == 0 {
+            if !is_free(row + step_row, col) || !is_free(row, col + step_col) {
+                return false;
+            }
+            row += step_row;
+            col += step_col;
+            crossed_rows += 1;
+            crossed_cols += 1;
+        } else if next < 0 {
+            col += step_col;
+            crossed_cols += 1;
+        } else {
+            row += step_row;
+            crossed_rows += 1;
+        }
+        if !is_free(row, col) {
+            return false;
+        }
+    }
+    true
+}
+
+/// The corners of the obstacles of a grid, linked to every corner they see.
+///
+/// A free cell is a waypoint when a solid cell touches it diagonally while
+/// both cells next to both of them are free, i.e. when a path may have to turn
+/// around it. The waypoints connect the same cells as eight-way steps with
+/// `CornerCutting::Never`, by paths no longer than theirs. As a `Graph` the
+/// nodes are the indices of the waypoints.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::waypoint::WaypointGraph;
+///
+/// let mut grid = vec![vec![0; 7]; 7];
+/// for row in 2..5 {
+///     for col in 2..5 {
+///         grid[row][col] = 1;
+///     }
+/// }
+/// let waypoints = WaypointGraph::new(&grid, |row, col, grid| grid[row][col] == 1);
+///
+/// assert_eq!(waypoints.waypoints(), &[(1, 1), (1, 5), (5, 1), (5, 5)]);
+///
+/// // Around the block over one of its corners on each side, about 2.24 + 4 + 2.24 cells.
+/// let path = waypoints.find_path((3, 0), (3, 6)).unwrap();
+/// assert_eq!(path.len(), 4);
+/// assert_eq!(path.cost(), 224 + 400 + 224);
+///
+/// // Like the other searches, a path to the start is the start alone.
+/// let path = waypoints.find_path((0, 0), (0, 0)).unwrap();
+/// assert_eq!((path.nodes(), path.cost()), (&[(0, 0)][..], 0));
+/// ```
+pub struct WaypointGraph<'a, F> {
+    grid: &'a Vec<Vec<i32>>,
+    is_solid: F,
+    waypoints: Vec<(i32, i32)>,
+    links: Vec<Vec<(usize, u32)>>,
+}
+
+impl<'a, F> WaypointGraph<'a, F>
+where
+    F: Fn(usize, usize, &Vec<Vec<i32>>) -> bool,
+{
+    /// Finds the waypoints of `grid` and links the ones that see each other.
+    pub fn new(grid: &'a Vec<Vec<i32>>, is_solid: F) -> Self {
+        let mut graph = WaypointGraph {
+            grid,
+            is_solid,
+            waypoints: vec![],
+            links: vec![],
+        };
+        for (row, cells) in grid.iter().enumerate() {
+            for col in 0..cells.len() {
+                let (row, col) = (row as i32, col as i32);
+                let corner = [(-1, -1), (-1, 1), (1, -1), (1, 1)]
+                    .iter()
+                    .any(|&(dr, dc)| {
+                        graph.is_free(row, col)
+                            && graph.is_free(row + dr, col)
+                            && graph.is_free(row, col + dc)
+                            && graph.is_solid_cell(row + dr, col + dc)
+                    });
+                if corner {
+                    graph.waypoints.push((row, col));
+                }
+            }
+        }
+        graph.links = (0..graph.waypoints.len())
+            .map(|from| {
+                graph
+                    .visible(graph.waypoints[from])
+                    .filter(|&(to, _)| to != from)
+                    .collect()
+            })
+            .collect();
+        graph
+    }
+
+    /// Whether `(row, col)` is a cell of the grid that can be entered.
+    fn is_free(&self, row: i32, col: i32) -> bool {
+        row >= 0
+            && col >= 0
+            && self
+                .grid
+                .get(row as usize)
+                .is_some_and(|cells| (col as usize) < cells.len())
+            && !(self.is_solid)(row as usize, col as usize, self.grid)
+    }
+
+    /// Whether `(row, col)` is a solid cell of the grid.
+    fn is_solid_cell(&self, row: i32, col: i32) -> bool {
+        row >= 0
+            && col >= 0
+            && self
+                .grid
+                .get(row as usize)
+                .is_some_and(|cells| (col as usize) < cells.len())
+            && (self.is_solid)(row as usize, col as usize, self.grid)
+    }
+
+    /// The waypoints seen from `cell`, with the costs of the links to them.
+    fn visible(&self, cell: (i32, i32)) -> impl Iterator<Item = (usize, u32)> + '_ {
+        self.waypoints
+            .iter()
+            .enumerate()
+            .filter(move |&(_, &waypoint)| {
+                line_of_sight(cell, waypoint, |row, col| self.is_free(row, col))
+            })
+            .map(move |(index, &waypoint)| (index, link_cost(cell, waypoint)))
+    }
+
+    /// The waypoints, row by row.
+    pub fn waypoints(&self) -> &[(i32, i32)] {
+        &self.waypoints
+    }
+
+    /// Searches an any-angle path from `start` to `end`, linking both to the
+    /// waypoints they see for the time of the search.
+    ///
+    /// ### Returns
+    ///
+    /// The `Path` of the cells where the path turns, `start` and `end`
+    /// included, with the costs in `COST_SCALE` units. If there is none, the
+    /// `SearchError` tells why, like `astar`.
+    pub fn find_path(
+        &self,
+        start: (i32, i32),
+        end: (i32, i32),
+    ) -> Result<Path<(i32, i32)>, SearchError> {
+        crate::check_endpoints(start, end, self.grid, &self.is_solid)?;
+        if start == end {
+            return Ok(Path::from_unit_steps(vec![start]));
+        }
+        // The start and the end are the nodes after the waypoints.
+        let (start_node, end_node) = (self.waypoints.len(), self.waypoints.len() + 1);
+        let position = |node: usize| match node {
+            node if node == start_node => start,
+            node if node == end_node => end,
+            node => self.waypoints[node],
+        };
+        let mut sees_end = vec![false; self.waypoints.len()];
+        for (waypoint, _) in self.visible(end) {
+            sees_end[waypoint] = true;
+        }
+        let direct = line_of_sight(start, end, |row, col| self.is_free(row, col));
+        let search = graph::from_fn(|node: usize| {
+            let mut successors: Vec<(usize, u32)> = if node == start_node {
+                self.visible(start).collect()
+            } else if node == end_node {
+                vec![]
+            } else {
+                self.links[node].clone()
+            };
+            let linked = match node {
+                node if node == start_node => direct,
+                node if node == end_node => false,
+                node => sees_end[node],
+            };
+            if linked {
+                successors.push((end_node, link_cost(position(node), end)));
+            }
+            successors
+        });
+        let path = crate::astar_graph(&search, start_node, end_node, |node: usize, _| {
+            distance(position(node), end)
+        })?;
+        Ok(path.map(position))
+    }
+}
+
+impl<F> Graph for WaypointGraph<'_, F>
+where
+    F: Fn(usize, usize, &Vec<Vec<i32>>) -> bool,
+{
+    type Node = usize;
+
+    fn successors(&self, waypoint: usize) -> Vec<(usize, u32)> {
+        self.links[waypoint].clone()
+    }
+}
@@ -0,0 +1,155 @@
+//! Grids placed in a larger world, addressed by world coordinates that may be
+//! negative: the cell `(0, 0)` of the grid sits at an origin, and every access
+//! and search translates between the two.
+
+use alloc::vec::Vec;
+
+use crate::cell::Cell;
+use crate::coord::Coord;
+use crate::graph::Graph;
+use crate::grid::{Grid, Rect};
+use crate::movement::MovementModel;
+
+/// A grid whose top left cell is at `origin` in world coordinates, e.g. a map
+/// centered on `(0, 0)`. The accessors take world coordinates; `grid` gives
+/// the grid itself, indexed from `(0, 0)` as usual.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::coord::Coord;
+/// use pathfinding::grid::{Grid, Rect};
+/// use pathfinding::world::WorldGrid;
+///
+/// let mut world = WorldGrid::new(Grid::new(5, 5), (-2, -2));
+/// world.set((0, 0), 1);
+///
+/// assert_eq!(world.grid().get(2, 2), Some(&1));
+/// assert_eq!(world.get((-2, 2)), Some(&0));
+/// assert_eq!(world.get((3, 0)), None);
+/// assert_eq!(world.to_local((-1, 0)), Some((1, 2)));
+/// assert_eq!(world.to_world(4, 4), Coord::new(2, 2));
+/// assert_eq!(world.bounds(), Some(Rect::new((-2, -2), (2, 2))));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WorldGrid<T> {
+    grid: Grid<T>,
+    origin: Coord,
+}
+
+impl<T> WorldGrid<T> {
+    /// Places `grid` with its top left cell at `origin`.
+    pub fn new(grid: Grid<T>, origin: impl Into<Coord>) -> Self {
+        WorldGrid {
+            grid,
+            origin: origin.into(),
+        }
+    }
+
+    /// The world coordinates of the top left cell.
+    pub fn origin(&self) -> Coord {
+        self.origin
+    }
+
+    /// The grid, indexed from its top left cell.
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    /// The grid, indexed from its top left cell, to edit it.
+    pub fn grid_mut(&mut self) -> &mut Grid<T> {
+        &mut self.grid
+    }
+
+    /// Unwraps the grid.
+    pub fn into_inner(self) -> Grid<T> {
+        self.grid
+    }
+
+    /// The `(row, col)` in the grid of the world cell `pos`, `None` if it is
+    /// outside of the grid.
+    pub fn to_local(&self, pos: impl Into<Coord>) -> Option<(usize, usize)> {
+        let (row, col) = (pos.into() - self.origin).to_usize()?;
+        (row < self.grid.height && col < self.grid.width).then_some((row, col))
+    }
+
+    /// The world coordinates of the cell at `row`, `col` of the grid.
+    pub fn to_world(&self, row: usize, col: usize) -> Coord {
+        self.origin + Coord::new(row as i32, col as i32)
+    }
+
+    /// The cells of the grid in world coordinates, `None` if it is empty.
+    pub fn bounds(&self) -> Option<Rect> {
+        if self.grid.width == 0 || self.grid.height == 0 {
+            return None;
+        }
+        let last = self.to_world(self.grid.height - 1, self.grid.width - 1);
+        Some(Rect::new(self.origin.into(), last.into()))
+    }
+
+    /// The world cell `pos`, `None` if it is outside of the grid.
+    pub fn get(&self, pos: impl Into<Coord>) -> Option<&T> {
+        let (row, col) = self.to_local(pos)?;
+        self.grid.get(row, col)
+    }
+
+    /// Replaces the world cell `pos` and returns the old value, `None` if it
+    /// is outside of the grid.
+    pub fn set(&mut self, pos: impl Into<Coord>, value: T) -> Option<T> {
+        let (row, col) = self.to_local(pos)?;
+        self.grid.set(row, col, value)
+    }
+}
+
+/// Adapter searching a `WorldGrid` of `Cell`s in world coordinates: the nodes
+/// are world cells, the paths found too.
+///
+/// ### Example
+///
+/// ```
+/// use pathfinding::grid::{ascii_walls, Grid};
+/// use pathfinding::world::{WorldGraph, WorldGrid};
+/// use pathfinding::{astar_graph, manhattan_distance};
+///
+/// let grid = Grid::from_ascii("...\n.#.\n...", ascii_walls).unwrap().grid;
+/// let world = WorldGrid::new(grid, (-1, -1));
+///
+/// let path = astar_graph(&WorldGraph::new(&world), (-1, 0), (1, 0), manhattan_distance).unwrap();
+/// assert_eq!(path.len(), 5);
+/// assert!(!path.contains(&(0, 0)));
+/// ```
+pub struct WorldGraph<'a, T> {
+    world: &'a WorldGrid<T>,
+    movement: MovementModel,
+}
+
+impl<'a, T: Cell> WorldGraph<'a, T> {
+    /// Creates the adapter from a world grid, moving up/down/left/right.
+    pub fn new(world: &'a WorldGrid<T>) -> Self {
+        WorldGraph {
+            world,
+            movement: MovementModel::four_way(),
+        }
+    }
+
+    /// Replaces the movement model, e.g. to allow diagonal steps.
+    pub fn with_movement(mut self, movement: MovementModel) -> Self {
+        self.movement = movement;
+        self
+    }
+}
+
+impl<T: Cell> Graph for WorldGraph<'_, T> {
+    type Node = (i32, i32);
+
+    fn successors(&self, node: (i32, i32)) -> Vec<((i32, i32), u32)> {
+        let origin = self.world.origin;
+        let local = Coord::from(node) - origin;
+        self.world
+            .grid
+            .neighbors(local.into(), &self.movement)
+            .map(|(cell, cost)| ((Coord::from(cell) + origin).into(), cost))
+            .collect()
+    }
+}